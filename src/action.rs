@@ -1,26 +1,43 @@
 use crate::actions::{
-    clean::CleanAction, config::ConfigAction, help::HelpAction, install::InstallAction,
-    list::ListAction, reinstall::ReinstallAction, remove::RemoveAction, update::UpdateAction,
+    adopt::AdoptAction, clean::CleanAction, completions::CompletionsAction, config::ConfigAction,
+    doctor::DoctorAction, export::ExportAction, help::HelpAction, info::InfoAction,
+    install::InstallAction, installer::InstallerAction, list::ListAction, lock::LockAction,
+    owns::OwnsAction, reinstall::ReinstallAction, remove::RemoveAction, repo::RepoAction,
+    search::SearchAction, sync::SyncAction, update::UpdateAction, verify::VerifyAction,
     version::VersionAction,
 };
+use crate::json;
 use crate::paths::{cache_dir, lock_file_path};
 
 use crate::Args;
 use crate::Font;
 use crate::InstalledFonts;
+use crate::suggest;
 
 use std::fs;
 use std::sync::{Arc, Mutex};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
     Install,
     Reinstall,
     Update,
     Remove,
+    Adopt,
     List,
+    Info,
+    Search,
+    Verify,
+    Owns,
+    Sync,
+    Export,
+    Lock,
     Clean,
+    Doctor,
     Config,
+    Repo,
+    Installer,
+    Completions,
     Version,
     Help,
 }
@@ -35,34 +52,113 @@ Actions:
     reinstall             Reinstall fonts
     update                Update installed fonts
     remove                Remove installed fonts
+    adopt                 Track fonts which were installed manually
     list                  List installed or available fonts
+    info                  Show details about fonts
+    search                Search available fonts
+    verify                Check installed fonts for changes
+    owns                  Show which font installed a file
+    sync                  Install the fonts listed by a manifest
+    export                Write installed fonts to a manifest
+    lock                  Lock the fonts of a manifest to exact downloads
     clean                 Remove temporary cache files
+    doctor                Check for problems and fix them
     config                Manage the configuration file
+    repo                  Manage installer repositories
+    installer             Create new installers
+    completions           Print shell completion scripts
     version               Show the current version number
     help                  Show help for any action
 "
     }
 
+    /// Every action, in the same order as `help_actions`
+    pub const ALL: [Action; 21] = [
+        Action::Install,
+        Action::Reinstall,
+        Action::Update,
+        Action::Remove,
+        Action::Adopt,
+        Action::List,
+        Action::Info,
+        Action::Search,
+        Action::Verify,
+        Action::Owns,
+        Action::Sync,
+        Action::Export,
+        Action::Lock,
+        Action::Clean,
+        Action::Doctor,
+        Action::Config,
+        Action::Repo,
+        Action::Installer,
+        Action::Completions,
+        Action::Version,
+        Action::Help,
+    ];
+
+    /// Returns the names the action can be called by, starting with its main name
+    #[must_use]
+    pub const fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Action::Install => &["install", "get"],
+            Action::Reinstall => &["reinstall"],
+            Action::Update => &["update", "upgrade", "up"],
+            Action::Remove => &["remove", "uninstall", "rm"],
+            Action::Adopt => &["adopt"],
+            Action::List => &["list", "ls"],
+            Action::Info => &["info", "show"],
+            Action::Search => &["search", "find"],
+            Action::Verify => &["verify", "check"],
+            Action::Owns => &["owns"],
+            Action::Sync => &["sync"],
+            Action::Export => &["export"],
+            Action::Lock => &["lock"],
+            Action::Clean => &["clean", "clear"],
+            Action::Doctor => &["doctor"],
+            Action::Config => &["config", "cfg"],
+            Action::Repo => &["repo", "repos"],
+            Action::Installer => &["installer"],
+            Action::Completions => &["completions"],
+            Action::Version => &["version", "ver", "v"],
+            Action::Help => &["help", "h"],
+        }
+    }
+
+    /// Returns the fixed items accepted by the action (see each action's help)
+    #[must_use]
+    pub const fn items(&self) -> &'static [&'static str] {
+        match self {
+            Action::List => ListAction::ITEMS,
+            Action::Clean => CleanAction::ITEMS,
+            Action::Config => ConfigAction::ITEMS,
+            Action::Repo => RepoAction::ITEMS,
+            Action::Installer => InstallerAction::ITEMS,
+            Action::Completions => CompletionsAction::ITEMS,
+            _ => &[],
+        }
+    }
+
     pub fn parse(action: Option<&String>) -> Result<Self, String> {
-        Ok(match action {
-            Some(a) => match a.as_str() {
-                "install" | "get" => Action::Install,
-                "reinstall" => Action::Reinstall,
-                "update" | "upgrade" | "up" => Action::Update,
-                "remove" | "uninstall" | "rm" => Action::Remove,
-                "list" | "ls" => Action::List,
-                "clean" | "clear" => Action::Clean,
-                "config" | "cfg" => Action::Config,
-                "version" | "ver" | "v" => Action::Version,
-                "help" | "h" => Action::Help,
-                _ => {
-                    HelpAction::run(&Action::Help);
-                    println!();
-                    return Err(format!("Unrecognized action: {a}"));
-                }
-            },
-            None => Action::Help,
-        })
+        let Some(action) = action else {
+            return Ok(Action::Help);
+        };
+
+        match Self::ALL
+            .iter()
+            .find(|a| a.aliases().contains(&action.as_str()))
+        {
+            Some(action) => Ok(action.clone()),
+            None => {
+                HelpAction::run(&Action::Help);
+                println!();
+                let aliases = Self::ALL.iter().flat_map(Action::aliases).copied();
+                Err(match suggest(action, aliases) {
+                    Some(alias) => format!("Unrecognized action: {action} (did you mean `{alias}`?)"),
+                    None => format!("Unrecognized action: {action}"),
+                })
+            }
+        }
     }
 }
 
@@ -72,6 +168,17 @@ pub fn perform(
     lock_state: Option<&String>,
     installed_fonts: &Arc<Mutex<InstalledFonts>>,
 ) -> Result<(), String> {
+    let lock = |lock_action: &str| {
+        if let Some(lock_state) = lock_state {
+            println!("Install state is locked; refusing to continue");
+            println!("It looks like another instance is currently {lock_state} something");
+            println!("If this is not the case, you may run `fin clean state --force` to manually unlock it");
+            panic!("Lock file exists; refusing to continue");
+        }
+
+        let _ = fs::create_dir_all(cache_dir());
+        let _ = fs::write(lock_file_path(), lock_action);
+    };
     let init_fonts = |require_valid_config: bool,
                       lock_action: Option<&str>,
                       no_fonts_message: &str|
@@ -81,15 +188,7 @@ pub fn perform(
         }
 
         if let Some(lock_action) = lock_action {
-            if let Some(lock_state) = lock_state {
-                println!("Install state is locked; refusing to continue");
-                println!("It looks like another instance is currently {lock_state} something");
-                println!("If this is not the case, you may run `fin clean state --force` to manually unlock it");
-                panic!("Lock file exists; refusing to continue");
-            }
-
-            let _ = fs::create_dir_all(cache_dir());
-            let _ = fs::write(lock_file_path(), lock_action);
+            lock(lock_action);
         }
 
         let fonts: Box<[Font]> =
@@ -102,6 +201,9 @@ pub fn perform(
             if !no_fonts_message.is_empty() {
                 println!("{no_fonts_message}");
             }
+            if args.options.json {
+                json::emit::<[()]>(&[]);
+            }
             None
         } else {
             Some(fonts)
@@ -135,14 +237,41 @@ pub fn perform(
             };
             RemoveAction::run(args, &fonts, installed_fonts)?;
         }
+        Action::Adopt => {
+            let Some(mut fonts) = init_fonts(true, Some("adopting"), "Nothing to adopt") else {
+                return Ok(());
+            };
+            AdoptAction::run(args, &mut fonts, installed_fonts)?;
+        }
         Action::List => {
             let Some(fonts) = init_fonts(false, None, "") else {
                 return Ok(());
             };
             ListAction::run(args, &fonts, Arc::clone(installed_fonts));
         }
+        Action::Verify => {
+            let lock_action = args.options.repair.then_some("repairing");
+            let Some(fonts) = init_fonts(true, lock_action, "Nothing to verify") else {
+                return Ok(());
+            };
+            VerifyAction::run(args, &fonts, installed_fonts)?;
+        }
+        Action::Sync => {
+            args.config.panic_if_invalid();
+            lock("syncing");
+            SyncAction::run(args, items, installed_fonts)?;
+        }
+        Action::Owns => OwnsAction::run(items, installed_fonts)?,
+        Action::Export => ExportAction::run(args, items, installed_fonts)?,
+        Action::Lock => LockAction::run(args, items)?,
+        Action::Info => InfoAction::run(args, items, installed_fonts)?,
+        Action::Search => SearchAction::run(items, installed_fonts)?,
         Action::Clean => CleanAction::run(args, items, lock_state)?,
+        Action::Doctor => DoctorAction::run(args, lock_state, installed_fonts)?,
         Action::Config => ConfigAction::run(items)?,
+        Action::Repo => RepoAction::run(items)?,
+        Action::Installer => InstallerAction::run(args, items)?,
+        Action::Completions => CompletionsAction::run(items, installed_fonts)?,
         Action::Version => VersionAction::run(),
         Action::Help => {
             let action = Action::parse(items.iter().next())?;
@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex};
+
+use crate::args::Args;
+use crate::font::Font;
+use crate::installed::InstalledFonts;
+use crate::installer::Installer;
+use crate::user_prompt;
+
+pub struct AdoptAction;
+
+impl AdoptAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Track fonts which were copied into the installation directory manually
+    Compares their files with the release, without copying anything
+
+Usage:
+    fin adopt [font]
+    fin adopt [font]:[tag]
+    fin adopt [font]@[variant]:[tag]
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        args: &Args,
+        fonts: &mut Box<[Font]>,
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        println!("Adopting: ");
+        Args::list_fonts_green(fonts);
+
+        if !user_prompt("Proceed?", args) {
+            return Ok(());
+        }
+
+        let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+        let mut errors = Vec::new();
+        for font in fonts.iter_mut() {
+            let Some(installer) = &mut font.installer else {
+                errors.push(format!("{font}: {}", red!("Installer has not been loaded")));
+                continue;
+            };
+            if let Err(e) = adopt_font(args, &runtime, installer, installed_fonts) {
+                println!("\nFailed to adopt {}:\n{}", installer.name, red!(&e));
+                errors.push(format!("{font}: {}", red!(&e)));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            println!();
+            errors.iter().for_each(|e| println!("{e}"));
+            Err("One or more fonts failed to be adopted".to_string())
+        }
+    }
+}
+
+/// Stages the release of the font, and records the font as installed
+/// from the files already within its directory
+fn adopt_font(
+    args: &Args,
+    runtime: &tokio::runtime::Runtime,
+    installer: &mut Installer,
+    installed_fonts: &Arc<Mutex<InstalledFonts>>,
+) -> Result<(), String> {
+    let url = installer.source.ref_direct_url()?;
+    match args.options.verbose || args.config.verbose_urls {
+        true => println!("\n{} ({}): ", installer.name, url),
+        false => println!("\n{}:", installer.name),
+    }
+    let (differing, missing) = runtime.block_on(async {
+        installer
+            .download_font()
+            .await?
+            .verify_download()?
+            .prepare_install(args)?
+            .adopt(args, installed_fonts)
+    })?;
+
+    let total = installer.files.len();
+    let matching = total - differing.len() - missing.len();
+    match matching == total {
+        true => println_green!("All {total} files match the release"),
+        false => {
+            println_orange!("{matching} of {total} files match the release, the others differ:");
+            differing
+                .iter()
+                .for_each(|file| println_orange!("   ~ {file} (modified)"));
+            missing
+                .iter()
+                .for_each(|file| println_red!("   - {file} (missing)"));
+            let name = &installer.installer_name;
+            println!("Note: run `fin reinstall {name}` to replace them with the release");
+        }
+    }
+    Ok(())
+}
@@ -1,11 +1,16 @@
 use crate::paths::{cache_dir, lock_file_path, page_cache_dir, staging_dir};
 use std::fs;
+use std::path::Path;
 
 use crate::args::Args;
+use crate::format_size;
+use crate::installed::list_files;
 
 pub struct CleanAction;
 
 impl CleanAction {
+    pub const ITEMS: &[&str] = &["all", "pages", "staging", "state", "help"];
+
     pub fn show_help() -> String {
         let help = "\
 Action:
@@ -15,6 +20,7 @@ Action:
 Usage:
     fin clean
     fin clean [item]
+    fin clean [item] --dry-run
 
 Items:
     all                   Remove all cache
@@ -28,7 +34,7 @@ Items:
     }
 
     pub fn run(args: &Args, items: &[String], lock_state: Option<&String>) -> Result<(), String> {
-        if lock_state.is_some() && !args.options.force {
+        if lock_state.is_some() && !args.options.force && !args.options.dry_run {
             println!("Cleaning the cache while another instance is running is not recommended");
             println!("Note: try passing `--force` to clean it anyway");
             return Err("Attempted to alter cache while another instance was running".to_string());
@@ -39,36 +45,31 @@ Items:
             false => items,
         };
 
+        // Shows what would be removed instead when passing `--dry-run`
+        let remove = |target: &str, description: &str| -> Result<(), String> {
+            if !fs::exists(target).unwrap_or(true) {
+                return Ok(());
+            }
+            if args.options.dry_run {
+                let size = format_size(path_size(target) as f64);
+                println!("Would remove {description}: {target} ({size})");
+                return Ok(());
+            }
+            match Path::new(target).is_dir() {
+                true => fs::remove_dir_all(target),
+                false => fs::remove_file(target),
+            }
+            .map_err(|e| e.to_string())?;
+            println!("Removed {description}: {target}");
+            Ok(())
+        };
+
         for item in items {
             match item.as_str() {
-                "all" => {
-                    let target = cache_dir();
-                    if fs::exists(target).unwrap_or(true) {
-                        fs::remove_dir_all(target).map_err(|e| e.to_string())?;
-                        println!("Removed the cache directory: {target}");
-                    }
-                }
-                "pages" => {
-                    let target = page_cache_dir();
-                    if fs::exists(target).unwrap_or(true) {
-                        fs::remove_dir_all(target).map_err(|e| e.to_string())?;
-                        println!("Removed the page cache directory: {target}");
-                    }
-                }
-                "staging" => {
-                    let target = staging_dir();
-                    if fs::exists(target).unwrap_or(true) {
-                        fs::remove_dir_all(target).map_err(|e| e.to_string())?;
-                        println!("Removed the staging directory: {target}");
-                    }
-                }
-                "state" => {
-                    let target = lock_file_path();
-                    if fs::exists(target).unwrap_or(true) {
-                        fs::remove_file(target).map_err(|e| e.to_string())?;
-                        println!("Removed the lock file: {target}");
-                    }
-                }
+                "all" => remove(cache_dir(), "the cache directory")?,
+                "pages" => remove(page_cache_dir(), "the page cache directory")?,
+                "staging" => remove(staging_dir(), "the staging directory")?,
+                "state" => remove(lock_file_path(), "the lock file")?,
                 "help" => {
                     Self::show_help();
                 }
@@ -81,3 +82,15 @@ Items:
         Ok(())
     }
 }
+
+/// Returns the size of the file, or of every file within the directory
+fn path_size(path: &str) -> u64 {
+    let size = |path: &str| fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0);
+    match Path::new(path).is_dir() {
+        true => list_files(path)
+            .iter()
+            .map(|file| size(&format!("{path}/{file}")))
+            .sum(),
+        false => size(path),
+    }
+}
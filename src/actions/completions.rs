@@ -0,0 +1,490 @@
+use crate::action::Action;
+use crate::font_page::read_cached_pages;
+use crate::installed::InstalledFonts;
+use crate::installer::Installer;
+use crate::options::{Argument, Options};
+use crate::repos;
+use crate::template;
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+pub struct CompletionsAction;
+
+/// Where the completions of an action's items come from
+enum Items {
+    /// Names of the available installers
+    Installers,
+    /// Names of the installed fonts
+    Installed,
+    /// The action's fixed items (see `Action::items`)
+    Fixed(String),
+    None,
+}
+
+impl CompletionsAction {
+    pub const ITEMS: &[&str] = &["bash", "zsh", "fish", "help"];
+
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Print shell completion scripts
+
+Usage:
+    fin completions [shell]
+
+Items:
+    bash                  Print the bash completion script
+    zsh                   Print the zsh completion script
+    fish                  Print the fish completion script
+    help                  Show this help message
+
+Installation:
+    bash                  fin completions bash > ~/.local/share/bash-completion/completions/fin
+    zsh                   fin completions zsh > \"${fpath[1]}/_fin\"
+    fish                  fin completions fish > ~/.config/fish/completions/fin.fish
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        items: &[String],
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        let Some(item) = items.first() else {
+            Self::show_help();
+            return Ok(());
+        };
+
+        match item.as_str() {
+            "bash" => print!("{}", Self::bash()),
+            "zsh" => print!("{}", Self::zsh()),
+            "fish" => print!("{}", Self::fish()),
+            "help" => {
+                Self::show_help();
+            }
+
+            // Used by the completion scripts
+            "installers" => Installer::filter_installers(&["*".to_string()])?
+                .iter()
+                .for_each(|installer| println!("{installer}")),
+            "installed" => installed_fonts
+                .lock()
+                .unwrap()
+                .get_names()
+                .iter()
+                .for_each(|font| println!("{font}")),
+            "tags" => items[1..]
+                .iter()
+                .flat_map(|font| Self::tags(font).into_iter().map(move |tag| (font, tag)))
+                .for_each(|(font, tag)| println!("{font}:{tag}")),
+
+            shell => {
+                Self::show_help();
+                println!();
+                return Err(format!("Unsupported shell: {shell}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the known tags of the font: its default tag, and the
+    /// tags of the GitHub releases found within the page cache
+    fn tags(font: &str) -> BTreeSet<String> {
+        let mut tags = BTreeSet::new();
+        if font.contains(['/', '\\']) || font.starts_with('.') {
+            return tags;
+        }
+
+        let Some(source) = repos::find_installer(font)
+            .and_then(|(dir, _)| template::read(&dir, font).ok())
+            .and_then(|installer| installer.parse::<toml::Table>().ok())
+            .and_then(|mut installer| installer.remove("source"))
+            .and_then(|source| {
+                source
+                    .as_table()
+                    .and_then(|s| s.iter().next())
+                    .map(|(k, v)| (k.clone(), v.clone()))
+            })
+        else {
+            return tags;
+        };
+
+        let (kind, fields) = source;
+        if let Some(tag) = fields.get("tag").and_then(toml::Value::as_str) {
+            tags.insert(tag.to_owned());
+        }
+
+        if kind == "GitHub"
+            && let (Some(author), Some(project)) = (
+                fields.get("author").and_then(toml::Value::as_str),
+                fields.get("project").and_then(toml::Value::as_str),
+            )
+        {
+            tags.insert("latest".to_owned());
+            let releases = format!("https://api.github.com/repos/{author}/{project}/releases");
+            for page in read_cached_pages(&releases) {
+                let Ok(page) = serde_json::from_str::<serde_json::Value>(&page) else {
+                    continue;
+                };
+                let releases = match page.as_array() {
+                    Some(releases) => releases.iter().collect(),
+                    None => vec![&page],
+                };
+                releases
+                    .into_iter()
+                    .filter_map(|release| release["tag_name"].as_str())
+                    .for_each(|tag| {
+                        tags.insert(format!("tags/{tag}"));
+                    });
+            }
+        }
+
+        tags
+    }
+
+    fn items(action: &Action) -> Items {
+        match action {
+            Action::Install | Action::Info => Items::Installers,
+            Action::Reinstall | Action::Update | Action::Remove | Action::Verify => {
+                Items::Installed
+            }
+            Action::Help => Items::Fixed(
+                Action::ALL
+                    .iter()
+                    .map(|action| action.aliases()[0])
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            action if !action.items().is_empty() => Items::Fixed(action.items().join(" ")),
+            _ => Items::None,
+        }
+    }
+
+    /// Returns the description of the action from `Action::help_actions`
+    fn description(action: &Action) -> &'static str {
+        let name = action.aliases()[0];
+        Action::help_actions()
+            .lines()
+            .find_map(|line| {
+                let (action, description) = line.trim().split_once(' ')?;
+                (action == name).then(|| description.trim())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the actions which accept a `name:tag` version override
+    fn tag_actions() -> String {
+        [Action::Install, Action::Reinstall, Action::Info]
+            .iter()
+            .flat_map(Action::aliases)
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the words which complete the argument
+    fn argument_words(argument: &Argument) -> Vec<String> {
+        let mut words = vec![match argument.value {
+            Some(_) => format!("{}=", argument.long),
+            None => argument.long.to_owned(),
+        }];
+        if let Some(short) = argument.short {
+            words.push(format!("-{short}"));
+        }
+        words
+    }
+
+    /// Returns the names of the arguments which take their value from the
+    /// following word, when not specified using `=`
+    fn value_arguments() -> Vec<String> {
+        Options::ARGUMENTS
+            .iter()
+            .filter(|argument| argument.value.is_some())
+            .flat_map(|argument| {
+                let short = argument.short.map(|short| format!("-{short}"));
+                [Some(argument.long.to_owned()), short]
+                    .into_iter()
+                    .flatten()
+            })
+            .collect()
+    }
+
+    /// Returns the aliases of the actions which accept the argument,
+    /// or `None` if every action accepts it
+    fn argument_actions(argument: &Argument) -> Option<Vec<&'static str>> {
+        (!argument.actions.is_empty()).then(|| {
+            argument
+                .actions
+                .iter()
+                .flat_map(Action::aliases)
+                .copied()
+                .collect()
+        })
+    }
+
+    #[must_use]
+    pub fn bash() -> String {
+        let actions = Action::ALL
+            .iter()
+            .map(|action| action.aliases()[0])
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut arguments = Vec::new();
+        let mut argument_cases = String::new();
+        for argument in Options::ARGUMENTS {
+            let words = Self::argument_words(argument).join(" ");
+            match Self::argument_actions(argument) {
+                Some(actions) => {
+                    argument_cases += &format!(
+                        "        case \"$action\" in\n            {}|\"\") candidates+=\" {words}\" ;;\n        esac\n",
+                        actions.join("|")
+                    );
+                }
+                None => arguments.push(words),
+            }
+        }
+
+        let mut cases = String::new();
+        for action in &Action::ALL {
+            let words = match Self::items(action) {
+                Items::Installers => "$(fin completions installers 2>/dev/null)".to_owned(),
+                Items::Installed => "$(fin completions installed 2>/dev/null)".to_owned(),
+                Items::Fixed(items) => items,
+                Items::None => continue,
+            };
+            cases += &format!(
+                "            {}) candidates=\"{words}\" ;;\n",
+                action.aliases().join("|")
+            );
+        }
+
+        format!(
+            r#"# bash completion for fin
+
+_fin() {{
+    local cur words cword
+    if declare -F _get_comp_words_by_ref >/dev/null; then
+        _get_comp_words_by_ref -n =: cur words cword
+    else
+        cur="${{COMP_WORDS[COMP_CWORD]}}"
+        words=("${{COMP_WORDS[@]}}")
+        cword=$COMP_CWORD
+    fi
+
+    local action="" i
+    for ((i = 1; i < cword; i++)); do
+        case "${{words[i]}}" in
+            {value_arguments}) ((i++)) ;;
+            -*) ;;
+            *)
+                action="${{words[i]}}"
+                break
+                ;;
+        esac
+    done
+
+    local candidates=""
+    if [[ $cur == -* ]]; then
+        candidates="{arguments}"
+{argument_cases}    elif [[ -z $action ]]; then
+        candidates="{actions}"
+    elif [[ $cur == *:* ]]; then
+        case "$action" in
+            {tag_actions}) candidates="$(fin completions tags "${{cur%%:*}}" 2>/dev/null)" ;;
+        esac
+    else
+        case "$action" in
+{cases}        esac
+    fi
+
+    COMPREPLY=($(compgen -W "$candidates" -- "$cur"))
+    if [[ ${{#COMPREPLY[@]}} -eq 1 && ${{COMPREPLY[0]}} == *= ]]; then
+        compopt -o nospace
+    fi
+    if declare -F __ltrim_colon_completions >/dev/null; then
+        __ltrim_colon_completions "$cur"
+    fi
+}}
+
+complete -F _fin fin
+"#,
+            arguments = arguments.join(" "),
+            value_arguments = Self::value_arguments().join("|"),
+            tag_actions = Self::tag_actions().replace(' ', "|"),
+        )
+    }
+
+    #[must_use]
+    pub fn zsh() -> String {
+        let actions = Action::ALL
+            .iter()
+            .map(|action| {
+                format!(
+                    "            '{}:{}'\n",
+                    action.aliases()[0],
+                    Self::description(action)
+                )
+            })
+            .collect::<String>();
+
+        let mut arguments = String::new();
+        let mut argument_cases = String::new();
+        for argument in Options::ARGUMENTS {
+            let candidates = Self::argument_words(argument)
+                .iter()
+                .map(|word| format!("'{word}:{}'", argument.description))
+                .collect::<Vec<_>>();
+            match Self::argument_actions(argument) {
+                Some(actions) => {
+                    argument_cases += &format!(
+                        "        case $action in\n            {}|'') candidates+=({}) ;;\n        esac\n",
+                        actions.join("|"),
+                        candidates.join(" ")
+                    );
+                }
+                None => {
+                    candidates
+                        .iter()
+                        .for_each(|candidate| arguments += &format!("            {candidate}\n"));
+                }
+            }
+        }
+
+        let mut cases = String::new();
+        for action in &Action::ALL {
+            let candidates = match Self::items(action) {
+                Items::Installers => {
+                    "${(f)\"$(fin completions installers 2>/dev/null)\"}".to_owned()
+                }
+                Items::Installed => "${(f)\"$(fin completions installed 2>/dev/null)\"}".to_owned(),
+                Items::Fixed(items) => items,
+                Items::None => continue,
+            };
+            cases += &format!(
+                "            {}) candidates=({candidates}) ;;\n",
+                action.aliases().join("|")
+            );
+        }
+
+        format!(
+            r#"#compdef fin
+
+_fin() {{
+    local cur="${{words[CURRENT]}}"
+    local action="" i
+    for ((i = 2; i < CURRENT; i++)); do
+        case ${{words[i]}} in
+            {value_arguments}) ((i++)) ;;
+            -*) ;;
+            *)
+                action="${{words[i]}}"
+                break
+                ;;
+        esac
+    done
+
+    local -a candidates
+    if [[ $cur == -* ]]; then
+        candidates=(
+{arguments}        )
+{argument_cases}        _describe -t arguments 'argument' candidates -S ''
+        return
+    elif [[ -z $action ]]; then
+        candidates=(
+{actions}        )
+        _describe -t actions 'action' candidates
+        return
+    elif [[ $cur == *:* ]]; then
+        case $action in
+            {tag_actions}) candidates=(${{(f)"$(fin completions tags "${{cur%%:*}}" 2>/dev/null)"}}) ;;
+        esac
+    else
+        case $action in
+{cases}        esac
+    fi
+
+    compadd -a candidates
+}}
+
+if [ "$funcstack[1]" = "_fin" ]; then
+    _fin "$@"
+else
+    compdef _fin fin
+fi
+"#,
+            tag_actions = Self::tag_actions().replace(' ', "|"),
+            value_arguments = Self::value_arguments().join("|"),
+        )
+    }
+
+    #[must_use]
+    pub fn fish() -> String {
+        let all_aliases = Action::ALL
+            .iter()
+            .flat_map(Action::aliases)
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut script = String::from("# fish completion for fin\n\ncomplete -c fin -f\n\n");
+
+        for action in &Action::ALL {
+            script += &format!(
+                "complete -c fin -n 'not __fish_seen_subcommand_from {all_aliases}' -a {} -d '{}'\n",
+                action.aliases()[0],
+                Self::description(action)
+            );
+        }
+        script += "\n";
+
+        for argument in Options::ARGUMENTS {
+            script += &format!("complete -c fin -l {}", &argument.long[2..]);
+            if let Some(short) = argument.short {
+                script += &format!(" -s {short}");
+            }
+            match argument.value {
+                Some("path") => script += " -r -F",
+                Some(_) => script += " -x",
+                None => {}
+            }
+            if let Some(actions) = Self::argument_actions(argument) {
+                let others = Action::ALL
+                    .iter()
+                    .flat_map(Action::aliases)
+                    .filter(|alias| !actions.contains(alias))
+                    .copied()
+                    .collect::<Vec<_>>();
+                script += &format!(" -n 'not __fish_seen_subcommand_from {}'", others.join(" "));
+            }
+            script += &format!(" -d '{}'\n", argument.description);
+        }
+        script += "\n";
+
+        let tag_actions = Self::tag_actions();
+        for action in &Action::ALL {
+            let (candidates, no_tag) = match Self::items(action) {
+                Items::Installers => ("(fin completions installers 2>/dev/null)".to_owned(), true),
+                Items::Installed => ("(fin completions installed 2>/dev/null)".to_owned(), true),
+                Items::Fixed(items) => (items, false),
+                Items::None => continue,
+            };
+            script += &format!(
+                "complete -c fin -n '__fish_seen_subcommand_from {}{}' -a \"{candidates}\"\n",
+                action.aliases().join(" "),
+                match no_tag {
+                    true => "; and not string match -q -- \"*:*\" (commandline -ct)",
+                    false => "",
+                }
+            );
+        }
+        script += &format!(
+            "complete -c fin -n '__fish_seen_subcommand_from {tag_actions}; and string match -q -- \"*:*\" (commandline -ct)' -a \"(fin completions tags (commandline -ct | string split -f1 :) 2>/dev/null)\"\n"
+        );
+
+        script
+    }
+}
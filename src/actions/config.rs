@@ -6,6 +6,8 @@ use crate::{default_config, Config};
 pub struct ConfigAction;
 
 impl ConfigAction {
+    pub const ITEMS: &[&str] = &["show", "show-default", "write-default", "delete", "help"];
+
     pub fn show_help() -> String {
         let help = "\
 Action:
@@ -0,0 +1,302 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::args::Args;
+use crate::installed::{InstalledFonts, is_generated_file, list_files};
+use crate::installer::Installer;
+use crate::manifest;
+use crate::paths::{collapse_home, config_file_path, lock_file_path, staging_dir};
+use crate::repos;
+use crate::template;
+use crate::{Config, format_size, user_prompt};
+
+/// Lock states older than this were left behind by an instance which did not exit
+const STALE_LOCK_STATE: Duration = Duration::from_secs(60 * 60);
+
+pub struct DoctorAction;
+
+struct Problem {
+    message: String,
+    fix: Option<Fix>,
+}
+
+/// Fixes only remove state created by fin, never any fonts
+enum Fix {
+    /// Forgets an installed font whose directory no longer exists
+    Forget(String),
+    RemoveStaging,
+    RemoveLockState,
+}
+
+impl Problem {
+    fn new(message: String) -> Self {
+        Self { message, fix: None }
+    }
+
+    fn with_fix(message: String, fix: Fix) -> Self {
+        Self {
+            message,
+            fix: Some(fix),
+        }
+    }
+}
+
+impl DoctorAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Check for problems and offer to fix them
+    Reports untracked fonts and files, fonts whose installer was removed,
+    leftover state and problems within the configuration file
+
+Usage:
+    fin doctor
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        args: &Args,
+        lock_state: Option<&String>,
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        let (state, running) = check_state(lock_state);
+        let sections = [
+            ("Configuration", check_config()),
+            ("Installed fonts", check_installed(installed_fonts)),
+            (
+                "Installation directories",
+                check_install_dirs(args, installed_fonts),
+            ),
+            ("State", state),
+        ];
+
+        let mut problems = 0;
+        let mut fixes = Vec::new();
+        for (heading, found) in sections {
+            println!("{heading}:");
+            if found.is_empty() {
+                println_green!("   No problems found");
+            }
+            for problem in found {
+                problems += 1;
+                println!("   {}", orange!(&problem.message));
+                fixes.extend(problem.fix);
+            }
+        }
+
+        if problems == 0 {
+            return Ok(());
+        }
+        if let (Some(lock_state), true) = (lock_state, running) {
+            println!("\nAnother instance is currently {lock_state} something");
+            println!("Note: run `fin doctor` again once it has finished to fix the problems");
+        } else if !fixes.is_empty() {
+            println!("\nFixes:");
+            for fix in &fixes {
+                println!("   {}", green!(&fix.describe()));
+            }
+            println!();
+            if user_prompt("Apply the fixes?", args) {
+                for fix in &fixes {
+                    fix.apply(installed_fonts)?;
+                }
+                problems -= fixes.len();
+            }
+        }
+
+        match problems {
+            0 => {
+                println_green!("All problems were fixed");
+                Ok(())
+            }
+            _ => Err(format!("{problems} problem(s) found")),
+        }
+    }
+}
+
+impl Fix {
+    fn describe(&self) -> String {
+        match self {
+            Fix::Forget(name) => format!("Forget {name}, whose directory no longer exists"),
+            Fix::RemoveStaging => format!("Remove the staging directory: {}", staging_dir()),
+            Fix::RemoveLockState => format!("Remove the lock state: {}", lock_file_path()),
+        }
+    }
+
+    fn apply(&self, installed_fonts: &Arc<Mutex<InstalledFonts>>) -> Result<(), String> {
+        match self {
+            Fix::Forget(name) => installed_fonts.lock().unwrap().remove_entry(name),
+            Fix::RemoveStaging => fs::remove_dir_all(staging_dir()).map_err(|e| e.to_string())?,
+            Fix::RemoveLockState => {
+                fs::remove_file(lock_file_path()).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the configuration file for unknown fields and invalid values
+fn check_config() -> Vec<Problem> {
+    let Ok(contents) = fs::read_to_string(config_file_path()) else {
+        return Vec::new();
+    };
+    let config_file = collapse_home(config_file_path());
+    Config::problems(&contents)
+        .into_iter()
+        .map(|problem| Problem::new(format!("{config_file}: {problem}")))
+        .collect()
+}
+
+/// Checks that each installed font still has an installer and a directory,
+/// and finds files within its directory which no installed font owns
+fn check_installed(installed_fonts: &Arc<Mutex<InstalledFonts>>) -> Vec<Problem> {
+    let installed_fonts = installed_fonts.lock().unwrap();
+    let ownership = installed_fonts.ownership();
+    let mut problems = Vec::new();
+    for (name, installed) in &installed_fonts.installed {
+        let dir = installed.get_dir();
+        if !Path::new(&dir).is_dir() {
+            problems.push(Problem::with_fix(
+                format!("{name}: Directory not found: {}", installed.dir),
+                Fix::Forget(name.to_owned()),
+            ));
+            continue;
+        }
+        if repos::find_installer(name).is_none() {
+            problems.push(Problem::new(format!(
+                "{name}: Installer not found (run `fin remove {name}` to remove the font)"
+            )));
+        }
+        let extra = installed.untracked_files(&ownership);
+        if !extra.is_empty() {
+            problems.push(Problem::new(format!(
+                "{name}: Files not installed by fin within {}: {}",
+                installed.dir,
+                extra.join(", ")
+            )));
+        }
+    }
+    problems
+}
+
+/// Finds the directories and files within the installation directories
+/// which do not belong to any installed font
+fn check_install_dirs(args: &Args, installed_fonts: &Arc<Mutex<InstalledFonts>>) -> Vec<Problem> {
+    let installed_fonts = installed_fonts.lock().unwrap();
+    let normalize = |dir: &str| dir.trim_end_matches('/').to_owned();
+    let tracked = installed_fonts
+        .installed
+        .values()
+        .map(|installed| normalize(&installed.get_dir()))
+        .collect::<BTreeSet<_>>();
+    // Fonts synced from a manifest may be installed into other directories
+    let install_dirs = tracked
+        .iter()
+        .map(|dir| manifest::parent_dir(dir))
+        .chain([normalize(&args.config.install_dir)])
+        .collect::<BTreeSet<_>>();
+
+    let mut installers = None;
+    let mut problems = Vec::new();
+    for install_dir in install_dirs {
+        let Ok(entries) = fs::read_dir(&install_dir) else {
+            continue;
+        };
+        let mut entries = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        for path in entries {
+            let display = collapse_home(&path.to_string_lossy());
+            if path.is_file() {
+                if is_generated_file(&path.to_string_lossy()) {
+                    continue;
+                }
+                problems.push(Problem::new(format!("Untracked file: {display}")));
+                continue;
+            }
+            if tracked.contains(&normalize(&path.to_string_lossy())) {
+                continue;
+            }
+            let dir_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            match installers
+                .get_or_insert_with(installers_by_font_name)
+                .get(&dir_name)
+            {
+                Some(installer) => problems.push(Problem::new(format!(
+                    "Untracked directory: {display}/ (run `fin adopt {installer}` to track it)"
+                ))),
+                None => problems.push(Problem::new(format!("Untracked directory: {display}/"))),
+            }
+        }
+    }
+    problems
+}
+
+/// Returns the installer names of the available installers by the name of
+/// their font, which is also the name of the directory they install into
+fn installers_by_font_name() -> HashMap<String, String> {
+    Installer::filter_installers(&["*".to_string()])
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|installer_name| {
+            let (dir, _) = repos::find_installer(&installer_name)?;
+            let contents = template::read(&dir, &installer_name).ok()?;
+            let table = contents.parse::<toml::Table>().ok()?;
+            let name = table.get("name")?.as_str()?.to_owned();
+            Some((name, installer_name))
+        })
+        .collect()
+}
+
+/// Checks for a lock state left behind by an instance which did not exit,
+/// and for files left within the staging directory. Returns whether another
+/// instance appears to be running, in which case neither is a problem.
+fn check_state(lock_state: Option<&String>) -> (Vec<Problem>, bool) {
+    let mut problems = Vec::new();
+    if let Some(lock_state) = lock_state {
+        let age = fs::metadata(lock_file_path())
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .unwrap_or_default();
+        if age < STALE_LOCK_STATE {
+            return (Vec::new(), true);
+        }
+        let hours = age.as_secs() / 3600;
+        problems.push(Problem::with_fix(
+            format!("Stale lock state: {lock_state} ({hours} hour(s) old)"),
+            Fix::RemoveLockState,
+        ));
+    }
+
+    let files = list_files(staging_dir());
+    if files.is_empty() {
+        return (problems, false);
+    }
+    let size = files
+        .iter()
+        .filter_map(|file| fs::metadata(format!("{}/{file}", staging_dir())).ok())
+        .map(|metadata| metadata.len() as f64)
+        .sum();
+    problems.push(Problem::with_fix(
+        format!(
+            "Leftover staging files: {} file(s), {} within {}",
+            files.len(),
+            format_size(size),
+            collapse_home(staging_dir())
+        ),
+        Fix::RemoveStaging,
+    ));
+    (problems, false)
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::args::Args;
+use crate::installed::InstalledFonts;
+use crate::manifest::Manifest;
+
+pub struct ExportAction;
+
+impl ExportAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Write the installed fonts to a manifest, for use with `fin sync`
+    Prints the manifest when no path is specified
+
+Usage:
+    fin export
+    fin export [manifest]
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        args: &Args,
+        items: &[String],
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        let manifest =
+            Manifest::from_installed(&installed_fonts.lock().unwrap(), &args.config.install_dir);
+        let toml = manifest.to_toml();
+
+        match items {
+            [] => print!("{toml}"),
+            [path] => {
+                if Path::new(path).exists() && !args.options.force {
+                    println!("Note: try passing `--force` to overwrite it");
+                    return Err(format!("Manifest already exists: {path}"));
+                }
+                fs::write(path, toml).map_err(|e| format!("{path}: {e}"))?;
+                let count = manifest.fonts.len();
+                println_green!("Exported {count} fonts: {path}");
+            }
+            _ => {
+                Self::show_help();
+                println!();
+                return Err("Expected at most one manifest".to_string());
+            }
+        }
+        Ok(())
+    }
+}
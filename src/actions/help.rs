@@ -1,6 +1,9 @@
 use crate::actions::{
-    clean::CleanAction, config::ConfigAction, install::InstallAction, list::ListAction,
-    reinstall::ReinstallAction, remove::RemoveAction, update::UpdateAction, version::VersionAction,
+    adopt::AdoptAction, clean::CleanAction, completions::CompletionsAction, config::ConfigAction,
+    doctor::DoctorAction, export::ExportAction, info::InfoAction, install::InstallAction,
+    installer::InstallerAction, list::ListAction, lock::LockAction, owns::OwnsAction,
+    reinstall::ReinstallAction, remove::RemoveAction, repo::RepoAction, search::SearchAction,
+    sync::SyncAction, update::UpdateAction, verify::VerifyAction, version::VersionAction,
 };
 
 use crate::action::Action;
@@ -33,9 +36,21 @@ Usage:
             Action::Reinstall => ReinstallAction::show_help(),
             Action::Update => UpdateAction::show_help(),
             Action::Remove => RemoveAction::show_help(),
+            Action::Adopt => AdoptAction::show_help(),
             Action::List => ListAction::show_help(),
+            Action::Info => InfoAction::show_help(),
+            Action::Search => SearchAction::show_help(),
+            Action::Verify => VerifyAction::show_help(),
+            Action::Owns => OwnsAction::show_help(),
+            Action::Sync => SyncAction::show_help(),
+            Action::Export => ExportAction::show_help(),
+            Action::Lock => LockAction::show_help(),
             Action::Clean => CleanAction::show_help(),
+            Action::Doctor => DoctorAction::show_help(),
             Action::Config => ConfigAction::show_help(),
+            Action::Repo => RepoAction::show_help(),
+            Action::Installer => InstallerAction::show_help(),
+            Action::Completions => CompletionsAction::show_help(),
             Action::Version => VersionAction::show_help(),
             Action::Help => Self::show_help(),
         }
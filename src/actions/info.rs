@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use crate::args::Args;
+use crate::file_action::FileAction;
+use crate::font::Font;
+use crate::font_page::FontPage;
+use crate::format_size;
+use crate::installed::{InstalledFont, InstalledFonts};
+use crate::installer::Installer;
+use crate::repos;
+use crate::template;
+use crate::source::Source;
+
+pub struct InfoAction;
+
+impl InfoAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Show details about the specified fonts and their installers
+    Includes the installed files of installed fonts
+
+Usage:
+    fin info [font(s)]
+    fin info [font]:[tag]
+    fin info [font]@[variant]
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        args: &Args,
+        items: &[String],
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        if items.is_empty() {
+            Self::show_help();
+            return Ok(());
+        }
+
+        let args = Arc::new(args.clone());
+        let cached_pages = Arc::new(Mutex::new(HashMap::new()));
+
+        let mut errors = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+
+            let (name, tag) = match item.split_once(':') {
+                Some((name, tag)) => (name, Some(tag)),
+                None => (item.as_str(), None),
+            };
+            let (name, variant) = match name.split_once('@') {
+                Some((name, variant)) => (name, Some(variant)),
+                None => (name, None),
+            };
+            let installed = installed_fonts.lock().unwrap().installed.get(name).cloned();
+            if !Font::has_installer(name) && installed.is_none() {
+                println_red!("{name}");
+                println!("   Neither installed nor available");
+                errors.push(name);
+                continue;
+            }
+
+            println_green!("{name}");
+            let installer = match Font::has_installer(name) {
+                true => {
+                    // Show the variant the font was installed with, unless
+                    // another one was specified
+                    let installed_variant = installed.as_ref().and_then(|i| i.variant.as_deref());
+                    let variant = variant.or(installed_variant);
+                    Self::show_installer(&args, name, variant, tag, &cached_pages)
+                }
+                false => {
+                    field("Installer:", &orange!("Not found"));
+                    None
+                }
+            };
+            if installer.is_none() {
+                errors.push(name);
+            }
+
+            println!();
+            match installed {
+                Some(installed) => {
+                    let update = match &installer {
+                        Some(installer) if installer.has_updates(installed_fonts) => {
+                            orange!("Update available")
+                        }
+                        Some(_) => green!("Up to date"),
+                        None => "Unknown".to_owned(),
+                    };
+                    field(
+                        "Installed:",
+                        installed.tag.as_deref().unwrap_or("Unknown tag"),
+                    );
+                    if let Some(variant) = &installed.variant {
+                        field("Variant:", variant);
+                    }
+                    if installed.dependency {
+                        field("Reason:", "Installed as a dependency");
+                    }
+                    field("Status:", &update);
+                    Self::show_installed(&installed);
+                }
+                None => field("Installed:", "No"),
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(format!("Failed to show: {}", errors.join(", "))),
+        }
+    }
+
+    /// Shows the installer's source and resolved download links, and returns
+    /// the parsed installer, or `None` if it could not be parsed
+    fn show_installer(
+        args: &Arc<Args>,
+        name: &str,
+        variant: Option<&str>,
+        tag: Option<&str>,
+        cached_pages: &Arc<Mutex<HashMap<String, FontPage>>>,
+    ) -> Option<Installer> {
+        let (dir, origin) = repos::find_installer(name)?;
+        let path = [dir.as_str(), name].concat();
+        field("Installer:", &path);
+        if let Some(template) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| contents.parse::<toml::Table>().ok())
+            .and_then(|installer| installer.get("extends")?.as_str().map(ToOwned::to_owned))
+        {
+            field("Template:", &template);
+        }
+        match &origin {
+            Some(repo) => field("Origin:", &format!("Repository ({repo})")),
+            None => field("Origin:", "Local"),
+        }
+
+        // The source as specified by the installer, before resolving it
+        if let Some(unresolved) = template::read(&dir, name)
+            .ok()
+            .and_then(|contents| toml::from_str::<Installer>(&contents).ok())
+        {
+            field("Name:", &unresolved.name);
+            field("Source:", &describe_source(&unresolved.source));
+            if !unresolved.depends.is_empty() {
+                field("Depends:", &unresolved.depends.join(", "));
+            }
+            if !unresolved.variants.is_empty() {
+                let variants = unresolved.variants.keys().cloned().collect::<Vec<_>>();
+                field("Variants:", &variants.join(", "));
+            }
+        }
+
+        let installer =
+            match Installer::load(args, name, variant, tag, Arc::clone(cached_pages)) {
+                Ok(installer) => installer,
+                Err(e) => {
+                    field("Error:", &red!(&e));
+                    return None;
+                }
+            };
+
+        field(
+            "Tag:",
+            installer.source.ref_tag().ok().flatten().unwrap_or("None"),
+        );
+        for asset in &installer.action {
+            println!();
+            field("Download:", &asset.url);
+            match &asset.action {
+                FileAction::Extract {
+                    include, exclude, ..
+                } => {
+                    field("Action:", "Extract");
+                    field("Include:", &include.join(", "));
+                    if let Some(exclude) = exclude {
+                        field("Exclude:", &exclude.join(", "));
+                    }
+                }
+                FileAction::SingleFile { .. } => field("Action:", "Single file"),
+                FileAction::None => field("Action:", "None"),
+            }
+            match &asset.check {
+                Some(check) if check.is_weak() => field("Check:", &orange!(&check.describe())),
+                Some(check) => field("Check:", &check.describe()),
+                None => field("Check:", &orange!("None")),
+            }
+        }
+
+        Some(installer)
+    }
+
+    /// Shows the installation directory, and the size of each installed file
+    fn show_installed(installed: &InstalledFont) {
+        let dir = installed.get_dir();
+        let sizes = installed
+            .files
+            .iter()
+            .map(|file| {
+                fs::metadata([dir.as_str(), file].concat())
+                    .map(|metadata| metadata.len())
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+
+        field("Directory:", &installed.dir);
+        field(
+            "Files:",
+            &format!(
+                "{} ({})",
+                installed.files.len(),
+                format_size(sizes.iter().flatten().sum::<u64>() as f64)
+            ),
+        );
+
+        let width = installed.files.iter().map(String::len).max().unwrap_or(0);
+        for (file, size) in installed.files.iter().zip(sizes) {
+            let size = match size {
+                Some(size) => format_size(size as f64),
+                None => red!("Missing"),
+            };
+            println!("      {file:<width$}  {size}");
+        }
+    }
+}
+
+fn field(label: &str, value: &str) {
+    println!("   {label:<14}{value}");
+}
+
+fn describe_source(source: &Source) -> String {
+    match source {
+        Source::GitHub {
+            author, project, ..
+        } => format!("GitHub ({author}/{project})"),
+        Source::Webpage { url, .. } => format!("Webpage ({url})"),
+        Source::Direct { url, .. } => format!("Direct link ({url})"),
+        Source::None => "None".to_owned(),
+    }
+}
@@ -1,9 +1,11 @@
 use std::sync::{Arc, Mutex};
 
+use crate::action::Action;
 use crate::args::Args;
 use crate::font::Font;
 use crate::installed::InstalledFonts;
 use crate::installer::Installer;
+use crate::json::{self, OutcomeRecord};
 use crate::user_prompt;
 
 pub struct InstallAction;
@@ -17,6 +19,7 @@ Action:
 Usage:
     fin install [font]
     fin install [font]:[tag]
+    fin install [font]@[variant]:[tag]
 ";
         print!("{help}");
         help.to_string()
@@ -30,7 +33,10 @@ Usage:
         println!("Installing: ");
         Args::list_fonts_green(fonts);
 
-        if !user_prompt("Proceed?", args) {
+        if !args.options.dry_run && !user_prompt("Proceed?", args) {
+            if args.options.json {
+                json::emit(&OutcomeRecord::with_status(fonts, "skipped"));
+            }
             return Ok(());
         }
 
@@ -45,9 +51,17 @@ pub fn install_fonts(
     installed_fonts: &Arc<Mutex<InstalledFonts>>,
 ) -> Result<(), String> {
     let mut errors = Vec::new();
+    let mut records = Vec::new();
     for font in fonts {
         if let Some(installer) = &mut font.installer {
-            match download_and_install(args, installer, installed_fonts) {
+            let result = download_and_install(args, installer, installed_fonts);
+            records.push(OutcomeRecord {
+                name: font.name.clone(),
+                version: installer.version(),
+                status: install_status(args, &result),
+                error: result.as_ref().err().cloned(),
+            });
+            match result {
                 Ok(()) => (),
                 Err(e) => {
                     match args.options.verbose || args.config.verbose_files {
@@ -63,6 +77,12 @@ pub fn install_fonts(
                 false => println!("\nFailed to install {font}"),
             }
             println_red!("Installer for '{font}' has not been loaded");
+            records.push(OutcomeRecord {
+                name: font.name.clone(),
+                version: font.override_version.clone(),
+                status: "failed",
+                error: Some("Installer has not been loaded".to_string()),
+            });
             errors.push(format!(
                 "{}: {}",
                 font,
@@ -71,6 +91,13 @@ pub fn install_fonts(
         }
     }
 
+    if args.options.json {
+        json::emit(&records);
+    }
+    if args.options.dry_run {
+        println!("\nDry run: no changes were made");
+    }
+
     if errors.is_empty() {
         Ok(())
     } else {
@@ -80,6 +107,18 @@ pub fn install_fonts(
     }
 }
 
+/// Returns the status reported by `--json` for the result of installing a font
+#[must_use]
+pub fn install_status(args: &Args, result: &Result<(), String>) -> &'static str {
+    match (result, &args.action) {
+        (Err(_), _) => "failed",
+        (Ok(()), _) if args.options.dry_run => "planned",
+        (Ok(()), Action::Update) => "updated",
+        (Ok(()), Action::Reinstall) => "reinstalled",
+        (Ok(()), _) => "installed",
+    }
+}
+
 fn download_and_install(
     args: &Args,
     installer: &mut Installer,
@@ -92,11 +131,17 @@ fn download_and_install(
     }
     let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
     runtime.block_on(async {
-        installer
+        let installer = installer
             .download_font()
             .await?
             .verify_download()?
-            .prepare_install(args)?
-            .finalize_install(args, installed_fonts)
+            .prepare_install(args)?;
+        match args.options.dry_run {
+            true => installer.show_plan(args, installed_fonts),
+            false => {
+                let claimed = installer.claim_files(args, installed_fonts)?;
+                installer.finalize_install(args, installed_fonts, &claimed)
+            }
+        }
     })
 }
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Write, stdin, stdout};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use reqwest::header::USER_AGENT;
+
+use crate::args::Args;
+use crate::file_action::{FileAction, FileType};
+use crate::font_page::FontPage;
+use crate::installer::Installer;
+use crate::lint::{self, Severity};
+use crate::paths::{cache_dir, collapse_home, installers_dir};
+use crate::repos;
+use crate::scaffold::{self, Asset, Scaffold};
+use crate::source::Source;
+use crate::template;
+use crate::wildcards::match_any_wildcard;
+use crate::{format_size, user_prompt};
+
+pub struct InstallerAction;
+
+impl InstallerAction {
+    pub const ITEMS: &[&str] = &["new", "lint", "help"];
+
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Create and manage installers
+
+Usage:
+    fin installer new [name] --github [author/project]
+    fin installer new [name] [url]
+    fin installer lint [path(s)]
+
+Items:
+    new                   Create an installer from a GitHub release or webpage
+    lint                  Check installers for errors without downloading anything
+    help                  Show this help message
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(args: &Args, items: &[String]) -> Result<(), String> {
+        let Some((item, items)) = items.split_first() else {
+            Self::show_help();
+            return Ok(());
+        };
+
+        match item.as_str() {
+            "new" => Self::new_installer(args, items),
+            "lint" => Self::lint(items),
+            "help" => {
+                Self::show_help();
+                Ok(())
+            }
+            _ => {
+                Self::show_help();
+                println!();
+                Err(format!("Unrecognized item: {item}"))
+            }
+        }
+    }
+
+    /// Proposes an installer for the font, and writes it once accepted
+    fn new_installer(args: &Args, items: &[String]) -> Result<(), String> {
+        let (installer_name, source) = match (items, &args.options.github) {
+            ([name], Some(repo)) => (name, github_source(repo)?),
+            ([name, url], None) => (name, url_source(url)?),
+            _ => {
+                Self::show_help();
+                println!();
+                return Err("Expected the installer name, and either `--github` or a URL".into());
+            }
+        };
+        if installer_name.is_empty()
+            || installer_name.starts_with('.')
+            || installer_name.contains(['/', '\\', ':'])
+        {
+            return Err(format!("Invalid installer name: {installer_name}"));
+        }
+        let path = [installers_dir().as_str(), installer_name].concat();
+        if Path::new(&path).exists() && !args.options.force {
+            println!("Note: try passing `--force` to overwrite it");
+            return Err(format!("Installer already exists: {path}"));
+        }
+
+        let args = Arc::new(args.clone());
+        let cached_pages = Arc::new(Mutex::new(HashMap::new()));
+        let page_url = match &source {
+            Source::GitHub {
+                author, project, ..
+            } => format!("https://api.github.com/repos/{author}/{project}/releases/latest"),
+            Source::Webpage { url, .. } => url.clone(),
+            _ => unreachable!(),
+        };
+        let page = FontPage::get_font_page(
+            &page_url,
+            &args,
+            &reqwest::blocking::Client::new(),
+            Arc::clone(&cached_pages),
+        )?
+        .contents
+        .unwrap_or_default();
+        let (assets, tag) = match &source {
+            Source::GitHub { .. } => scaffold::release_assets(&page)?,
+            _ => (scaffold::page_links(&page), None),
+        };
+
+        let fonts = assets
+            .iter()
+            .filter(|asset| scaffold::is_font_asset(&asset.name))
+            .collect::<Vec<_>>();
+        if fonts.is_empty() {
+            return Err(format!("No fonts or archives were found at {page_url}"));
+        }
+        let asset = choose_asset(&fonts, &args);
+        let file_type = FileAction::get_file_type(&asset.name);
+
+        let (include, exclude) = match file_type {
+            FileType::Unsupported => (None, Vec::new()),
+            _ => {
+                let (include, exclude) = Self::propose_rules(asset, &file_type)?;
+                (Some(include), exclude)
+            }
+        };
+
+        // Checksum files are preferred over the digests listed by GitHub,
+        // which are only used when the installer declares no check
+        let names = assets.iter().map(|a| a.name.clone()).collect::<Vec<_>>();
+        let check = scaffold::detect_checksum(&asset.name, &names)
+            .map(|(algorithm, file)| (algorithm, scaffold::generalize_file(&file, tag.as_deref())));
+
+        let scaffold = Scaffold {
+            name: scaffold::display_name(installer_name),
+            source,
+            file: scaffold::generalize_file(&asset.name, tag.as_deref()),
+            include,
+            exclude,
+            check,
+        };
+        let toml = scaffold.to_toml();
+
+        // Parse the installer before writing it, resolving its download links
+        let staging = [cache_dir().as_str(), "new_installer/"].concat();
+        fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+        fs::write([staging.as_str(), installer_name].concat(), &toml).map_err(|e| e.to_string())?;
+        let parsed = Installer::parse(&args, &staging, installer_name, None, None, cached_pages);
+        let _ = fs::remove_dir_all(&staging);
+        parsed.map_err(|e| format!("The proposed installer is invalid: {e}"))?;
+
+        println!("\n{toml}");
+        if !user_prompt(&format!("Write the installer to {path}?"), &args) {
+            return Ok(());
+        }
+        fs::create_dir_all(installers_dir()).map_err(|e| e.to_string())?;
+        fs::write(&path, toml).map_err(|e| e.to_string())?;
+        println_green!("Created installer: {path}");
+        println!("Note: review it, then run `fin install {installer_name}`");
+
+        Ok(())
+    }
+
+    /// Checks the installers at the specified paths (which may also be
+    /// directories or installer names), or all local installers
+    fn lint(items: &[String]) -> Result<(), String> {
+        let items = match items.is_empty() {
+            true => &[installers_dir().to_owned()],
+            false => items,
+        };
+
+        let mut paths = Vec::new();
+        for item in items {
+            let path = Path::new(item);
+            if path.is_dir() {
+                paths.extend(files_within(path)?);
+                // Templates are checked along with the installers extending them
+                let templates = path.join(template::TEMPLATES_DIR);
+                if templates.is_dir() {
+                    paths.extend(files_within(&templates)?);
+                }
+            } else if path.is_file() {
+                paths.push(path.to_path_buf());
+            } else if let Some((dir, _)) = repos::find_installer(item) {
+                paths.push(Path::new(&dir).join(item));
+            } else {
+                return Err(format!("Installer not found: {item}"));
+            }
+        }
+
+        let (mut errors, mut warnings) = (0, 0);
+        for path in &paths {
+            let display = collapse_home(&path.to_string_lossy());
+            let contents = fs::read_to_string(path).map_err(|e| format!("{display}: {e}"))?;
+            let installer_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let dir = path
+                .parent()
+                .map(|dir| dir.to_string_lossy().into_owned() + "/")
+                .unwrap_or_default();
+
+            let diagnostics = match dir.ends_with(&format!("/{}", template::TEMPLATES_DIR)) {
+                true => lint::lint_template(&contents),
+                false => lint::lint(&dir, &installer_name, &contents),
+            };
+            for diagnostic in diagnostics {
+                let severity = match diagnostic.severity {
+                    Severity::Error => {
+                        errors += 1;
+                        red!("error")
+                    }
+                    Severity::Warning => {
+                        warnings += 1;
+                        orange!("warning")
+                    }
+                };
+                println!(
+                    "{display}:{}:{}: {severity}: {}",
+                    diagnostic.line, diagnostic.column, diagnostic.message
+                );
+            }
+        }
+
+        let summary = format!(
+            "Checked {} installers: {errors} errors, {warnings} warnings",
+            paths.len()
+        );
+        match errors {
+            0 => {
+                println_green!("{summary}");
+                Ok(())
+            }
+            _ => {
+                println!("{summary}");
+                Err("One or more installers are invalid".to_string())
+            }
+        }
+    }
+
+    /// Downloads the archive, shows the files within it, and returns the
+    /// proposed `include` and `exclude` patterns
+    fn propose_rules(
+        asset: &Asset,
+        file_type: &FileType,
+    ) -> Result<(Vec<String>, Vec<String>), String> {
+        println!("Downloading {}", asset.url);
+        let data = reqwest::blocking::Client::new()
+            .get(&asset.url)
+            .header(USER_AGENT, "fin")
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status)
+            .and_then(reqwest::blocking::Response::bytes)
+            .map_err(|e| e.to_string())?
+            .to_vec();
+        let size = format_size(data.len() as f64);
+
+        let paths = scaffold::archive_paths(data, file_type)?;
+        let (include, exclude) = scaffold::propose_rules(&paths);
+        if include.is_empty() {
+            return Err(format!("No fonts were found within {}", asset.name));
+        }
+
+        println!("\nFiles within {} ({size}):", asset.name);
+        for path in &paths {
+            match match_any_wildcard(path, &include) && !match_any_wildcard(path, &exclude) {
+                true => println_green!("   + {path}"),
+                false => println!("     {path}"),
+            }
+        }
+        Ok((include, exclude))
+    }
+}
+
+/// Returns the files within the directory, sorted by name
+fn files_within(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = fs::read_dir(dir)
+        .map_err(|e| format!("{}: {e}", dir.display()))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the source of a GitHub repository (`author/project`), ignoring
+/// the rest of the path (such as `/releases` or `/tree/main`)
+fn github_source(repo: &str) -> Result<Source, String> {
+    let path = repo.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = path.split('/');
+    match (segments.next(), segments.next()) {
+        (Some(author), Some(project)) if !author.is_empty() && !project.is_empty() => {
+            Ok(Source::GitHub {
+                tag: None,
+                author: author.to_owned(),
+                project: project.trim_end_matches(".git").to_owned(),
+            })
+        }
+        _ => Err(format!("Expected `author/project`: {repo}")),
+    }
+}
+
+/// Returns the source of a URL, which is either a GitHub repository or a webpage
+fn url_source(url: &str) -> Result<Source, String> {
+    if let Some(repo) = url.strip_prefix("https://github.com/") {
+        return github_source(repo);
+    }
+    match url.starts_with("https://") || url.starts_with("http://") {
+        true => Ok(Source::Webpage {
+            tag: None,
+            url: url.to_owned(),
+        }),
+        false => Err(format!("Invalid URL: {url}")),
+    }
+}
+
+/// Lists the assets, and asks which one should be installed
+fn choose_asset<'a>(assets: &[&'a Asset], args: &Args) -> &'a Asset {
+    println!("Available files:");
+    for (i, asset) in assets.iter().enumerate() {
+        println!("   {}. {}", i + 1, asset.name);
+    }
+    if assets.len() == 1 || args.options.answer.is_some() {
+        println!("Using {}", assets[0].name);
+        return assets[0];
+    }
+
+    loop {
+        print!("Choose a file [1-{}] (default 1): ", assets.len());
+        let _ = stdout().flush();
+        let mut input = String::new();
+        stdin().read_line(&mut input).unwrap();
+        match input.trim() {
+            "" => return assets[0],
+            input => match input.parse::<usize>() {
+                Ok(i) if (1..=assets.len()).contains(&i) => return assets[i - 1],
+                _ => continue,
+            },
+        }
+    }
+}
@@ -2,11 +2,14 @@ use std::fs;
 use std::sync::{Arc, Mutex};
 
 use crate::Args;
+use crate::json::{self, FontRecord};
 use crate::{font::Font, installed::InstalledFonts};
 
 pub struct ListAction;
 
 impl ListAction {
+    pub const ITEMS: &[&str] = &["installed", "available", "all", "help"];
+
     pub fn show_help() -> String {
         let help = "\
 Action:
@@ -27,10 +30,40 @@ Items:
     }
 
     pub fn run(args: &Args, fonts: &[Font], installed_fonts: Arc<Mutex<InstalledFonts>>) {
+        if args.options.json {
+            let installed_fonts = installed_fonts.lock().unwrap();
+            let records = fonts
+                .iter()
+                .map(|font| match installed_fonts.installed.get(&font.name) {
+                    Some(installed) => FontRecord {
+                        name: font.name.clone(),
+                        dir: Some(installed.get_dir()),
+                        version: installed.tag.clone(),
+                        status: if !Font::has_installer(&font.name) {
+                            "missing installer"
+                        } else if !fs::exists(installed.get_dir()).unwrap_or_default() {
+                            "missing directory"
+                        } else {
+                            "installed"
+                        },
+                    },
+                    None => FontRecord {
+                        name: font.name.clone(),
+                        dir: None,
+                        version: None,
+                        status: "available",
+                    },
+                })
+                .collect::<Vec<_>>();
+            json::emit(&records);
+            return;
+        }
+
         fonts.iter().for_each(|font| {
             if let Some(installed) = installed_fonts.lock().unwrap().installed.get(&font.name) {
                 if Font::has_installer(&font.name) {
                     match fs::exists(installed.get_dir()).unwrap_or_default() {
+                        true if installed.dependency => println_green!("{font} (dependency)"),
                         true => println_green!("{font}"),
                         false => println_orange!("{font} (missing directory)"),
                     }
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use crate::args::Args;
+use crate::checksum::sha256_data;
+use crate::dependencies::{base_name, expand_dependencies};
+use crate::font::Font;
+use crate::installer::Installer;
+use crate::lockfile::{LockedAsset, LockedFont, Lockfile};
+use crate::manifest::Manifest;
+use crate::paths::{collapse_home, manifest_file_path};
+
+pub struct LockAction;
+
+impl LockAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Lock the fonts of a manifest to their exact downloads
+    Reads ~/.config/fin/fonts.toml when unspecified, and writes fonts.lock
+    Use `fin sync --locked` to install the locked downloads
+
+Usage:
+    fin lock
+    fin lock [manifest]
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(args: &Args, items: &[String]) -> Result<(), String> {
+        let path = match items {
+            [] => manifest_file_path().to_owned(),
+            [path] => path.to_owned(),
+            _ => {
+                Self::show_help();
+                println!();
+                return Err("Expected at most one manifest".to_string());
+            }
+        };
+        let manifest = Manifest::read(&path)?;
+        let listed = manifest
+            .fonts
+            .iter()
+            .map(|(name, font)| font.to_font(name))
+            .collect::<Vec<_>>();
+        let fonts = expand_dependencies(&listed, &Installer::read_dependencies)?;
+
+        let args = Arc::new(args.clone());
+        let cached_pages = Arc::new(Mutex::new(HashMap::new()));
+        let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+
+        let mut lockfile = Lockfile::default();
+        let mut errors = Vec::new();
+        for (font, dependency) in fonts {
+            let result = Font::parse(Arc::clone(&args), &font, true, Arc::clone(&cached_pages))
+                .map_err(|e| e.to_string())
+                .and_then(|font| {
+                    let mut installer = font.installer.ok_or("Installer has not been loaded")?;
+                    installer.dependency = dependency;
+                    lock_font(&args, &runtime, &mut installer)
+                });
+            match result {
+                Ok(locked) => _ = lockfile.fonts.insert(base_name(&font).to_owned(), locked),
+                Err(e) => {
+                    println!("\nFailed to lock {font}:\n{}", red!(&e));
+                    errors.push(format!("{font}: {}", red!(&e)));
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            println!();
+            errors.iter().for_each(|e| println!("{e}"));
+            return Err("One or more fonts could not be locked".to_string());
+        }
+
+        let lock_path = Lockfile::path(&path);
+        fs::write(&lock_path, lockfile.to_toml()).map_err(|e| format!("{lock_path}: {e}"))?;
+        let count = lockfile.fonts.len();
+        let lock_path = collapse_home(&lock_path);
+        println!();
+        println_green!("Locked {count} fonts: {lock_path}");
+        Ok(())
+    }
+}
+
+/// Downloads and verifies every asset of the font, and records its sum
+fn lock_font(
+    args: &Args,
+    runtime: &tokio::runtime::Runtime,
+    installer: &mut Installer,
+) -> Result<LockedFont, String> {
+    let url = installer.source.ref_direct_url()?;
+    match args.options.verbose || args.config.verbose_urls {
+        true => println!("\n{} ({}): ", installer.name, url),
+        false => println!("\n{}:", installer.name),
+    }
+    runtime.block_on(async {
+        installer.download_font().await?.verify_download()?;
+        Ok::<(), String>(())
+    })?;
+
+    let assets = installer
+        .action
+        .iter()
+        .map(|asset| LockedAsset {
+            url: asset.url.clone(),
+            sha256: sha256_data(asset.data.as_deref().unwrap_or_default()),
+        })
+        .collect();
+    Ok(LockedFont {
+        tag: installer.version(),
+        variant: installer.variant.clone(),
+        dependency: installer.dependency,
+        assets,
+    })
+}
@@ -1,9 +1,21 @@
+pub mod adopt;
 pub mod clean;
+pub mod completions;
 pub mod config;
+pub mod doctor;
+pub mod export;
 pub mod help;
+pub mod info;
 pub mod install;
+pub mod installer;
 pub mod list;
+pub mod lock;
+pub mod owns;
 pub mod reinstall;
 pub mod remove;
+pub mod repo;
+pub mod search;
+pub mod sync;
 pub mod update;
+pub mod verify;
 pub mod version;
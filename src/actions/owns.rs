@@ -0,0 +1,53 @@
+use std::sync::{Arc, Mutex};
+
+use crate::installed::InstalledFonts;
+use crate::ownership;
+use crate::paths::{collapse_home, expand_home};
+
+pub struct OwnsAction;
+
+impl OwnsAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Show which installed font a file or directory belongs to
+
+Usage:
+    fin owns [paths]
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        items: &[String],
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        if items.is_empty() {
+            Self::show_help();
+            return Ok(());
+        }
+
+        let ownership = installed_fonts.lock().unwrap().ownership();
+        let mut unowned = false;
+        for item in items {
+            let mut path = item.to_owned();
+            expand_home(&mut path);
+            let path = ownership::resolve(&path);
+            let display = collapse_home(&path);
+
+            match ownership.owners(&path).as_slice() {
+                [] => {
+                    unowned = true;
+                    println!("{display}: {}", red!("Not installed by fin"));
+                }
+                owners => println!("{display}: {}", green!(&owners.join(", "))),
+            }
+        }
+
+        match unowned {
+            false => Ok(()),
+            true => Err("One or more paths were not installed by fin".to_string()),
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use crate::args::Args;
 use crate::font::Font;
 use crate::installed::InstalledFonts;
+use crate::json::{self, OutcomeRecord};
 use crate::user_prompt;
 
 use crate::actions::install::install_fonts;
@@ -30,7 +31,10 @@ Usage:
         println!("Reinstalling: ");
         Args::list_fonts_green(fonts);
 
-        if !user_prompt("Proceed?", args) {
+        if !args.options.dry_run && !user_prompt("Proceed?", args) {
+            if args.options.json {
+                json::emit(&OutcomeRecord::with_status(fonts, "skipped"));
+            }
             return Ok(());
         }
 
@@ -1,8 +1,12 @@
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 use crate::args::Args;
 use crate::font::Font;
-use crate::installed::InstalledFonts;
+use crate::installed::{InstalledFonts, list_files};
+use crate::json::{self, OutcomeRecord};
+use crate::paths::collapse_home;
+use crate::plan::FilePlan;
 use crate::user_prompt;
 
 pub struct RemoveAction;
@@ -28,25 +32,93 @@ Usage:
         println!("Removing: ");
         Args::list_fonts_red(fonts);
 
-        if !user_prompt("Proceed?", args) {
+        if !args.options.dry_run && !user_prompt("Proceed?", args) {
+            if args.options.json {
+                json::emit(&OutcomeRecord::with_status(fonts, "skipped"));
+            }
             return Ok(());
         }
 
-        remove_fonts(args, fonts, installed_fonts)
+        match args.options.dry_run {
+            true => {
+                show_plan(args, fonts, installed_fonts);
+                Ok(())
+            }
+            false => remove_fonts(args, fonts, installed_fonts),
+        }
     }
 }
 
+/// Lists the files which removing the fonts would delete
+fn show_plan(args: &Args, fonts: &[Font], installed_fonts: &Arc<Mutex<InstalledFonts>>) {
+    let installed_fonts = installed_fonts.lock().unwrap();
+    for font in fonts {
+        let Some(installed) = installed_fonts.installed.get(&font.name) else {
+            continue;
+        };
+        let dir = installed.get_dir();
+        // `--force` removes the whole directory, including files fin did not install
+        let removed = match installed_fonts.removes_dir(args, &font.name) {
+            true => list_files(&dir),
+            false => installed
+                .files
+                .iter()
+                .filter(|file| Path::new(&format!("{dir}/{file}")).is_file())
+                .cloned()
+                .collect(),
+        };
+        let plan = FilePlan {
+            removed,
+            ..FilePlan::default()
+        };
+
+        let dir = collapse_home(&dir);
+        println!("\n{}:", font.name);
+        match plan.is_empty() {
+            true => println!("No files to remove within {dir}"),
+            false => {
+                println!("Changes within {dir}:");
+                plan.print();
+            }
+        }
+    }
+
+    if args.options.json {
+        json::emit(&OutcomeRecord::with_status(fonts, "planned"));
+    }
+    println!("\nDry run: no changes were made");
+}
+
 fn remove_fonts(
     args: &Args,
     fonts: &[Font],
     installed_fonts: &Arc<Mutex<InstalledFonts>>,
 ) -> Result<(), String> {
-    fonts.iter().try_for_each(|font| {
-        installed_fonts
-            .lock()
-            .unwrap()
-            .uninstall(args, &font.name)
-            .map(|_| ())
-    })?;
-    Ok(())
+    let mut records = Vec::new();
+    for font in fonts {
+        let mut installed_fonts = installed_fonts.lock().unwrap();
+        let version = installed_fonts
+            .installed
+            .get(&font.name)
+            .and_then(|installed| installed.tag.clone());
+        let error = installed_fonts.uninstall(args, &font.name).err();
+        records.push(OutcomeRecord {
+            name: font.name.clone(),
+            version,
+            status: match error {
+                Some(_) => "failed",
+                None => "removed",
+            },
+            error,
+        });
+    }
+
+    if args.options.json {
+        json::emit(&records);
+    }
+
+    match records.iter().any(|record| record.error.is_some()) {
+        true => Err("One or more fonts failed to be removed".to_string()),
+        false => Ok(()),
+    }
 }
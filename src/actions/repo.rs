@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::Path;
+
+use crate::paths::{config_dir, repos_file_path};
+use crate::repos::{self, Repo, Repos};
+
+pub struct RepoAction;
+
+impl RepoAction {
+    pub const ITEMS: &[&str] = &["add", "update", "remove", "list", "help"];
+
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Manage the repositories installers are obtained from
+    Local installers override those of repositories
+
+Usage:
+    fin repo add [name] [url]
+    fin repo update [name(s)]
+    fin repo remove [name(s)]
+    fin repo list
+
+Items:
+    add                   Subscribe to a repository and download its installers
+    update                Download the installers of all or the specified repositories
+    remove                Unsubscribe from repositories
+    list                  List the subscribed repositories
+    help                  Show this help message
+
+Repositories:
+    A git repository (URL ending with `.git`), an archive (`.tar`,
+    `.tar.gz`, `.tgz` or `.zip`) or an index file listing one installer
+    name per line, located next to the installers
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(items: &[String]) -> Result<(), String> {
+        let Some((item, items)) = items.split_first() else {
+            Self::show_help();
+            return Ok(());
+        };
+
+        match item.as_str() {
+            "add" => Self::add(items),
+            "update" => Self::update(items),
+            "remove" => Self::remove(items),
+            "list" => Self::list(),
+            "help" => {
+                Self::show_help();
+                Ok(())
+            }
+            _ => {
+                Self::show_help();
+                println!();
+                Err(format!("Unrecognized item: {item}"))
+            }
+        }
+    }
+
+    fn add(items: &[String]) -> Result<(), String> {
+        let [name, url] = items else {
+            Self::show_help();
+            println!();
+            return Err("Expected the name and URL of the repository".to_string());
+        };
+        if !repos::is_valid_name(name) {
+            return Err(format!(
+                "Invalid repository name: {name} (use letters, digits, `-` and `_`)"
+            ));
+        }
+
+        let mut repos = Repos::read()?;
+        if let Some(repo) = repos.repos.get(name) {
+            return Err(format!("Repository already exists: {name} ({})", repo.url));
+        }
+
+        // Only subscribe once the installers were downloaded successfully
+        let repo = Repo { url: url.clone() };
+        let count = repo.update(name)?;
+        repos.repos.insert(name.clone(), repo);
+        let _ = fs::create_dir_all(config_dir());
+        repos.write()?;
+
+        println_green!("Added repository: {name} ({count} installers)");
+        Ok(())
+    }
+
+    fn update(items: &[String]) -> Result<(), String> {
+        let repos = Repos::read()?;
+        if repos.repos.is_empty() {
+            println!("No repositories have been added");
+            return Ok(());
+        }
+
+        let names = match items.is_empty() {
+            true => repos.repos.keys().cloned().collect(),
+            false => items.to_vec(),
+        };
+
+        let mut errors = Vec::new();
+        for name in &names {
+            let Some(repo) = repos.repos.get(name) else {
+                println_red!("Repository not found: {name}");
+                errors.push(name.as_str());
+                continue;
+            };
+            match repo.update(name) {
+                Ok(count) => println_green!("Updated {name} ({count} installers)"),
+                Err(e) => {
+                    println_red!("Failed to update {name}: {e}");
+                    errors.push(name.as_str());
+                }
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(format!("Failed to update: {}", errors.join(", "))),
+        }
+    }
+
+    fn remove(items: &[String]) -> Result<(), String> {
+        if items.is_empty() {
+            Self::show_help();
+            println!();
+            return Err("No repositories were specified".to_string());
+        }
+
+        let mut repos = Repos::read()?;
+        let mut errors = Vec::new();
+        for name in items {
+            if repos.repos.remove(name).is_none() {
+                println_red!("Repository not found: {name}");
+                errors.push(name.as_str());
+                continue;
+            }
+            let dir = repos::cached_dir(name);
+            if Path::new(&dir).exists() {
+                fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+            }
+            println!("Removed repository: {name}");
+        }
+        repos.write()?;
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(format!("Failed to remove: {}", errors.join(", "))),
+        }
+    }
+
+    fn list() -> Result<(), String> {
+        let repos = Repos::read()?;
+        if repos.repos.is_empty() {
+            println!("No repositories have been added");
+            println!("Repositories are stored in: {}", repos_file_path());
+            return Ok(());
+        }
+
+        for (name, repo) in &repos.repos {
+            match fs::read_dir(repos::cached_dir(name)) {
+                Ok(entries) => {
+                    let count = entries
+                        .filter_map(Result::ok)
+                        .filter(|entry| entry.path().is_file())
+                        .count();
+                    println_green!("{name} ({count} installers)");
+                }
+                Err(_) => println_orange!("{name} (not downloaded)"),
+            }
+            println!("   {}", repo.url);
+        }
+        Ok(())
+    }
+}
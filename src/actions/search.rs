@@ -0,0 +1,172 @@
+use std::sync::{Arc, Mutex};
+
+use crate::installed::InstalledFonts;
+use crate::installer::Installer;
+use crate::repos;
+use crate::template;
+
+pub struct SearchAction;
+
+/// An installer matched by a search query
+pub struct SearchResult {
+    pub installer_name: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub score: u32,
+}
+
+impl SearchAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Search the available installers
+    Matches installer names, font names, descriptions and tags
+
+Usage:
+    fin search [query]
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        items: &[String],
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        if items.is_empty() {
+            Self::show_help();
+            return Ok(());
+        }
+
+        let results = Self::search(&Installer::filter_installers(&["*".to_string()])?, items);
+        if results.is_empty() {
+            println!("No fonts found matching: '{}'", items.join(" "));
+            return Ok(());
+        }
+
+        let installed_fonts = installed_fonts.lock().unwrap();
+        for result in results {
+            let font = &result.installer_name;
+            let name = match &result.name {
+                Some(name) => format!(" ({name})"),
+                None => String::new(),
+            };
+            match installed_fonts.installed.contains_key(font) {
+                true => println_green!("{font}{name} [installed]"),
+                false => println!("{font}{name}"),
+            }
+            if let Some(description) = &result.description {
+                println!("   {description}");
+            }
+            if !result.tags.is_empty() {
+                println!("   Tags: {}", result.tags.join(", "));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the installers matching every term of the query, with the
+    /// best matches first
+    #[must_use]
+    pub fn search(installer_names: &[String], query: &[String]) -> Vec<SearchResult> {
+        let terms = query
+            .iter()
+            .flat_map(|item| item.split_whitespace())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+
+        let mut results = installer_names
+            .iter()
+            .filter_map(|installer_name| {
+                // Installers which cannot be parsed may still match by their name
+                let installer = repos::find_installer(installer_name)
+                    .and_then(|(dir, _)| template::read(&dir, installer_name).ok())
+                    .and_then(|contents| toml::from_str::<Installer>(&contents).ok());
+                let mut result = SearchResult {
+                    installer_name: installer_name.clone(),
+                    name: installer.as_ref().map(|installer| installer.name.clone()),
+                    description: installer
+                        .as_ref()
+                        .and_then(|installer| installer.description.clone()),
+                    tags: installer
+                        .map(|installer| installer.tags)
+                        .unwrap_or_default(),
+                    score: 0,
+                };
+                result.score = terms
+                    .iter()
+                    .map(|term| result.score_term(term))
+                    .sum::<Option<u32>>()?;
+                Some(result)
+            })
+            .collect::<Vec<_>>();
+
+        results.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.installer_name.cmp(&b.installer_name))
+        });
+        results
+    }
+}
+
+impl SearchResult {
+    /// Returns the score of the best match of the lowercase `term`,
+    /// or `None` if nothing matches it
+    #[must_use]
+    pub fn score_term(&self, term: &str) -> Option<u32> {
+        let names = [Some(&self.installer_name), self.name.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter_map(|name| score_text(term, name, true).map(|score| score * 3));
+        let tags = self
+            .tags
+            .iter()
+            .filter_map(|tag| score_text(term, tag, false).map(|score| score * 2));
+        let description = self
+            .description
+            .iter()
+            .filter_map(|description| score_text(term, description, false));
+
+        names.chain(tags).chain(description).max()
+    }
+}
+
+/// Scores how well `term` matches `text`, ignoring case. Fuzzy matches
+/// (the characters of `term` appearing in order) are only considered
+/// when `fuzzy` is `true`.
+fn score_text(term: &str, text: &str, fuzzy: bool) -> Option<u32> {
+    let text = text.to_lowercase();
+    if text == term {
+        return Some(100);
+    }
+    if text.starts_with(term) {
+        return Some(80);
+    }
+    if let Some(index) = text.find(term) {
+        // Prefer matches at the start of a word
+        let at_word = text[..index].ends_with(|c: char| !c.is_alphanumeric());
+        return Some(if at_word { 70 } else { 60 });
+    }
+    if !fuzzy || term.chars().count() < 2 {
+        return None;
+    }
+
+    // Penalize the characters skipped between the matched characters
+    let mut term_chars = term.chars().peekable();
+    let (mut start, mut end) = (None, 0);
+    for (i, c) in text.chars().enumerate() {
+        if term_chars.peek() == Some(&c) {
+            term_chars.next();
+            start.get_or_insert(i);
+            end = i;
+        }
+    }
+    if term_chars.peek().is_some() {
+        return None;
+    }
+    let skipped = (end - start.unwrap_or_default() + 1 - term.chars().count()) as u32;
+    Some(40u32.saturating_sub(skipped * 2).max(10))
+}
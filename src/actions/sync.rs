@@ -0,0 +1,317 @@
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+use crate::action::Action;
+use crate::actions::install::install_fonts;
+use crate::args::Args;
+use crate::dependencies::{base_name, expand_dependencies};
+use crate::font::Font;
+use crate::installed::InstalledFonts;
+use crate::installer::Installer;
+use crate::lockfile::Lockfile;
+use crate::manifest::{self, Manifest};
+use crate::options::Options;
+use crate::paths::{collapse_home, expand_home, manifest_file_path};
+use crate::user_prompt;
+
+pub struct SyncAction;
+
+impl SyncAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Install, update or downgrade fonts to match a manifest
+    Reads ~/.config/fin/fonts.toml when unspecified
+
+Usage:
+    fin sync
+    fin sync [manifest]
+    fin sync [manifest] --prune
+    fin sync [manifest] --locked
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        args: &Args,
+        items: &[String],
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        let path = match items {
+            [] => manifest_file_path().to_owned(),
+            [path] => path.to_owned(),
+            _ => {
+                Self::show_help();
+                println!();
+                return Err("Expected at most one manifest".to_string());
+            }
+        };
+        let manifest = Manifest::read(&path)?;
+        let lockfile = match args.options.locked {
+            true => Some(Self::read_lockfile(&path, &manifest)?),
+            false => None,
+        };
+        let listed = manifest
+            .fonts
+            .iter()
+            .map(|(name, font)| match lockfile.as_ref().and_then(|l| l.fonts.get(name)) {
+                Some(locked) => locked.to_font(name),
+                // Bundles are not locked (see `Lockfile::check`)
+                None => font.to_font(name),
+            })
+            .collect::<Vec<_>>();
+
+        // Fonts installed into another directory than the manifest specifies
+        // are reinstalled, even if they are up to date
+        let moved = {
+            let installed_fonts = installed_fonts.lock().unwrap();
+            manifest
+                .fonts
+                .iter()
+                .filter(|(name, font)| {
+                    let install_dir = font
+                        .install_dir
+                        .as_ref()
+                        .unwrap_or(&args.config.install_dir);
+                    installed_fonts
+                        .installed
+                        .get(*name)
+                        .is_some_and(|installed| {
+                            let parent = manifest::parent_dir(&installed.get_dir());
+                            !manifest::same_dir(&parent, install_dir)
+                        })
+                })
+                .map(|(name, _)| name.to_owned())
+                .collect::<BTreeSet<_>>()
+        };
+
+        // Locked fonts are compared with the lockfile instead of the latest
+        // release, so every font is resolved
+        let install_args = Arc::new(Args {
+            action: Action::Install,
+            options: Options {
+                reinstall: args.options.reinstall || lockfile.is_some(),
+                ..args.options.clone()
+            },
+            ..args.clone()
+        });
+        let mut fonts = Font::get_actionable_fonts(&install_args, &listed, installed_fonts)
+            .map_err(|e| e.to_string())?;
+        if let Some(lockfile) = &lockfile {
+            Self::apply_lock(lockfile, &mut fonts, &moved, installed_fonts)?;
+        } else if !moved.is_empty() {
+            fonts.retain(|font| !moved.contains(&font.name));
+            let reinstall_args = Arc::new(Args {
+                action: Action::Install,
+                options: Options {
+                    reinstall: true,
+                    ..args.options.clone()
+                },
+                ..args.clone()
+            });
+            let moved_fonts = listed
+                .iter()
+                .filter(|font| moved.contains(base_name(font)))
+                .cloned()
+                .collect::<Vec<_>>();
+            let reinstalled =
+                Font::get_actionable_fonts(&reinstall_args, &moved_fonts, installed_fonts)
+                    .map_err(|e| e.to_string())?;
+            fonts.extend(
+                reinstalled
+                    .into_iter()
+                    .filter(|font| moved.contains(&font.name)),
+            );
+        }
+        for font in &mut fonts {
+            // Moved fonts which the manifest lists without a directory are
+            // moved into the default one
+            let install_dir = manifest
+                .fonts
+                .get(&font.name)
+                .and_then(|listed| listed.install_dir.clone())
+                .or_else(|| {
+                    moved
+                        .contains(&font.name)
+                        .then(|| args.config.install_dir.clone())
+                });
+            if let (Some(installer), Some(mut install_dir)) = (&mut font.installer, install_dir) {
+                expand_home(&mut install_dir);
+                installer.install_dir = Some(install_dir);
+            }
+        }
+
+        // Dependencies of the listed fonts are kept as well
+        let pruned = match args.options.prune {
+            true => {
+                let kept = expand_dependencies(&listed, &Installer::read_dependencies)?
+                    .iter()
+                    .map(|(font, _)| base_name(font).to_owned())
+                    .collect::<BTreeSet<_>>();
+                installed_fonts
+                    .lock()
+                    .unwrap()
+                    .get_names()
+                    .into_iter()
+                    .filter(|name| !kept.contains(name))
+                    .collect()
+            }
+            false => Vec::new(),
+        };
+
+        if fonts.is_empty() && pruned.is_empty() {
+            println!("Installed fonts already match the manifest");
+            return Ok(());
+        }
+        Self::show_plan(args, &fonts, &moved, &pruned, installed_fonts);
+        if !user_prompt("Proceed?", args) {
+            return Ok(());
+        }
+
+        // Moved fonts are installed into their new directory first, and only
+        // removed from the previous one once that succeeded
+        let previous = {
+            let installed_fonts = installed_fonts.lock().unwrap();
+            moved
+                .iter()
+                .filter(|name| fonts.iter().any(|f| &f.name == *name))
+                .filter_map(|name| Some((name, installed_fonts.installed.get(name)?.clone())))
+                .collect::<Vec<_>>()
+        };
+        let mut fonts: Box<[Font]> = fonts.into();
+        let result = install_fonts(args, &mut fonts, installed_fonts);
+
+        let mut failed = Vec::new();
+        for (name, previous) in &previous {
+            let moved = installed_fonts
+                .lock()
+                .unwrap()
+                .installed
+                .get(*name)
+                .is_some_and(|installed| !manifest::same_dir(&installed.dir, &previous.dir));
+            if moved && previous.remove_files(args).is_err() {
+                failed.push(name.as_str());
+            }
+        }
+        for name in &pruned {
+            if installed_fonts
+                .lock()
+                .unwrap()
+                .uninstall(args, name)
+                .is_err()
+            {
+                failed.push(name.as_str());
+            }
+        }
+        match failed.is_empty() {
+            true => result,
+            false => Err(format!("Failed to remove: {}", failed.join(", "))),
+        }
+    }
+
+    /// Reads the lockfile of the manifest, which must lock every font it lists
+    fn read_lockfile(path: &str, manifest: &Manifest) -> Result<Lockfile, String> {
+        let lock_path = Lockfile::path(path);
+        let lockfile = Lockfile::read(&lock_path).inspect_err(|_| {
+            println!("Note: run `fin lock` to create the lockfile");
+        })?;
+        lockfile.check(manifest, &Installer::read_dependencies).map_err(|e| {
+            println!("Note: run `fin lock` to update the lockfile");
+            format!("{}: The lockfile is out of date: {e}", collapse_home(&lock_path))
+        })?;
+        Ok(lockfile)
+    }
+
+    /// Makes each font download the assets of the lockfile, and leaves out
+    /// fonts which are already installed from them
+    fn apply_lock(
+        lockfile: &Lockfile,
+        fonts: &mut Vec<Font>,
+        moved: &BTreeSet<String>,
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        for font in fonts.iter_mut() {
+            let (Some(installer), Some(locked)) =
+                (&mut font.installer, lockfile.fonts.get(&font.name))
+            else {
+                println!("Note: run `fin lock` to update the lockfile");
+                return Err(format!("{}: Not found within the lockfile", font.name));
+            };
+            installer.apply_lock(locked)?;
+        }
+
+        let installed_fonts = installed_fonts.lock().unwrap();
+        fonts.retain(|font| {
+            let locked = &lockfile.fonts[&font.name];
+            moved.contains(&font.name)
+                || !installed_fonts
+                    .installed
+                    .get(&font.name)
+                    .is_some_and(|installed| {
+                        // The links identify the release, as the recorded
+                        // tag may be the `latest` the installer asked for
+                        installed.variant == locked.variant
+                            && locked
+                                .assets
+                                .first()
+                                .is_some_and(|asset| installed.url == asset.url)
+                    })
+        });
+        Ok(())
+    }
+
+    /// Lists the fonts which will be installed, updated, moved and removed
+    fn show_plan(
+        args: &Args,
+        fonts: &[Font],
+        moved: &BTreeSet<String>,
+        pruned: &[String],
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) {
+        let installed_fonts = installed_fonts.lock().unwrap();
+        let (mut installing, mut updating, mut moving) = (Vec::new(), Vec::new(), Vec::new());
+        for font in fonts {
+            let tag = font
+                .installer
+                .as_ref()
+                .and_then(|installer| installer.source.ref_tag().ok().flatten())
+                .unwrap_or("unknown tag");
+            match installed_fonts.installed.get(&font.name) {
+                None => installing.push(format!("{font} ({tag})")),
+                Some(installed) if moved.contains(&font.name) => {
+                    let install_dir = font
+                        .installer
+                        .as_ref()
+                        .and_then(|installer| installer.install_dir.as_ref())
+                        .unwrap_or(&args.config.install_dir);
+                    moving.push(format!(
+                        "{font} ({} -> {})",
+                        manifest::parent_dir(&installed.dir),
+                        collapse_home(install_dir)
+                    ));
+                }
+                Some(installed) => {
+                    let old = installed.tag.as_deref().unwrap_or("unknown tag");
+                    updating.push(format!("{font} ({old} -> {tag})"));
+                }
+            }
+        }
+
+        for (heading, fonts) in [
+            ("Installing:", installing),
+            ("Updating:", updating),
+            ("Moving:", moving),
+        ] {
+            if !fonts.is_empty() {
+                println!("{heading}");
+                fonts.iter().for_each(|font| println_green!("   {font}"));
+            }
+        }
+        if !pruned.is_empty() {
+            println!("Removing:");
+            pruned.iter().for_each(|font| println_red!("   {font}"));
+        }
+        println!();
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use crate::args::Args;
 use crate::font::Font;
 use crate::installed::InstalledFonts;
+use crate::json::{self, OutcomeRecord};
 use crate::user_prompt;
 
 use crate::actions::install::install_fonts;
@@ -31,7 +32,10 @@ Usage:
     ) -> Result<(), String> {
         println!("Updating: ");
         Args::list_fonts_green(fonts);
-        if !user_prompt("Proceed?", args) {
+        if !args.options.dry_run && !user_prompt("Proceed?", args) {
+            if args.options.json {
+                json::emit(&OutcomeRecord::with_status(fonts, "outdated"));
+            }
             return Ok(());
         }
 
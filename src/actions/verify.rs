@@ -0,0 +1,128 @@
+use std::sync::{Arc, Mutex};
+
+use crate::action::Action;
+use crate::actions::install::install_fonts;
+use crate::args::Args;
+use crate::font::Font;
+use crate::installed::InstalledFonts;
+use crate::user_prompt;
+
+pub struct VerifyAction;
+
+impl VerifyAction {
+    pub fn show_help() -> String {
+        let help = "\
+Action:
+    Check installed fonts for missing, modified or extra files
+    Verifies all fonts when unspecified
+
+Usage:
+    fin verify
+    fin verify [font(s)]
+    fin verify [font(s)] --repair
+
+Arguments:
+    --repair              Reinstall the fonts which failed verification
+";
+        print!("{help}");
+        help.to_string()
+    }
+
+    pub fn run(
+        args: &Args,
+        fonts: &[Font],
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        let verbose = args.options.verbose || args.config.verbose_files;
+
+        let ownership = installed_fonts.lock().unwrap().ownership();
+        let mut failed = Vec::new();
+        for font in fonts {
+            let Some(installed) = installed_fonts
+                .lock()
+                .unwrap()
+                .installed
+                .get(&font.name)
+                .cloned()
+            else {
+                continue;
+            };
+
+            let integrity = installed.verify(&ownership);
+            match (integrity.failed(), integrity.extra.is_empty()) {
+                (false, true) => println_green!("{font}"),
+                (false, false) => println_orange!("{font}"),
+                (true, _) => println_red!("{font}"),
+            }
+
+            if integrity.dir_missing {
+                println!("   {}", red!("Directory not found"));
+                println!("    ↪ {}", installed.dir);
+            }
+            integrity
+                .missing
+                .iter()
+                .for_each(|file| println!("   {} {file}", red!("Missing: ")));
+            integrity
+                .modified
+                .iter()
+                .for_each(|file| println!("   {} {file}", red!("Modified:")));
+            integrity
+                .extra
+                .iter()
+                .for_each(|file| println!("   {} {file}", orange!("Extra:   ")));
+            if verbose {
+                integrity
+                    .unverified
+                    .iter()
+                    .for_each(|file| println!("   {} {file}", orange!("No sum:  ")));
+            } else if !integrity.unverified.is_empty() {
+                println!(
+                    "   {}",
+                    orange!(&format!(
+                        "{} file(s) have no recorded sum (reinstall to record them)",
+                        integrity.unverified.len()
+                    ))
+                );
+            }
+
+            if integrity.failed() {
+                failed.push(font.name.clone());
+            }
+        }
+
+        if failed.is_empty() {
+            return Ok(());
+        }
+
+        if !args.options.repair {
+            println!("\nNote: try passing `--repair` to reinstall the affected fonts");
+            return Err(format!("{} font(s) failed verification", failed.len()));
+        }
+
+        println!("\nRepairing: ");
+        failed.iter().for_each(|font| println_green!("   {font}"));
+        println!();
+
+        if !user_prompt("Proceed?", args) {
+            return Ok(());
+        }
+
+        let reinstall_args = Arc::new(Self::repair_args(args));
+        let mut fonts: Box<[Font]> =
+            Font::get_actionable_fonts(&reinstall_args, &failed, installed_fonts)
+                .map_err(|e| e.to_string())?
+                .into();
+
+        install_fonts(&reinstall_args, &mut fonts, installed_fonts)
+    }
+
+    /// Returns the arguments the failed fonts are reinstalled with by `--repair`
+    #[must_use]
+    pub fn repair_args(args: &Args) -> Args {
+        Args {
+            action: Action::Reinstall,
+            ..args.clone()
+        }
+    }
+}
@@ -1,8 +1,10 @@
+use crate::Config;
 use crate::action::Action;
+use crate::actions::help::HelpAction;
 use crate::font::Font;
 use crate::options::Options;
 use crate::paths;
-use crate::Config;
+use crate::suggest;
 
 use std::env;
 
@@ -16,25 +18,95 @@ pub struct Args {
 impl Args {
     /// Loads the user-specified actions and arguments
     pub fn build() -> Result<(Self, Vec<String>), String> {
-        let mut args = env::args();
-        args.next();
-
-        let action = Action::parse(args.next().as_ref())?;
+        Self::parse(env::args().skip(1), Config::load()?)
+    }
 
+    /// Parses the action, arguments and items (excluding the program name).
+    /// Items which start with `-` may be passed after `--`.
+    pub fn parse(
+        args: impl IntoIterator<Item = String>,
+        mut config: Config,
+    ) -> Result<(Self, Vec<String>), String> {
+        let mut args = args.into_iter();
         let mut flags = Vec::new();
+        let mut action = None;
         let mut items = Vec::new();
-        for item in args {
-            if item.chars().nth(0).unwrap() == '-' {
-                flags.push(item);
+
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                items.extend(args.by_ref());
+            } else if let Some(long) = arg.strip_prefix("--") {
+                let (name, value) = match long.split_once('=') {
+                    Some((name, value)) => (format!("--{name}"), Some(value.to_string())),
+                    None => (arg.clone(), None),
+                };
+                let argument = Options::find_long(&name).ok_or_else(|| unknown_argument(&name))?;
+                let value = match (argument.value, value) {
+                    (Some(_), Some(value)) => Some(value),
+                    (Some(value_name), None) => Some(
+                        args.next()
+                            .ok_or_else(|| format!("Missing value: {name}=[{value_name}]"))?,
+                    ),
+                    (None, Some(_)) => {
+                        return Err(format!("Argument does not take a value: {name}"));
+                    }
+                    (None, None) => None,
+                };
+                flags.push((argument, value));
+            } else if let Some(shorts) = arg.strip_prefix('-')
+                && !shorts.is_empty()
+            {
+                let mut shorts = shorts.chars();
+                while let Some(short) = shorts.next() {
+                    let argument = Options::find_short(short)
+                        .ok_or_else(|| unknown_argument(&format!("-{short}")))?;
+                    let value = match argument.value {
+                        Some(value_name) => Some(match shorts.by_ref().collect::<String>() {
+                            value if !value.is_empty() => value,
+                            _ => args
+                                .next()
+                                .ok_or_else(|| format!("Missing value: -{short} [{value_name}]"))?,
+                        }),
+                        None => None,
+                    };
+                    flags.push((argument, value));
+                }
+            } else if action.is_none() {
+                action = Some(arg);
             } else {
-                items.push(item);
+                items.push(arg);
             }
         }
 
-        let mut config = Config::load()?;
+        if action.iter().chain(&items).any(String::is_empty) {
+            return Err("Empty items are not allowed".into());
+        }
+
+        // Everything after `--` is an item, so the action must precede it
+        let action = match action {
+            Some(action) => Action::parse(Some(&action))?,
+            None if items.is_empty() => Action::Help,
+            None => return Err("Missing action before `--`".into()),
+        };
+
+        if let Some((argument, _)) = flags
+            .iter()
+            .find(|(argument, _)| !argument.supports(&action))
+        {
+            return Err(format!(
+                "Argument not supported by `{}`: {}",
+                action.aliases()[0],
+                argument.long
+            ));
+        }
+
         let options = Options::build(&flags, &mut config)?;
         paths::expand_home(&mut config.install_dir);
 
+        if options.json && options.answer.is_none() && action != Action::List && !options.dry_run {
+            return Err("Prompts cannot be answered with `--json`; use `--yes` or `--no`".into());
+        }
+
         Ok((
             Args {
                 action,
@@ -55,3 +127,20 @@ impl Args {
         println!();
     }
 }
+
+/// Prints the help message, and returns the error message for an unknown
+/// argument, suggesting the closest known argument
+fn unknown_argument(name: &str) -> String {
+    HelpAction::run(&Action::Help);
+    println!();
+
+    let arguments = Options::ARGUMENTS.iter().map(|argument| argument.long);
+    match name
+        .starts_with("--")
+        .then(|| suggest(name, arguments))
+        .flatten()
+    {
+        Some(argument) => format!("Unknown argument: {name} (did you mean `{argument}`?)"),
+        None => format!("Unknown argument: {name}"),
+    }
+}
@@ -0,0 +1,184 @@
+use crate::bar::ProgressBar;
+use crate::checksum::{Checksum, sha256_data};
+use crate::file_action::FileAction;
+use crate::format_size;
+
+use std::collections::BTreeMap;
+use std::io::{self, stdout, Write};
+
+use futures::stream::StreamExt;
+use reqwest::header::USER_AGENT;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+
+/// A single entry of the installer's `action` field:
+/// the file to download, what to do with it, and how to verify it
+#[derive(Debug)]
+pub struct Asset {
+    pub action: FileAction,
+    pub check: Option<Checksum>,
+    /// SHA-256 sum recorded by the lockfile, which the download must match
+    pub locked_sum: Option<String>,
+
+    pub url: String,
+    pub data: Option<Vec<u8>>,
+    pub data_size: f64,
+}
+
+impl<'de> Deserialize<'de> for Asset {
+    /// Reads the `check` field of the entry, and the `FileAction`
+    /// from the remaining fields (e.g. `[action.Extract]`)
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut table = toml::Table::deserialize(deserializer)?;
+        let check = table
+            .remove("check")
+            .map(toml::Value::try_into)
+            .transpose()
+            .map_err(D::Error::custom)?;
+
+        Ok(Self {
+            action: toml::Value::Table(table)
+                .try_into()
+                .map_err(D::Error::custom)?,
+            check,
+            locked_sum: None,
+            url: String::new(),
+            data: None,
+            data_size: 0.0,
+        })
+    }
+}
+
+impl Asset {
+    pub fn validate(
+        &mut self,
+        tag: Option<&str>,
+        has_font_page: bool,
+        name: &str,
+    ) -> Result<(), String> {
+        if let Some(check) = &mut self.check {
+            check.validate(tag, has_font_page, name)?;
+        }
+        self.action.validate(tag, name)
+    }
+
+    /// Downloads the asset and stores its contents in `self.data`
+    pub async fn download(
+        &mut self,
+        font_page: Option<String>,
+        reqwest_client: &reqwest::Client,
+        installer_name: &str,
+    ) -> Result<(), String> {
+        let mut progress_bar = ProgressBar::new("Downloading:");
+
+        print!("… Downloading…",);
+        let _ = stdout().flush();
+
+        if let Some(checksum) = &mut self.check {
+            checksum
+                .obtain(font_page, &self.url, reqwest_client, installer_name)
+                .await
+                .inspect_err(|_| progress_bar.fail())?;
+        }
+
+        let remote_data = reqwest_client
+            .get(&self.url)
+            .header(USER_AGENT, "fin")
+            .send()
+            .await
+            .map_err(|e| {
+                progress_bar.fail();
+                e.to_string()
+            })?;
+
+        self.data_size = remote_data.content_length().unwrap_or_default() as f64;
+        let file_size = format_size(self.data_size);
+
+        let mut downloaded_bytes = 0;
+
+        let mut buffer = Vec::new();
+        let mut stream = remote_data.bytes_stream();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| {
+                progress_bar.fail();
+                e.to_string()
+            })?;
+
+            io::copy(&mut chunk.as_ref(), &mut buffer).map_err(|e| e.to_string())?;
+
+            downloaded_bytes += chunk.len();
+
+            let downloaded_bytes = downloaded_bytes as f64;
+            if downloaded_bytes > self.data_size {
+                self.data_size = downloaded_bytes;
+            }
+
+            let progress_text = format_size(downloaded_bytes);
+            progress_bar.update_progress(
+                downloaded_bytes / self.data_size,
+                &format!(" {progress_text} / {file_size}"),
+            );
+        }
+
+        self.data = Some(buffer);
+
+        progress_bar.pass();
+
+        Ok(())
+    }
+
+    /// Verifies downloaded data integrity using a checksum
+    pub fn verify(&mut self) -> Result<(), String> {
+        let data = self.data.as_ref().unwrap().as_slice();
+        if let Some(expected) = &self.locked_sum {
+            let sum = sha256_data(data);
+            if sum != *expected {
+                return Err(format!(
+                    "{}: Download does not match the lockfile, it may have been replaced upstream\n   Expected SHA-256: {expected}\n   Actual SHA-256:   {sum}",
+                    self.url
+                ));
+            }
+        }
+        match self.check.take() {
+            Some(mut checksum) => checksum.check(data, self.data_size, &self.url),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the name of the downloaded file
+    #[must_use]
+    pub fn file_name(&self) -> &str {
+        self.url.rsplit_once('/').unwrap_or_default().1
+    }
+}
+
+/// Checks that no two assets staged files with the same name, as the files
+/// of the later asset would replace those of the earlier one.
+/// `staged` lists the URL of each asset, and the files it staged.
+pub fn check_duplicate_files(staged: &[(&str, Vec<String>)]) -> Result<(), String> {
+    let mut sources = BTreeMap::<&str, Vec<&str>>::new();
+    for (url, files) in staged {
+        for file in files {
+            sources.entry(file).or_default().push(url);
+        }
+    }
+
+    let mut message = String::from("Multiple assets would write the same file:");
+    let mut duplicates = false;
+    for (file, urls) in sources.into_iter().filter(|(_, urls)| urls.len() > 1) {
+        duplicates = true;
+        message += &format!("\n   {file}");
+        for url in urls {
+            message += &format!("\n    ↪ {url}");
+        }
+    }
+    match duplicates {
+        true => Err(message + "\nNote: try using `rename` to change the names of extracted files"),
+        false => Ok(()),
+    }
+}
@@ -1,108 +1,314 @@
 use crate::format_size;
+use crate::signature::{SignatureCheck, SignatureKind};
 use crate::source::Source;
 use crate::{bar::ProgressBar, file_action::FileAction};
 
 use serde::Deserialize;
 
-use sha2::digest::generic_array::ArrayLength;
-use sha2::digest::{FixedOutput, HashMarker, OutputSizeUser, Update};
+use blake2::{Blake2b512, Blake2s256};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::digest::DynDigest;
 use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
-use std::io::{Read, Write, stdout};
-use std::ops::Add;
+use std::fs::File;
+use std::io::{self, Read, Write, stdout};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum Checksum {
-    SHA224 { file: Option<String> },
-    SHA256 { file: Option<String> },
-    SHA384 { file: Option<String> },
-    SHA512 { file: Option<String> },
+    MD5(ExpectedSum),
+    SHA1(ExpectedSum),
+    SHA224(ExpectedSum),
+    SHA256(ExpectedSum),
+    SHA384(ExpectedSum),
+    SHA512(ExpectedSum),
+    BLAKE2b(ExpectedSum),
+    BLAKE2s(ExpectedSum),
+    BLAKE3(ExpectedSum),
+    Minisign(SignatureCheck),
+    PGP(SignatureCheck),
 }
+
+/// Where to obtain the expected checksum from
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedSum {
+    /// Name of the checksum file linked from the font page
+    pub file: Option<String>,
+    /// The expected sum itself
+    pub sum: Option<String>,
+    /// Direct link to the checksum file
+    pub url: Option<String>,
+
+    /// Contents containing the expected sum, once obtained
+    #[serde(skip)]
+    pub contents: Option<String>,
+    /// Skip verification if the release lists the file without a digest
+    #[serde(skip)]
+    pub optional: bool,
+}
+
 impl Checksum {
-    /// Obtains the checksum and assigns it to `file`
+    /// Uses the digest of the asset listed within the GitHub API release,
+    /// skipping verification for releases which do not list digests
+    #[must_use]
+    pub fn github_digest() -> Self {
+        Self::SHA256(ExpectedSum {
+            optional: true,
+            ..ExpectedSum::default()
+        })
+    }
+
+    /// Returns the signature format and check, or `None` for checksums
+    fn signature(&mut self) -> Option<(SignatureKind, &mut SignatureCheck)> {
+        match self {
+            Checksum::Minisign(signature) => Some((SignatureKind::Minisign, signature)),
+            Checksum::PGP(signature) => Some((SignatureKind::PGP, signature)),
+            _ => None,
+        }
+    }
+
+    /// Note: Signatures are handled separately (see `signature`)
+    fn expected(&mut self) -> &mut ExpectedSum {
+        match self {
+            Checksum::MD5(expected)
+            | Checksum::SHA1(expected)
+            | Checksum::SHA224(expected)
+            | Checksum::SHA256(expected)
+            | Checksum::SHA384(expected)
+            | Checksum::SHA512(expected)
+            | Checksum::BLAKE2b(expected)
+            | Checksum::BLAKE2s(expected)
+            | Checksum::BLAKE3(expected) => expected,
+            Checksum::Minisign(_) | Checksum::PGP(_) => unreachable!("Signatures have no sum"),
+        }
+    }
+
+    /// Ensures at most one of `file`, `sum` or `url` is specified, and
+    /// substitutes `$tag`. Sums within the font page (`file`, or none of the
+    /// fields) require a font page, which direct links do not have.
+    pub fn validate(
+        &mut self,
+        tag: Option<&str>,
+        has_font_page: bool,
+        name: &str,
+    ) -> Result<(), String> {
+        if let Some((_, signature)) = self.signature() {
+            return signature.validate(tag, has_font_page, name);
+        }
+
+        let expected = self.expected();
+        match (&mut expected.file, &mut expected.sum, &mut expected.url) {
+            (Some(file), None, None) if has_font_page => FileAction::validate_file(file, tag, name),
+            (None, None, None) if has_font_page => Ok(()),
+            (Some(_), None, None) | (None, None, None) => Err(format!(
+                "{name}: Direct links require the checksum `sum` or `url` to be specified"
+            )),
+            (None, Some(value), None) | (None, None, Some(value)) => {
+                if let Some(tag) = tag {
+                    *value = value.replace("$tag", tag);
+                } else if value.contains("$tag") {
+                    return Err(format!("{name}: Use of missing field: `$tag`"));
+                }
+                Ok(())
+            }
+            _ => Err(format!(
+                "{name}: Only one of the checksum `file`, `sum` or `url` may be specified"
+            )),
+        }
+    }
+
+    /// Obtains the text containing the expected checksum and assigns it to
+    /// `contents` (or the signature of the file at `asset_url`, for signatures).
+    /// `$file` within `url` is replaced by the name of the downloaded file.
     pub async fn obtain(
         &mut self,
         font_page: Option<String>,
-        tag: Option<&str>,
+        asset_url: &str,
         reqwest_client: &reqwest::Client,
         installer_name: &str,
     ) -> Result<(), String> {
+        if let Some((kind, signature)) = self.signature() {
+            return signature
+                .obtain(kind, font_page, asset_url, reqwest_client, installer_name)
+                .await;
+        }
+
+        let asset_file = asset_url.rsplit_once('/').unwrap_or_default().1;
+        let expected = self.expected();
+
+        let file_link = match (&expected.file, &expected.sum, &expected.url) {
+            (_, Some(sum), _) => {
+                expected.contents = Some(sum.to_owned());
+                return Ok(());
+            }
+            (_, _, Some(url)) => url.replace("$file", asset_file),
+            (Some(file), _, _) => Source::find_direct_link(
+                font_page.as_ref().expect("Font page is not available"),
+                file,
+                installer_name,
+            )?,
+            (None, None, None) => {
+                expected.contents = font_page;
+                return Ok(());
+            }
+        };
+
+        expected.contents = Some(
+            reqwest_client
+                .get(&file_link)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .error_for_status()
+                .map_err(|e| e.to_string())?
+                .text()
+                .await
+                .map_err(|e| e.to_string())?,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the name of the algorithm, as used by BSD-style checksum manifests
+    #[must_use]
+    pub const fn algorithm(&self) -> &'static str {
         match self {
-            Checksum::SHA224 { file }
-            | Checksum::SHA256 { file }
-            | Checksum::SHA384 { file }
-            | Checksum::SHA512 { file } => {
-                let Some(file) = file.as_mut() else {
-                    *file = font_page;
-                    return Ok(());
-                };
-                FileAction::validate_file(file, tag, installer_name)?;
-
-                let file_link = Source::find_direct_link(
-                    &font_page.expect("Font page is not available"),
-                    file,
-                    installer_name,
-                )?;
-
-                *file = reqwest_client
-                    .get(&file_link)
-                    .send()
-                    .await
-                    .map_err(|e| e.to_string())?
-                    .text()
-                    .await
-                    .map_err(|e| e.to_string())?;
+            Checksum::MD5(_) => "MD5",
+            Checksum::SHA1(_) => "SHA1",
+            Checksum::SHA224(_) => "SHA224",
+            Checksum::SHA256(_) => "SHA256",
+            Checksum::SHA384(_) => "SHA384",
+            Checksum::SHA512(_) => "SHA512",
+            Checksum::BLAKE2b(_) => "BLAKE2b",
+            Checksum::BLAKE2s(_) => "BLAKE2s",
+            Checksum::BLAKE3(_) => "BLAKE3",
+            Checksum::Minisign(_) => "Minisign",
+            Checksum::PGP(_) => "PGP",
+        }
+    }
 
-                Ok(())
+    /// Describes the algorithm, and where the expected sum or signature
+    /// is obtained from
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let algorithm = self.algorithm();
+        let (file, url) = match self {
+            Checksum::Minisign(signature) | Checksum::PGP(signature) => {
+                (&signature.file, &signature.url)
+            }
+            Checksum::MD5(expected)
+            | Checksum::SHA1(expected)
+            | Checksum::SHA224(expected)
+            | Checksum::SHA256(expected)
+            | Checksum::SHA384(expected)
+            | Checksum::SHA512(expected)
+            | Checksum::BLAKE2b(expected)
+            | Checksum::BLAKE2s(expected)
+            | Checksum::BLAKE3(expected) => {
+                if expected.sum.is_some() {
+                    return format!("{algorithm} (sum specified by the installer)");
+                }
+                if expected.optional {
+                    return format!("{algorithm} (digest listed by the release, if any)");
+                }
+                (&expected.file, &expected.url)
             }
+        };
+
+        match (file, url, self) {
+            (Some(file), _, _) => format!("{algorithm} ({file})"),
+            (_, Some(url), _) => format!("{algorithm} ({url})"),
+            (None, None, Checksum::Minisign(_) | Checksum::PGP(_)) => {
+                format!("{algorithm} (signature next to the downloaded file)")
+            }
+            (None, None, _) => format!("{algorithm} (sum listed on the font page)"),
+        }
+    }
+
+    /// Returns `true` for algorithms which are vulnerable to collisions, and
+    /// therefore only protect against accidental corruption
+    #[must_use]
+    pub const fn is_weak(&self) -> bool {
+        matches!(self, Checksum::MD5(_) | Checksum::SHA1(_))
+    }
+
+    fn hasher(&self) -> Hasher {
+        match self {
+            Checksum::MD5(_) => Hasher::Digest(Box::new(Md5::new())),
+            Checksum::SHA1(_) => Hasher::Digest(Box::new(Sha1::new())),
+            Checksum::SHA224(_) => Hasher::Digest(Box::new(Sha224::new())),
+            Checksum::SHA256(_) => Hasher::Digest(Box::new(Sha256::new())),
+            Checksum::SHA384(_) => Hasher::Digest(Box::new(Sha384::new())),
+            Checksum::SHA512(_) => Hasher::Digest(Box::new(Sha512::new())),
+            Checksum::BLAKE2b(_) => Hasher::Digest(Box::new(Blake2b512::new())),
+            Checksum::BLAKE2s(_) => Hasher::Digest(Box::new(Blake2s256::new())),
+            Checksum::BLAKE3(_) => Hasher::Blake3(Box::default()),
+            Checksum::Minisign(_) | Checksum::PGP(_) => unreachable!("Signatures have no sum"),
         }
     }
 
-    /// Computes the hash sum of `data` and compares it to `self.file`.
+    /// Computes the hash sum of `data` and compares it to the sum listed for
+    /// the downloaded file within the obtained `contents`, or verifies the
+    /// obtained signature of `data`.
     /// Returns `Ok` if the checksums match, or `Err` if they do not.
-    pub fn check(&mut self, data: &[u8], data_size: f64, source: &Source) -> Result<(), String> {
-        let filename = source
-            .ref_direct_url()?
-            .rsplit_once('/')
-            .unwrap_or_default()
-            .1;
+    pub fn check(&mut self, data: &[u8], data_size: f64, url: &str) -> Result<(), String> {
+        let filename = url.rsplit_once('/').unwrap_or_default().1;
+
+        if let Some((kind, signature)) = self.signature() {
+            print!("… Verifying:   {filename}");
+            let _ = stdout().flush();
+            let mut progress_bar = ProgressBar::new("Verifying:");
+            return match signature.verify(kind, data) {
+                Ok(()) => {
+                    progress_bar.pass();
+                    Ok(())
+                }
+                Err(e) => {
+                    progress_bar.fail();
+                    Err(format!("{filename}: Signature verification failed: {e}"))
+                }
+            };
+        }
+
+        let algorithm = self.algorithm();
+        let expected = self.expected();
+        let contents = expected
+            .contents
+            .take()
+            .ok_or_else(|| format!("{filename}: Checksum was not obtained"))?;
+
+        if expected.optional && lists_no_digest(&contents, filename) {
+            return Ok(());
+        }
+
         print!("… Verifying:   {filename}");
         let _ = stdout().flush();
-        match self {
-            Self::SHA224 { file } => {
-                let expected_sum = file.as_ref().unwrap();
-                Self::sha_check(expected_sum, Sha224::new(), data, data_size, filename)
-            }
-            Self::SHA256 { file } => {
-                let expected_sum = file.as_ref().unwrap();
-                Self::sha_check(expected_sum, Sha256::new(), data, data_size, filename)
-            }
-            Self::SHA384 { file } => {
-                let expected_sum = file.as_ref().unwrap();
-                Self::sha_check(expected_sum, Sha384::new(), data, data_size, filename)
-            }
-            Self::SHA512 { file } => {
-                let expected_sum = file.as_ref().unwrap();
-                Self::sha_check(expected_sum, Sha512::new(), data, data_size, filename)
-            }
+        self.hash_check(&contents, data, data_size, filename)?;
+
+        if self.is_weak() {
+            println_orange!(
+                "   Note: {algorithm} only protects against corrupted downloads, not tampering"
+            );
         }
+        Ok(())
     }
 
-    /// Computes the hash sum for the input `data`, and compares it to the `expected_sum`.
-    /// Returns `Ok` if the `expected_sum` contains the newly calculated sum, or `Err`
-    /// if it does not.
-    fn sha_check<H>(
-        expected_sum: &str,
-        mut hasher: H,
+    /// Computes the hash sum for the input `data`, and compares it to the sum
+    /// listed for `filename` within `contents` (see `find_expected_sum`).
+    /// Returns `Ok` if the sums are equal, or `Err` if they are not, or if
+    /// `contents` does not list a sum for `filename`.
+    fn hash_check(
+        &self,
+        contents: &str,
         mut data: &[u8],
         data_size: f64,
         filename: &str,
-    ) -> Result<(), String>
-    where
-        H: FixedOutput + Default + Update + HashMarker,
-        <H as OutputSizeUser>::OutputSize: Add,
-        <<H as OutputSizeUser>::OutputSize as Add>::Output: ArrayLength<u8>,
-    {
+    ) -> Result<(), String> {
         let mut progress_bar = ProgressBar::new("Verifying:");
+        let expected_sum = find_expected_sum(contents, filename, self.algorithm())
+            .inspect_err(|_| progress_bar.fail())?;
+
+        let mut hasher = self.hasher();
         let bytes_total_text = format_size(data_size);
         let mut bytes_progress = 0;
 
@@ -130,7 +336,7 @@ impl Checksum {
         }
 
         let sum = hasher.finalize();
-        if expected_sum.contains(&format!("{sum:x}")) {
+        if expected_sum.eq_ignore_ascii_case(&sum) {
             progress_bar.pass();
             Ok(())
         } else {
@@ -139,3 +345,131 @@ impl Checksum {
         }
     }
 }
+
+/// Computes the SHA-256 sum of the file at `path` as a hexadecimal string
+pub fn sha256_file(path: &str) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    io::copy(&mut file, &mut hasher).map_err(|e| e.to_string())?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Computes the SHA-256 sum of `data` as a hexadecimal string
+#[must_use]
+pub fn sha256_data(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// BLAKE3 does not implement the same `digest` traits as the other algorithms
+enum Hasher {
+    Digest(Box<dyn DynDigest>),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Digest(hasher) => hasher.update(data),
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    /// Returns the hash sum as a hexadecimal string
+    fn finalize(self) -> String {
+        match self {
+            Hasher::Digest(hasher) => hasher
+                .finalize()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+            Hasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+/// Returns `true` if the GitHub API release within `contents` lists
+/// `filename` without a digest, as releases from before digests were
+/// introduced do not list them
+fn lists_no_digest(contents: &str, filename: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(contents).is_ok_and(|release| {
+        release["assets"].as_array().is_some_and(|assets| {
+            assets
+                .iter()
+                .any(|asset| asset["name"].as_str() == Some(filename) && asset["digest"].is_null())
+        })
+    })
+}
+
+/// Finds the sum of `filename` within `contents`, which may be:
+/// - a bare sum, which applies to any file
+/// - a checksum manifest in the coreutils (`<sum>  <file>`, `<sum> *<file>`)
+///   or BSD (`<algorithm> (<file>) = <sum>`) format
+/// - a GitHub API release, listing the `digest` of each asset
+///
+/// Returns `Err` if no sum is listed for `filename`, or if the listed sums disagree.
+pub fn find_expected_sum(
+    contents: &str,
+    filename: &str,
+    algorithm: &str,
+) -> Result<String, String> {
+    let contents = contents.trim();
+    if !contents.is_empty() && contents.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(contents.to_owned());
+    }
+
+    if let Ok(release) = serde_json::from_str::<serde_json::Value>(contents)
+        && let Some(assets) = release["assets"].as_array()
+    {
+        let asset = assets
+            .iter()
+            .find(|asset| asset["name"].as_str() == Some(filename))
+            .ok_or_else(|| format!("{filename}: File is not listed within the release"))?;
+        let Some((digest_algorithm, sum)) =
+            asset["digest"].as_str().and_then(|d| d.split_once(':'))
+        else {
+            return Err(format!("{filename}: The release does not list a digest"));
+        };
+        if !digest_algorithm.eq_ignore_ascii_case(algorithm) {
+            return Err(format!(
+                "{filename}: The release lists a {digest_algorithm} digest, not {algorithm}"
+            ));
+        }
+        return Ok(sum.to_owned());
+    }
+
+    let names_file = |name: &str| {
+        let name = name.strip_prefix("./").unwrap_or(name);
+        name == filename
+            || name
+                .rsplit_once('/')
+                .is_some_and(|(_, name)| name == filename)
+    };
+
+    let mut sums = contents.lines().filter_map(|line| {
+        let line = line.trim_end();
+        // BSD: `SHA256 (file) = sum`
+        if let Some((tagged, sum)) = line.rsplit_once(" = ")
+            && let Some((tag, name)) = tagged.split_once(" (")
+            && let Some(name) = name.strip_suffix(')')
+        {
+            return (tag.eq_ignore_ascii_case(algorithm) && names_file(name)).then_some(sum);
+        }
+        // coreutils: `sum  file` (text mode) or `sum *file` (binary mode)
+        let line = line.strip_prefix('\\').unwrap_or(line);
+        let (sum, name) = line.split_once(' ')?;
+        let name = name.strip_prefix([' ', '*'])?;
+        (sum.chars().all(|c| c.is_ascii_hexdigit()) && names_file(name)).then_some(sum)
+    });
+
+    let Some(sum) = sums.next() else {
+        return Err(format!("{filename}: No checksum is listed for the file"));
+    };
+    if sums.any(|other| !other.eq_ignore_ascii_case(sum)) {
+        return Err(format!(
+            "{filename}: Conflicting checksums are listed for the file"
+        ));
+    }
+    Ok(sum.to_owned())
+}
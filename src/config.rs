@@ -1,4 +1,5 @@
 use crate::paths::{config_dir, config_file_path};
+use crate::suggest;
 use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
 
@@ -94,8 +95,42 @@ impl Config {
 
     pub fn panic_if_invalid(&self) {
         assert!(
-            self.install_dir.trim().split('/').count() > 2,
+            self.has_valid_install_dir(),
             "The specified installation directory is invalid"
         );
     }
+
+    #[must_use]
+    pub fn has_valid_install_dir(&self) -> bool {
+        self.install_dir.trim().split('/').count() > 2
+    }
+
+    /// Returns the problems found within the contents of a configuration
+    /// file, including unknown fields (which are otherwise ignored)
+    #[must_use]
+    pub fn problems(contents: &str) -> Vec<String> {
+        let table = match contents.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => return vec![format!("Failed to parse: {}", e.message())],
+        };
+        let fields = toml::Table::try_from(Self::default()).unwrap_or_default();
+        let mut problems = table
+            .keys()
+            .filter(|key| !fields.contains_key(*key))
+            .map(|key| match suggest(key, fields.keys().map(String::as_str)) {
+                Some(field) => format!("Unknown field: `{key}` (did you mean `{field}`?)"),
+                None => format!("Unknown field: `{key}`"),
+            })
+            .collect::<Vec<_>>();
+
+        match toml::from_str::<Self>(contents) {
+            Ok(config) if !config.has_valid_install_dir() => problems.push(format!(
+                "The installation directory is invalid: \"{}\"",
+                config.install_dir
+            )),
+            Ok(_) => (),
+            Err(e) => problems.push(e.message().to_owned()),
+        }
+        problems
+    }
 }
@@ -0,0 +1,75 @@
+use std::collections::BTreeSet;
+
+/// Returns the installer name of a font (without `@variant` or `:tag`)
+#[must_use]
+pub fn base_name(font: &str) -> &str {
+    font.split(['@', ':']).next().unwrap_or(font)
+}
+
+/// Returns the installer names of the fonts which `expand_dependencies`
+/// only returned as dependencies of other fonts
+#[must_use]
+pub fn dependency_names(expanded: &[(String, bool)]) -> BTreeSet<String> {
+    expanded
+        .iter()
+        .filter(|(_, dependency)| *dependency)
+        .map(|(font, _)| base_name(font).to_owned())
+        .collect()
+}
+
+/// Returns the dependencies of an installer, and whether it is a bundle,
+/// or `None` if there is no such installer (see `Installer::read_dependencies`)
+pub type ReadDependencies = dyn Fn(&str) -> Option<(Vec<String>, bool)>;
+
+/// Adds the dependencies of each font before the font itself, and leaves
+/// out bundles (which only consist of dependencies). Returns each font,
+/// and whether it is only installed as a dependency of another font.
+pub fn expand_dependencies(
+    fonts: &[String],
+    read_dependencies: &ReadDependencies,
+) -> Result<Vec<(String, bool)>, String> {
+    let mut expanded = Vec::new();
+    for font in fonts {
+        expand(font, &mut Vec::new(), &mut expanded, read_dependencies)?;
+    }
+    Ok(expanded)
+}
+
+fn expand(
+    font: &str,
+    parents: &mut Vec<String>,
+    expanded: &mut Vec<(String, bool)>,
+    read_dependencies: &ReadDependencies,
+) -> Result<(), String> {
+    let name = base_name(font);
+    if parents.iter().any(|parent| parent == name) {
+        return Err(format!(
+            "Dependency cycle: {} -> {name}",
+            parents.join(" -> ")
+        ));
+    }
+    let (depends, is_bundle) = match (read_dependencies(name), parents.last()) {
+        (Some(dependencies), _) => dependencies,
+        (None, Some(parent)) => return Err(format!("{parent}: Dependency not found: {name}")),
+        // Missing installers are reported when parsing them
+        (None, None) => (Vec::new(), false),
+    };
+
+    parents.push(name.to_owned());
+    for dependency in &depends {
+        expand(dependency, parents, expanded, read_dependencies)?;
+    }
+    parents.pop();
+    if is_bundle {
+        return Ok(());
+    }
+
+    let dependency = !parents.is_empty();
+    match expanded.iter_mut().find(|(f, _)| base_name(f) == name) {
+        // Fonts which are requested explicitly are not only dependencies
+        Some(entry) if !dependency => *entry = (font.to_owned(), false),
+        Some(_) => (),
+        None => expanded.push((font.to_owned(), dependency)),
+    }
+    Ok(())
+}
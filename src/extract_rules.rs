@@ -0,0 +1,134 @@
+use crate::wildcards::*;
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Deserialize;
+
+/// What to do when several archive entries would be written to the same file
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Refuse to install, listing the conflicting entries
+    #[default]
+    Error,
+    /// Append `-1`, `-2`, … to the file names of later entries
+    /// (in order of their path within the archive)
+    Suffix,
+}
+
+/// Decides which files get extracted from an archive, and where they are written
+#[derive(Debug, Default)]
+pub struct ExtractRules {
+    pub include: Box<[String]>,
+    pub exclude: Box<[String]>,
+    pub keep_folders: bool,
+    pub strip_components: usize,
+    pub rename: BTreeMap<String, String>,
+    pub on_conflict: OnConflict,
+}
+
+impl ExtractRules {
+    /// Returns `true` if the path is matched by `include` and not by `exclude`
+    #[must_use]
+    pub fn includes(&self, path: &str) -> bool {
+        match_any_wildcard(path, &self.include) && !match_any_wildcard(path, &self.exclude)
+    }
+
+    /// Returns the path (relative to the staging directory) where the archive
+    /// entry should be written, or `None` if `strip_components` removes it.
+    ///
+    /// The longest `rename` pattern matching the entry takes precedence,
+    /// otherwise leading folders are stripped and flattened as configured.
+    #[must_use]
+    pub fn destination(&self, path: &str) -> Option<String> {
+        let path = path.strip_prefix("./").unwrap_or(path);
+
+        if let Some((_, target)) = self
+            .rename
+            .iter()
+            .filter(|(pattern, _)| match_wildcard(path, pattern))
+            .max_by_key(|(pattern, _)| pattern.len())
+        {
+            return Some(target.to_owned());
+        }
+
+        let components: Vec<&str> = path.split('/').collect();
+        if components.len() <= self.strip_components {
+            return None;
+        }
+
+        Some(match self.keep_folders {
+            true => components[self.strip_components..].join("/"),
+            false => components.last().unwrap().to_string(),
+        })
+    }
+
+    /// Returns the destination of every archive entry in `paths`
+    /// (see `destination`), resolving conflicts according to `on_conflict`
+    pub fn resolve(&self, paths: &[String]) -> Result<Vec<Option<String>>, String> {
+        let mut destinations: Vec<Option<String>> =
+            paths.iter().map(|path| self.destination(path)).collect();
+
+        let mut sources = BTreeMap::<&str, Vec<usize>>::new();
+        for (index, destination) in destinations.iter().enumerate() {
+            if let Some(destination) = destination {
+                sources.entry(destination).or_default().push(index);
+            }
+        }
+
+        let conflicts: Vec<(String, Vec<usize>)> = sources
+            .into_iter()
+            .filter(|(_, indices)| indices.len() > 1)
+            .map(|(destination, indices)| (destination.to_owned(), indices))
+            .collect();
+
+        if conflicts.is_empty() {
+            return Ok(destinations);
+        }
+
+        match self.on_conflict {
+            OnConflict::Error => {
+                let mut message = String::from("Multiple files would be written to the same path:");
+                for (destination, indices) in &conflicts {
+                    message += &format!("\n   {destination}");
+                    for index in indices {
+                        message += &format!("\n    ↪ {}", paths[*index]);
+                    }
+                }
+                message += "\nNote: try setting `on_conflict = \"Suffix\"`, or use `rename`";
+                Err(message)
+            }
+            OnConflict::Suffix => {
+                let mut taken: HashSet<String> = destinations.iter().flatten().cloned().collect();
+
+                for (destination, mut indices) in conflicts {
+                    indices.sort_by_key(|index| &paths[*index]);
+                    let (stem, ext) = split_extension(&destination);
+
+                    let mut n = 0;
+                    for index in indices.into_iter().skip(1) {
+                        let suffixed = loop {
+                            n += 1;
+                            let suffixed = format!("{stem}-{n}{ext}");
+                            if !taken.contains(&suffixed) {
+                                break suffixed;
+                            }
+                        };
+                        taken.insert(suffixed.clone());
+                        destinations[index] = Some(suffixed);
+                    }
+                }
+                Ok(destinations)
+            }
+        }
+    }
+}
+
+/// Splits the path into the part before the file extension, and the extension
+/// (including the `.`), e.g. `"dir/Font.ttf"` becomes `("dir/Font", ".ttf")`
+fn split_extension(path: &str) -> (&str, &str) {
+    let name_start = path.rfind('/').map_or(0, |i| i + 1);
+    match path[name_start..].rfind('.') {
+        Some(dot) if dot > 0 => path.split_at(name_start + dot),
+        _ => (path, ""),
+    }
+}
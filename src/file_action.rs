@@ -1,10 +1,13 @@
 use crate::Args;
 use crate::bar::ProgressBar;
-use crate::installer::Installer;
+use crate::extract_rules::{ExtractRules, OnConflict};
 use crate::wildcards::*;
+use crate::woff;
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::{self, Read, Write, stdout};
+use std::path::Path;
 
 use flate2::read::GzDecoder;
 use tar::Archive;
@@ -12,18 +15,24 @@ use tar::Archive;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub enum FileAction {
     Extract {
         file: String,
         include: Box<[String]>,
         exclude: Option<Box<[String]>>,
         keep_folders: Option<bool>,
+        strip_components: Option<usize>,
+        rename: Option<BTreeMap<String, String>>,
+        on_conflict: Option<OnConflict>,
+        convert_web_fonts: Option<bool>,
 
-        #[serde(default, skip_serializing)]
+        #[serde(skip)]
         file_type: FileType,
     },
     SingleFile {
         file: String,
+        convert_web_fonts: Option<bool>,
     },
     None,
 }
@@ -45,6 +54,7 @@ impl FileAction {
                 file,
                 include,
                 exclude,
+                rename,
                 file_type,
                 ..
             } => {
@@ -57,14 +67,35 @@ impl FileAction {
                         || None,
                         |p| Some(p.iter().map(|p| p.replace("$tag", tag)).collect()),
                     );
+                    *rename = rename.as_ref().map(|rename| {
+                        rename
+                            .iter()
+                            .map(|(p, r)| (p.replace("$tag", tag), r.replace("$tag", tag)))
+                            .collect()
+                    });
                 } else if include.concat().contains("$tag")
                     || exclude
                         .as_ref()
                         .is_some_and(|exclude| exclude.concat().contains("$tag"))
+                    || rename.as_ref().is_some_and(|rename| {
+                        rename
+                            .iter()
+                            .any(|(p, r)| p.contains("$tag") || r.contains("$tag"))
+                    })
                 {
                     return Err(format!("{name}: Use of missing field: `$tag`"));
                 }
 
+                for target in rename.iter().flat_map(|rename| rename.values()) {
+                    if target.is_empty()
+                        || target.starts_with('/')
+                        || target.ends_with('/')
+                        || target.split('/').any(|c| c == "..")
+                    {
+                        return Err(format!("{name}: Invalid rename target: \"{target}\""));
+                    }
+                }
+
                 Self::validate_file(file, tag, name)?;
                 *file_type = Self::get_file_type(file);
                 if matches!(file_type, FileType::Unsupported) {
@@ -72,7 +103,7 @@ impl FileAction {
                 }
                 Ok(())
             }
-            FileAction::SingleFile { file } => {
+            FileAction::SingleFile { file, .. } => {
                 Self::validate_file(file, tag, name)?;
                 Ok(())
             }
@@ -114,76 +145,58 @@ impl FileAction {
         }
     }
 
-    /// Prepares the font for installation by writing its
-    /// files to a staging directory (`paths::staging_dir`)
+    /// Prepares the font for installation by writing its files to a staging
+    /// directory (`paths::staging_dir`), and returns the staged files
     pub fn stage_install(
         &mut self,
-        installer: &mut Installer,
+        url: &str,
         data: Vec<u8>,
-        extract_to: String,
+        extract_to: &str,
         args: &Args,
-    ) -> Result<(), String> {
+    ) -> Result<Vec<String>, String> {
+        let mut files = Vec::new();
         match self {
             FileAction::Extract {
                 file,
                 include,
                 exclude,
                 keep_folders,
+                strip_components,
+                rename,
+                on_conflict,
+                convert_web_fonts,
                 file_type,
             } => {
+                let rules = ExtractRules {
+                    include: std::mem::take(include),
+                    exclude: exclude.take().unwrap_or_else(|| [].into()),
+                    keep_folders: keep_folders.unwrap_or_default(),
+                    strip_components: strip_components.unwrap_or_default(),
+                    rename: rename.take().unwrap_or_default(),
+                    on_conflict: on_conflict.unwrap_or_default(),
+                };
                 let reader = std::io::Cursor::new(data);
-                installer.files = match file_type {
-                    FileType::Zip => Self::extract_zip(
-                        args,
-                        reader,
-                        &extract_to,
-                        include,
-                        &exclude.take().unwrap_or_else(|| [].into()),
-                        keep_folders.unwrap_or_default(),
-                    )?,
-                    FileType::Tar => Self::extract_tar(
-                        args,
-                        Archive::new(reader),
-                        &extract_to,
-                        include,
-                        &exclude.take().unwrap_or_else(|| [].into()),
-                        keep_folders.unwrap_or_default(),
-                    )?,
-                    FileType::TarGz => Self::extract_tar_gz(
-                        args,
-                        reader,
-                        &extract_to,
-                        include,
-                        &exclude.take().unwrap_or_else(|| [].into()),
-                        keep_folders.unwrap_or_default(),
-                    )?,
-                    FileType::TarXz => Self::extract_tar_xz(
-                        args,
-                        reader,
-                        &extract_to,
-                        include,
-                        &exclude.take().unwrap_or_else(|| [].into()),
-                        keep_folders.unwrap_or_default(),
-                    )?,
+                files = match file_type {
+                    FileType::Zip => Self::extract_zip(args, reader, extract_to, &rules)?,
+                    FileType::Tar => {
+                        Self::extract_tar(args, Archive::new(reader), extract_to, &rules)?
+                    }
+                    FileType::TarGz => Self::extract_tar_gz(args, reader, extract_to, &rules)?,
+                    FileType::TarXz => Self::extract_tar_xz(args, reader, extract_to, &rules)?,
                     FileType::Unsupported => {
                         return Err(format!("Unsupported archive extension: {file}"));
                     }
                 };
-                for file in &mut installer.files {
-                    if file.starts_with("./") {
-                        *file = file[2..].to_string();
-                    }
+                if convert_web_fonts.unwrap_or_default() {
+                    Self::convert_web_fonts(args, extract_to, &mut files)?;
                 }
             }
-            FileAction::SingleFile { .. } => {
+            FileAction::SingleFile {
+                convert_web_fonts, ..
+            } => {
                 let verbose = args.options.verbose || args.config.verbose_files;
-                fs::create_dir_all(&extract_to).map_err(|e| e.to_string())?;
-                let file = installer
-                    .source
-                    .ref_direct_url()?
-                    .rsplit_once('/')
-                    .unwrap()
-                    .1;
+                fs::create_dir_all(extract_to).map_err(|e| e.to_string())?;
+                let file = url.rsplit_once('/').unwrap().1;
 
                 let mut progress_bar = ProgressBar::new("Staging:");
 
@@ -196,8 +209,8 @@ impl FileAction {
                     false => progress_bar.update_progress(0.0, " 0 / 1"),
                 }
 
-                installer.files.push(file.to_string());
-                fs::write(extract_to + file, data).map_err(|e| {
+                files.push(file.to_string());
+                fs::write([extract_to, file].concat(), data).map_err(|e| {
                     progress_bar.fail();
                     println_red!("{e}");
                     e.to_string()
@@ -210,10 +223,97 @@ impl FileAction {
                         progress_bar.pass();
                     }
                 }
+
+                if convert_web_fonts.unwrap_or_default() {
+                    Self::convert_web_fonts(args, extract_to, &mut files)?;
+                }
             }
             FileAction::None => panic!(),
         }
 
+        Ok(files)
+    }
+
+    /// Decodes the staged WOFF/WOFF2 files into TTF/OTF files,
+    /// replacing them in the staging directory and in `files`
+    fn convert_web_fonts(
+        args: &Args,
+        extract_to: &str,
+        files: &mut [String],
+    ) -> Result<(), String> {
+        let web_fonts = files.iter().filter(|file| woff::is_web_font(file)).count();
+        if web_fonts == 0 {
+            return Ok(());
+        }
+
+        let verbose = args.options.verbose || args.config.verbose_files;
+        match verbose {
+            true => println!("Converting:"),
+            false => {
+                print!("… Converting…");
+                let _ = stdout().flush();
+            }
+        }
+
+        let mut progress_bar = ProgressBar::new("Converting:");
+        let mut files_processed = 0.0;
+
+        for index in 0..files.len() {
+            let file = &files[index];
+            if !woff::is_web_font(file) {
+                continue;
+            }
+            files_processed += 1.0;
+
+            match verbose {
+                true => {
+                    print!("   {file} ... ");
+                    let _ = stdout().flush();
+                }
+                false => progress_bar.update_progress(
+                    files_processed / web_fonts as f64,
+                    &format!(" {files_processed} / {web_fonts}"),
+                ),
+            }
+
+            let result = fs::read([extract_to, file].concat())
+                .map_err(|e| e.to_string())
+                .and_then(|data| woff::decode(&data))
+                .and_then(|(font, extension)| {
+                    let converted = format!("{}.{extension}", file.rsplit_once('.').unwrap().0);
+                    if files.contains(&converted) {
+                        return Err(format!("Converted file already exists: {converted}"));
+                    }
+                    fs::write([extract_to, &converted].concat(), font)
+                        .and_then(|()| fs::remove_file([extract_to, file].concat()))
+                        .map_err(|e| e.to_string())?;
+                    Ok(converted)
+                });
+
+            match result {
+                Ok(converted) => {
+                    if verbose {
+                        println_green!("{converted}");
+                    }
+                    files[index] = converted;
+                }
+                Err(e) => {
+                    match verbose {
+                        true => println_red!("{e}"),
+                        false => {
+                            progress_bar.fail();
+                            println!("{file}: {}", format_red!("{e}"));
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        if !verbose {
+            progress_bar.pass();
+        }
+
         Ok(())
     }
 
@@ -221,9 +321,7 @@ impl FileAction {
         args: &Args,
         reader: std::io::Cursor<Vec<u8>>,
         extract_to: &str,
-        include: &[String],
-        exclude: &[String],
-        keep_folders: bool,
+        rules: &ExtractRules,
     ) -> Result<Vec<String>, String> {
         let verbose = args.options.verbose || args.config.verbose_files;
         match verbose {
@@ -241,29 +339,18 @@ impl FileAction {
             e.to_string()
         })?;
 
-        let mut file_count = 0.0;
-        let mut files: Vec<String> = zip_archive
+        let paths: Vec<String> = zip_archive
             .file_names()
+            .filter(|file| !file.ends_with('/') && rules.includes(file))
             .map(ToString::to_string)
-            .filter(|file| {
-                if file.ends_with('/') {
-                    if keep_folders {
-                        // NOTE: This creates all paths, regardless if they're included or not
-                        let _ = fs::create_dir_all([extract_to, file].concat()).inspect_err(|e| {
-                            println!("   Directory creation error: {}", format_red!("{e}"));
-                        });
-                    }
-                    return false;
-                }
-                if match_any_wildcard(file, include) && !match_any_wildcard(file, exclude) {
-                    file_count += 1.0;
-                    true
-                } else {
-                    false
-                }
-            })
             .collect();
 
+        let destinations = rules.resolve(&paths).inspect_err(|_| {
+            if !verbose {
+                progress_bar.fail();
+            }
+        })?;
+
         fs::create_dir_all(extract_to).map_err(|e| {
             if !verbose {
                 progress_bar.fail();
@@ -272,9 +359,14 @@ impl FileAction {
             e.to_string()
         })?;
 
+        let file_count = destinations.iter().flatten().count() as f64;
         let mut files_processed = 0.0;
+        let mut files = Vec::new();
 
-        for file in &mut files {
+        for (path, file) in paths.iter().zip(destinations) {
+            let Some(file) = file else {
+                continue;
+            };
             files_processed += 1.0;
 
             match verbose {
@@ -290,7 +382,7 @@ impl FileAction {
 
             let mut file_contents = Vec::new();
             zip_archive
-                .by_name(file)
+                .by_name(path)
                 .map_err(|e| {
                     match verbose {
                         true => println_red!("{e}"),
@@ -307,17 +399,14 @@ impl FileAction {
                     e.to_string()
                 })?;
 
-            if !keep_folders {
-                *file = file.split('/').next_back().unwrap().to_owned();
-            }
-
-            fs::write([extract_to, file].concat(), file_contents).map_err(|e| {
+            Self::write_staged(extract_to, &file, file_contents).map_err(|e| {
                 match verbose {
                     true => println_red!("{e}"),
                     false => progress_bar.fail(),
                 }
-                e.to_string()
+                e
             })?;
+            files.push(file);
 
             if verbose {
                 println_green!("Done");
@@ -335,9 +424,7 @@ impl FileAction {
         args: &Args,
         mut archive: Archive<R>,
         extract_to: &str,
-        include: &[String],
-        exclude: &[String],
-        keep_folders: bool,
+        rules: &ExtractRules,
     ) -> Result<Vec<String>, String> {
         let verbose = args.options.verbose || args.config.verbose_files;
         match verbose {
@@ -358,73 +445,72 @@ impl FileAction {
             e.to_string()
         })?;
 
-        let mut files_processed = 0.0;
-        let mut fonts = Vec::new();
+        // Entries are read into memory first, so that conflicting
+        // destinations can be detected before anything is written
+        let mut paths = Vec::new();
+        let mut contents = Vec::new();
         let entries = archive.entries().map_err(|e| e.to_string())?;
         for mut entry in entries {
-            let entry = entry.as_mut().unwrap();
-            let file = match keep_folders {
-                true => entry.path().unwrap().to_str().unwrap().to_owned(),
-                false => entry
-                    .path()
-                    .unwrap()
-                    .to_str()
-                    .unwrap()
-                    .split('/')
-                    .next_back()
-                    .unwrap()
-                    .to_string(),
-            };
+            let entry = entry.as_mut().map_err(|e| e.to_string())?;
+            if entry.header().entry_type().is_dir() {
+                continue;
+            }
 
-            if !match_any_wildcard(&file, include) || match_any_wildcard(&file, exclude) {
+            let path = entry.path().unwrap().to_str().unwrap().to_owned();
+            let matched_path = match rules.keep_folders {
+                true => path.as_str(),
+                false => path.split('/').next_back().unwrap(),
+            };
+            if matched_path.is_empty() || !rules.includes(matched_path) {
                 continue;
             }
 
+            let mut file_contents = Vec::new();
+            entry.read_to_end(&mut file_contents).map_err(|e| {
+                if !verbose {
+                    progress_bar.fail();
+                }
+                println!("{path}: {}", format_red!("{e}"));
+                e.to_string()
+            })?;
+            paths.push(path);
+            contents.push(file_contents);
+        }
+
+        let destinations = rules.resolve(&paths).inspect_err(|_| {
+            if !verbose {
+                progress_bar.fail();
+            }
+        })?;
+
+        let file_count = destinations.iter().flatten().count() as f64;
+        let mut files_processed = 0.0;
+        let mut fonts = Vec::new();
+        for (file, file_contents) in destinations.into_iter().zip(contents) {
+            let Some(file) = file else {
+                continue;
+            };
+            files_processed += 1.0;
+
             match verbose {
                 true => {
                     print!("   {file} ... ");
                     let _ = stdout().flush();
                 }
-                false => {
-                    files_processed += 1.0;
-                    progress_bar
-                        .update_progress(1.0, &format!(" {files_processed} / {files_processed}"));
-                }
-            }
-
-            if file.is_empty() || file.ends_with('/') {
-                if keep_folders {
-                    // NOTE: This creates all paths, regardless if they're included or not
-                    fs::create_dir_all([extract_to, &file].concat()).map_err(|e| {
-                        if verbose {
-                            println_red!("{e}");
-                        } else {
-                            progress_bar.fail();
-                            println!("{file}: {}", format_red!("{e}"));
-                        }
-                        e.to_string()
-                    })?;
-                }
-                continue;
+                false => progress_bar.update_progress(
+                    files_processed / file_count,
+                    &format!(" {files_processed} / {file_count}"),
+                ),
             }
 
-            let mut file_contents = Vec::new();
-            entry.read_to_end(&mut file_contents).map_err(|e| {
+            Self::write_staged(extract_to, &file, file_contents).map_err(|e| {
                 if verbose {
                     println_red!("{e}");
                 } else {
                     progress_bar.fail();
                     println!("{file}: {}", format_red!("{e}"));
                 }
-                e.to_string()
-            })?;
-
-            fs::write([extract_to, &file].concat(), file_contents).map_err(|e| {
-                if !verbose {
-                    progress_bar.fail();
-                    println!("{file}: {}", format_red!("{e}"));
-                }
-                e.to_string()
+                e
             })?;
             fonts.push(file);
 
@@ -444,31 +530,20 @@ impl FileAction {
         args: &Args,
         reader: std::io::Cursor<Vec<u8>>,
         extract_to: &str,
-        include: &[String],
-        exclude: &[String],
-        keep_folders: bool,
+        rules: &ExtractRules,
     ) -> Result<Vec<String>, String> {
         let _ = stdout().flush();
 
         let mut tar_gz_archive = GzDecoder::new(reader);
 
-        Self::extract_tar(
-            args,
-            Archive::new(&mut tar_gz_archive),
-            extract_to,
-            include,
-            exclude,
-            keep_folders,
-        )
+        Self::extract_tar(args, Archive::new(&mut tar_gz_archive), extract_to, rules)
     }
 
     fn extract_tar_xz(
         _args: &Args,
         _reader: std::io::Cursor<Vec<u8>>,
         _extract_to: &str,
-        _include: &[String],
-        _exclude: &[String],
-        _keep_folders: bool,
+        _rules: &ExtractRules,
     ) -> Result<Vec<String>, String> {
         todo!("XZ format is currently unsupported");
 
@@ -483,15 +558,22 @@ impl FileAction {
         //     args,
         //     Archive::new(&mut tar_gz_archive),
         //     extract_to,
-        //     include,
-        //     exclude,
-        //     keep_folders,
+        //     rules,
         // )
     }
 
+    /// Writes the file into the staging directory, creating its parent folders
+    fn write_staged(extract_to: &str, file: &str, contents: Vec<u8>) -> Result<(), String> {
+        let target = [extract_to, file].concat();
+        if let Some(parent) = Path::new(&target).parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(target, contents).map_err(|e| e.to_string())
+    }
+
     pub fn ref_file(&self) -> Result<&str, String> {
         match self {
-            FileAction::Extract { file, .. } | FileAction::SingleFile { file } => Ok(file),
+            FileAction::Extract { file, .. } | FileAction::SingleFile { file, .. } => Ok(file),
             FileAction::None => Err(format!(
                 "Cannot obtain field `file` from `{:?}`",
                 FileAction::None
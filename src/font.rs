@@ -1,12 +1,14 @@
 use crate::action::Action;
+use crate::dependencies::{base_name, dependency_names, expand_dependencies};
 use crate::font_page::FontPage;
-use crate::paths::{installers_dir, page_cache_dir};
+use crate::paths::page_cache_dir;
+use crate::repos;
 use crate::Args;
+use crate::installed::InstalledFont;
 use crate::InstalledFonts;
 use crate::Installer;
 
 use std::collections::{BTreeSet, HashMap};
-use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::{fmt, fs};
@@ -33,6 +35,7 @@ impl fmt::Display for FontParseError {
 pub struct Font {
     pub name: String,
     pub installer: Option<Installer>,
+    pub variant: Option<String>,
     pub override_version: Option<String>,
 }
 
@@ -40,8 +43,12 @@ impl fmt::Display for Font {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}{}",
+            "{}{}{}",
             self.name,
+            match &self.variant {
+                Some(variant) => format!("@{variant}"),
+                None => String::new(),
+            },
             match &self.override_version {
                 Some(ver) => format!(" {ver}"),
                 None => String::new(),
@@ -63,10 +70,18 @@ impl Font {
 
         let mut s = name.split(':');
         if let (Some(name), version, None) = (s.next(), s.next(), s.next()) {
+            let (name, variant) = match name.split_once('@') {
+                Some((name, variant)) if !variant.is_empty() => (name, Some(variant)),
+                Some(_) => {
+                    eprintln!("Invalid format: '{name}'");
+                    return Err(FontParseError::InvalidName);
+                }
+                None => (name, None),
+            };
             return Ok(Self {
                 name: name.to_string(),
                 installer: if needs_installer {
-                    match Installer::parse(&args, installers_dir(), name, version, cached_pages) {
+                    match Installer::load(&args, name, variant, version, cached_pages) {
                         Ok(installer) => Some(installer),
                         Err(e) => {
                             eprintln!("{e}");
@@ -76,6 +91,7 @@ impl Font {
                 } else {
                     None
                 },
+                variant: variant.map(ToString::to_string),
                 override_version: version.map(ToString::to_string),
             });
         }
@@ -89,6 +105,9 @@ impl Font {
         installed_fonts: &Arc<Mutex<InstalledFonts>>,
     ) -> Result<Vec<Font>, FontParseError> {
         let needs_installer;
+        let mut dependencies = BTreeSet::new();
+        // Fonts installed as dependencies which are now requested explicitly
+        let mut promoted = BTreeSet::new();
         let actionable_fonts: Vec<String> = match args.action {
             Action::Install => {
                 if filters.is_empty() {
@@ -98,13 +117,37 @@ impl Font {
 
                 let fonts =
                     Installer::filter_installers(filters).map_err(FontParseError::Generic)?;
+                let fonts = match expand_dependencies(&fonts, &Installer::read_dependencies) {
+                    Ok(fonts) => fonts,
+                    Err(e) => {
+                        println_red!("{e}");
+                        return Ok(vec![]);
+                    }
+                };
 
                 if fonts.is_empty() {
                     return Ok(vec![]);
                 }
 
+                // Fonts are only recorded as explicit once they are installed
+                // (see `Installer::finalize_install`), so declining leaves them as is
+                let installed_fonts = installed_fonts.lock().unwrap();
+                dependencies = dependency_names(&fonts);
+                promoted = fonts
+                    .iter()
+                    .map(|(font, _)| base_name(font))
+                    .filter(|name| !dependencies.contains(*name))
+                    .filter(|name| {
+                        installed_fonts
+                            .installed
+                            .get(*name)
+                            .is_some_and(|installed| installed.dependency)
+                    })
+                    .map(ToOwned::to_owned)
+                    .collect();
+
                 needs_installer = true;
-                fonts
+                fonts.into_iter().map(|(font, _)| font).collect()
             }
             Action::Reinstall => {
                 if filters.is_empty() {
@@ -138,6 +181,23 @@ impl Font {
                 needs_installer = true;
                 fonts
             }
+            Action::Verify => {
+                let match_all = &["*".to_string()];
+                let fonts = Installer::filter_installed(
+                    match filters.is_empty() {
+                        true => match_all,
+                        false => filters,
+                    },
+                    installed_fonts,
+                );
+
+                if fonts.is_empty() {
+                    return Ok(vec![]);
+                }
+
+                needs_installer = false;
+                fonts
+            }
             Action::Remove => {
                 if filters.is_empty() {
                     println!("No fonts were specified.");
@@ -153,6 +213,33 @@ impl Font {
                 needs_installer = false;
                 fonts
             }
+            Action::Adopt => {
+                if filters.is_empty() {
+                    println!("No fonts were specified.");
+                    return Ok(vec![]);
+                }
+
+                // Installed fonts are already tracked
+                let installed_fonts = installed_fonts.lock().unwrap();
+                let fonts = Installer::filter_installers(filters)
+                    .map_err(FontParseError::Generic)?
+                    .into_iter()
+                    .filter(|font| {
+                        let installed = installed_fonts.installed.contains_key(base_name(font));
+                        if installed {
+                            println!("Already installed: '{font}'");
+                        }
+                        !installed
+                    })
+                    .collect::<Vec<_>>();
+
+                if fonts.is_empty() {
+                    return Ok(vec![]);
+                }
+
+                needs_installer = true;
+                fonts
+            }
             Action::List => {
                 let usage = "\
 Usage:
@@ -199,11 +286,42 @@ Items:
                 needs_installer = false;
                 fonts
             }
-            Action::Clean | Action::Config | Action::Version | Action::Help => {
+            Action::Info
+            | Action::Search
+            | Action::Owns
+            | Action::Sync
+            | Action::Export
+            | Action::Lock
+            | Action::Clean
+            | Action::Doctor
+            | Action::Config
+            | Action::Repo
+            | Action::Installer
+            | Action::Completions
+            | Action::Version
+            | Action::Help => {
                 return Ok(vec![]);
             }
         };
 
+        // Installed fonts keep the variant they were installed with
+        let actionable_fonts = match args.action {
+            Action::Reinstall | Action::Update => {
+                let installed_fonts = installed_fonts.lock().unwrap();
+                actionable_fonts
+                    .into_iter()
+                    .map(|font| match installed_fonts.installed.get(&font) {
+                        Some(InstalledFont {
+                            variant: Some(variant),
+                            ..
+                        }) => format!("{font}@{variant}"),
+                        _ => font,
+                    })
+                    .collect()
+            }
+            _ => actionable_fonts,
+        };
+
         let cached_pages = Arc::new(Mutex::new(HashMap::<String, FontPage>::new()));
         fs::create_dir_all(page_cache_dir()).map_err(|e| {
             eprintln!("Failed to create directory: {}", page_cache_dir());
@@ -221,14 +339,22 @@ Items:
 
         let mut actionable_fonts = Vec::new();
         for handle in handles {
-            let font = handle.join().unwrap();
+            let mut font = handle.join().unwrap();
+            if let Ok(font) = &mut font {
+                let is_dependency = dependencies.contains(&font.name);
+                if let Some(installer) = &mut font.installer {
+                    installer.dependency = is_dependency;
+                }
+            }
             if font.is_err() {
                 continue;
             }
             let installer = font.as_ref().unwrap().installer.as_ref();
             if match args.action {
                 Action::Update | Action::Install if !args.options.reinstall => {
-                    installer.unwrap().has_updates(installed_fonts)
+                    let installer = installer.unwrap();
+                    installer.has_updates(installed_fonts)
+                        || promoted.contains(&installer.installer_name)
                 }
                 _ => !needs_installer || font.as_ref().unwrap().installer.is_some(),
             } {
@@ -244,6 +370,6 @@ Items:
 
     #[must_use]
     pub fn has_installer(name: &str) -> bool {
-        Path::new(&[installers_dir(), name].concat()).exists()
+        repos::find_installer(name).is_some()
     }
 }
@@ -122,6 +122,26 @@ impl FontPage {
     }
 }
 
+/// Returns the contents of every cached page whose URL starts with `url_prefix`,
+/// regardless of whether it is outdated
+#[must_use]
+pub fn read_cached_pages(url_prefix: &str) -> Vec<String> {
+    let prefix = filename_friendly(url_prefix);
+    let Some(prefix) = prefix.strip_suffix(".toml") else {
+        return vec![];
+    };
+
+    let Ok(entries) = fs::read_dir(page_cache_dir()) else {
+        return vec![];
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .filter_map(|page| toml::from_str::<FontPage>(&page).ok()?.contents)
+        .collect()
+}
+
 fn filename_friendly(input: &str) -> String {
     const FILE_EXTENSION: &str = ".toml";
     const FILENAME_MAX_CHARS: usize = 80 - FILE_EXTENSION.len();
@@ -1,5 +1,8 @@
 use crate::args::Args;
 use crate::bar::ProgressBar;
+use crate::checksum::sha256_file;
+use crate::manifest;
+use crate::ownership::Ownership;
 use crate::paths::{expand_home, installed_file_path};
 
 use serde::{Deserialize, Serialize};
@@ -13,8 +16,46 @@ pub struct InstalledFont {
     // TODO: Allow locking fonts to a particular tag
     // pub lock: Option<String>,
     pub url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_urls: Vec<String>,
+    /// Tag which the font was installed from (such as `latest`), if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Tag which was asked for using `font:tag`, rather than by the installer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_tag: Option<String>,
     pub dir: String,
     pub files: Vec<String>,
+    /// SHA-256 sums of the installed files
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sums: BTreeMap<String, String>,
+    /// Repository the installer was read from, or `None` for local installers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// Variant of the font which was installed, kept when updating it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// `true` if the font was only installed as a dependency of another font
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dependency: bool,
+}
+
+/// Result of comparing an installed font against the files on disk
+#[derive(Default)]
+pub struct Integrity {
+    pub dir_missing: bool,
+    pub missing: Vec<String>,
+    pub modified: Vec<String>,
+    pub unverified: Vec<String>,
+    pub extra: Vec<String>,
+}
+
+impl Integrity {
+    /// Returns `true` if any of the installed files are missing or modified
+    #[must_use]
+    pub const fn failed(&self) -> bool {
+        self.dir_missing || !self.missing.is_empty() || !self.modified.is_empty()
+    }
 }
 
 impl InstalledFont {
@@ -23,8 +64,104 @@ impl InstalledFont {
         expand_home(&mut path);
         path
     }
+
+    /// Compares the installed files against their recorded sums, and finds
+    /// files within the font directory which no installed font owns
+    #[must_use]
+    pub fn verify(&self, ownership: &Ownership) -> Integrity {
+        let dir = self.get_dir();
+        if !Path::new(&dir).is_dir() {
+            return Integrity {
+                dir_missing: true,
+                ..Integrity::default()
+            };
+        }
+
+        let mut integrity = Integrity::default();
+        for file in &self.files {
+            let path = format!("{dir}/{file}");
+            if !Path::new(&path).is_file() {
+                integrity.missing.push(file.to_owned());
+                continue;
+            }
+            match self.sums.get(file) {
+                Some(sum) if sha256_file(&path).is_ok_and(|s| &s == sum) => (),
+                Some(_) => integrity.modified.push(file.to_owned()),
+                None => integrity.unverified.push(file.to_owned()),
+            }
+        }
+
+        integrity.extra = self.untracked_files(ownership);
+        integrity
+    }
+
+    /// Removes the installed files from the font directory (and the directory
+    /// once it is empty), leaving the entry alone. Used for the directory a
+    /// font was moved out of, once it was installed into the new one.
+    pub fn remove_files(&self, args: &Args) -> Result<(), String> {
+        let verbose = args.options.verbose || args.config.verbose_files;
+        let dir = self.get_dir();
+        let dir_name = dir
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or("(unknown)");
+
+        print!("\nRemoving {dir_name} from {}: ", manifest::parent_dir(&self.dir));
+        match verbose {
+            true => println!(),
+            false => print!("\n… Removing…    "),
+        }
+        let _ = stdout().flush();
+
+        InstalledFonts::remove_files(&self.files, &dir, dir_name, "Removing:", false, verbose)
+            .map_err(|()| format!("Errors were encountered while removing: {dir_name}"))
+    }
+
+    /// Returns the files within the font directory which no installed font
+    /// owns, as fonts may share a directory with other fonts
+    #[must_use]
+    pub fn untracked_files(&self, ownership: &Ownership) -> Vec<String> {
+        let dir = self.get_dir();
+        list_files(&dir)
+            .into_iter()
+            .filter(|file| !self.files.contains(file) && !is_generated_file(file))
+            .filter(|file| ownership.owners(&format!("{dir}/{file}")).is_empty())
+            .collect()
+    }
+}
+
+/// Returns `true` for the files which fontconfig generates within font
+/// directories (such as the `.uuid` written by `fc-cache`)
+#[must_use]
+pub fn is_generated_file(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    matches!(name, ".uuid" | "fonts.dir" | "fonts.scale") || name.starts_with("fonts.cache-")
+}
+
+/// Returns the paths of all files within `dir` (recursively), relative to `dir`
+#[must_use]
+pub fn list_files(dir: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut dirs = vec![String::new()];
+    while let Some(subdir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(format!("{dir}/{subdir}")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = [&subdir, entry.file_name().to_str().unwrap_or_default()].concat();
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => dirs.push(name + "/"),
+                Ok(_) => files.push(name),
+                Err(_) => (),
+            }
+        }
+    }
+    files.sort();
+    files
 }
 
+#[derive(Default)]
 pub struct InstalledFonts {
     pub installed: BTreeMap<String, InstalledFont>,
     changed: bool,
@@ -100,7 +237,27 @@ impl InstalledFonts {
         self.changed = true;
     }
 
-    pub fn cleanup(
+    /// Returns which font installed each file and directory
+    #[must_use]
+    pub fn ownership(&self) -> Ownership {
+        let mut ownership = Ownership::default();
+        for (name, installed) in &self.installed {
+            ownership.insert(name, &installed.get_dir(), &installed.files);
+        }
+        ownership
+    }
+
+    /// Stops tracking a file of the font, once another font has overwritten it,
+    /// so that removing the font leaves the file alone
+    pub fn disown(&mut self, name: &str, file: &str) {
+        if let Some(entry) = self.installed.get_mut(name) {
+            entry.files.retain(|owned| owned != file);
+            entry.sums.remove(file);
+            self.changed = true;
+        }
+    }
+
+    pub(crate) fn cleanup(
         &self,
         args: &Args,
         font: &str,
@@ -142,6 +299,20 @@ impl InstalledFonts {
         Ok(())
     }
 
+    /// Returns whether removing the font removes its whole directory, which
+    /// `--force` does unless other fonts were installed into it as well
+    #[must_use]
+    pub fn removes_dir(&self, args: &Args, font: &str) -> bool {
+        let Some(installed) = self.installed.get(font) else {
+            return false;
+        };
+        let sharing = self.ownership().sharing_dir(font, &installed.get_dir()).join(", ");
+        if args.options.force && !sharing.is_empty() {
+            println_orange!("Only removing the files of {font}, which shares its directory with: {sharing}");
+        }
+        args.options.force && sharing.is_empty()
+    }
+
     /// Removes the font from disk if it exists
     /// and returns the removed directory
     ///
@@ -158,6 +329,7 @@ impl InstalledFonts {
             dir_iter.next_back();
             let dir_name = dir_iter.next_back().unwrap_or("(unknown)");
 
+            let removes_dir = self.removes_dir(args, font);
             print!("\nRemoving {dir_name}: ");
             if !verbose {
                 print!("\n… Removing…    ");
@@ -181,7 +353,7 @@ impl InstalledFonts {
                 println!();
             }
 
-            let result = match args.options.force {
+            let result = match removes_dir {
                 false => Self::remove_files(
                     &installed_font.files,
                     &dir,
@@ -1,81 +1,215 @@
+use crate::asset::{Asset, check_duplicate_files};
 use crate::bar::ProgressBar;
-use crate::checksum::Checksum;
-use crate::file_action::FileAction;
+use crate::checksum::{Checksum, sha256_file};
 use crate::font_page::FontPage;
-use crate::installed::{InstalledFont, InstalledFonts};
+use crate::installed::{InstalledFont, InstalledFonts, list_files};
+use crate::lockfile::LockedFont;
+use crate::manifest;
+use crate::ownership::Conflict;
 use crate::paths::{collapse_home, installers_dir, staging_dir};
+use crate::plan::FilePlan;
+use crate::repos;
 use crate::source::Source;
-use crate::Args;
-use crate::{format_size, wildcards::*};
+use crate::template;
+use crate::{Args, user_prompt};
+use crate::wildcards::*;
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io::{self, stdout, Write};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use futures::stream::StreamExt;
-use reqwest::header::USER_AGENT;
-
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use toml::{Table, Value};
 
 #[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Installer {
     pub name: String,
+    /// Short description of the font, shown by `fin search`
+    pub description: Option<String>,
+    /// Keywords which describe the font, such as `monospace`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Fonts which are installed along with this one. Installers without
+    /// a source or actions are bundles, which only install their dependencies.
+    #[serde(default)]
+    pub depends: Vec<String>,
+    #[serde(default)]
     pub source: Source,
-    pub action: FileAction,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub action: Vec<Asset>,
     check: Option<Checksum>,
+    /// Named variants of the font (e.g. `[variants.nf]`), each of which
+    /// overrides fields of the installer's actions
+    #[serde(default)]
+    pub variants: BTreeMap<String, Table>,
 
-    #[serde(default, skip_serializing)]
+    #[serde(skip)]
     pub installer_name: String,
+    /// The variant selected using `font@variant`, if any
+    #[serde(skip)]
+    pub variant: Option<String>,
+    /// `true` if the font is only installed as a dependency of another font
+    #[serde(skip)]
+    pub dependency: bool,
+    /// Location the font is installed into, instead of `install_dir`
+    /// (used by `fin sync` for fonts which specify their own)
+    #[serde(skip)]
+    pub install_dir: Option<String>,
+    /// Repository the installer was read from, or `None` for local installers
+    #[serde(skip)]
+    pub origin: Option<String>,
+    /// Tag of the GitHub release the assets were found within, as the
+    /// installer may only ask for the `latest` release
+    #[serde(skip)]
+    pub release_tag: Option<String>,
+    /// The tag selected using `font:tag`, if any
+    #[serde(skip)]
+    pub requested_tag: Option<String>,
 
     // TODO: Re-think how the below fields are stored
-    #[serde(default, skip_serializing)]
-    pub data: Option<Vec<u8>>,
-    #[serde(default, skip_serializing)]
-    pub data_size: f64,
-    #[serde(default, skip_serializing)]
+    #[serde(skip)]
     pub files: Vec<String>,
-    #[serde(skip_serializing)]
+    #[serde(skip)]
     pub font_page: Option<String>,
 }
 
 impl Installer {
+    /// Finds the installer among the local and repository installers,
+    /// and parses it
+    pub fn load(
+        args: &Arc<Args>,
+        installer_name: &str,
+        variant: Option<&str>,
+        override_version: Option<&str>,
+        cached_pages: Arc<Mutex<HashMap<String, FontPage>>>,
+    ) -> Result<Self, String> {
+        let Some((installer_dir, origin)) = repos::find_installer(installer_name) else {
+            eprintln!("Error reading installer: {installer_name}");
+            return Err(format!("{installer_name}: Installer not found"));
+        };
+        let mut installer = Self::parse(
+            args,
+            &installer_dir,
+            installer_name,
+            variant,
+            override_version,
+            cached_pages,
+        )?;
+        installer.origin = origin;
+        Ok(installer)
+    }
+
     pub fn parse(
         args: &Arc<Args>,
         installer_dir: &str,
         installer_name: &str,
+        variant: Option<&str>,
         override_version: Option<&str>,
         cached_pages: Arc<Mutex<HashMap<String, FontPage>>>,
     ) -> Result<Self, String> {
-        let mut installer: Self = toml::from_str(
-            &fs::read_to_string([installer_dir, installer_name].concat()).map_err(|err| {
-                eprintln!("Error reading installer: {installer_name}");
-                err.to_string()
-            })?,
-        )
-        .map_err(|err| {
+        let contents = template::read(installer_dir, installer_name).inspect_err(|_| {
+            eprintln!("Error reading installer: {installer_name}");
+        })?;
+        let mut installer = Self::from_str(&contents, installer_name, variant, override_version)?;
+
+        let mut source = installer.source.take();
+        source.into_direct_url(&mut installer, args, cached_pages)?;
+        installer.source = source;
+
+        Ok(installer)
+    }
+
+    /// Parses and validates the installer without accessing the network,
+    /// leaving the download links of its assets unresolved
+    pub fn from_str(
+        contents: &str,
+        installer_name: &str,
+        variant: Option<&str>,
+        override_version: Option<&str>,
+    ) -> Result<Self, String> {
+        let parse_error = |err: toml::de::Error| {
             eprintln!("Error parsing installer: {installer_name}");
             err.to_string()
-        })?;
+        };
+        let mut installer: Self = match variant {
+            Some(variant) => {
+                let mut table = toml::from_str::<Table>(contents).map_err(parse_error)?;
+                apply_variant(&mut table, variant)
+                    .map_err(|e| format!("{installer_name}: {e}"))?;
+                table.try_into().map_err(parse_error)?
+            }
+            None => toml::from_str(contents).map_err(parse_error)?,
+        };
 
         installer.installer_name = installer_name.to_string();
+        installer.variant = variant.map(ToOwned::to_owned);
+        installer.requested_tag = override_version.map(ToOwned::to_owned);
         Self::validate_name(&installer.name, installer_name)?;
 
-        installer.source = {
-            let mut source = installer.source.take();
-            source.validate_tag(override_version);
+        if installer.is_bundle() {
+            return Ok(installer);
+        }
+
+        if installer.action.is_empty() {
+            return Err(format!(
+                "{installer_name}: At least one action must be provided"
+            ));
+        }
+
+        // The top-level checksum applies to every asset which doesn't specify its own
+        if let Some(check) = installer.check.take() {
             installer
                 .action
-                .validate(source.ref_tag()?, installer_name)?;
-            source.validate(installer.action.ref_file()?, installer_name)?;
-            source.into_direct_url(&mut installer, args, cached_pages)?;
-            source
-        };
+                .iter_mut()
+                .filter(|asset| asset.check.is_none())
+                .for_each(|asset| asset.check = Some(check.clone()));
+        }
+
+        // GitHub releases list the digest of each asset, which is used by default
+        if matches!(installer.source, Source::GitHub { .. }) {
+            installer
+                .action
+                .iter_mut()
+                .filter(|asset| asset.check.is_none())
+                .for_each(|asset| asset.check = Some(Checksum::github_digest()));
+        }
+
+        let source = &mut installer.source;
+        source.validate_tag(override_version);
+        let has_font_page = !matches!(source, Source::Direct { .. });
+        for asset in &mut installer.action {
+            asset.validate(source.ref_tag()?, has_font_page, installer_name)?;
+        }
+        source.validate(installer_name)?;
 
         Ok(installer)
     }
 
+    /// Returns `true` if the installer only installs its dependencies
+    #[must_use]
+    pub fn is_bundle(&self) -> bool {
+        matches!(self.source, Source::None) && self.action.is_empty() && !self.depends.is_empty()
+    }
+
+    /// Reads the dependencies of the installer without resolving its source,
+    /// and whether it is a bundle, or returns `None` if there is no such
+    /// installer. Installers which cannot be parsed have no dependencies,
+    /// leaving their errors to be reported when installing them.
+    #[must_use]
+    pub fn read_dependencies(installer_name: &str) -> Option<(Vec<String>, bool)> {
+        let (dir, _) = repos::find_installer(installer_name)?;
+        let installer = template::read(&dir, installer_name)
+            .ok()
+            .and_then(|contents| toml::from_str::<Self>(&contents).ok());
+        Some(installer.map_or_else(Default::default, |installer| {
+            let is_bundle = installer.is_bundle();
+            (installer.depends, is_bundle)
+        }))
+    }
+
     fn validate_name(name: &str, font_name: &str) -> Result<(), String> {
         if name.replace(['.', '/'], "").is_empty() || name.contains("..") {
             return Err(format!("{font_name}: Invalid name: \"{name}\""));
@@ -83,123 +217,78 @@ impl Installer {
         Ok(())
     }
 
-    /// Downloads the font and stores its contents in `self.data`
+    /// Downloads every asset of the font and stores their contents
     pub async fn download_font(&mut self) -> Result<&mut Self, String> {
-        let mut progress_bar = ProgressBar::new("Downloading:");
         let reqwest_client = reqwest::Client::new();
 
-        print!("… Downloading…",);
-        let _ = stdout().flush();
-
         let font_page = self.font_page.take();
-        if let Some(checksum) = &mut self.check {
-            checksum
-                .obtain(
-                    font_page,
-                    self.source.ref_tag()?,
+        for asset in &mut self.action {
+            asset
+                .download(
+                    font_page.clone(),
                     &reqwest_client,
                     &self.installer_name,
                 )
-                .await
-                .inspect_err(|_| progress_bar.fail())?;
+                .await?;
         }
 
-        let remote_data = reqwest_client
-            .get(self.source.ref_direct_url()?)
-            .header(USER_AGENT, "fin")
-            .send()
-            .await
-            .map_err(|e| {
-                progress_bar.fail();
-                e.to_string()
-            })?;
-
-        self.data_size = remote_data.content_length().unwrap_or_default() as f64;
-        let file_size = format_size(self.data_size);
-
-        let mut downloaded_bytes = 0;
-
-        let mut buffer = Vec::new();
-        let mut stream = remote_data.bytes_stream();
-
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| {
-                progress_bar.fail();
-                e.to_string()
-            })?;
-
-            io::copy(&mut chunk.as_ref(), &mut buffer).map_err(|e| e.to_string())?;
-
-            downloaded_bytes += chunk.len();
-
-            let downloaded_bytes = downloaded_bytes as f64;
-            if downloaded_bytes > self.data_size {
-                self.data_size = downloaded_bytes;
-            }
-
-            let progress_text = format_size(downloaded_bytes);
-            progress_bar.update_progress(
-                downloaded_bytes / self.data_size,
-                &format!(" {progress_text} / {file_size}"),
-            );
-        }
-
-        self.data = Some(buffer);
-
-        progress_bar.pass();
-
         Ok(self)
     }
 
-    /// Verifies dowbloaded data integrity using a checksum
+    /// Verifies downloaded data integrity using checksums
     pub fn verify_download(&mut self) -> Result<&mut Self, String> {
-        let data = self.data.as_ref().unwrap().as_slice();
-        match self.check.take() {
-            Some(mut checksum) => checksum
-                .check(data, self.data_size, &self.source)
-                .map(|()| self),
-            None => Ok(self),
+        for asset in &mut self.action {
+            asset.verify()?;
         }
+        Ok(self)
     }
 
-    /// Prepares the font for installation by writing its
-    /// files to a staging directory (`paths::staging_dir`)
+    /// Prepares the font for installation by writing the files
+    /// of all assets to a staging directory (`paths::staging_dir`)
     pub fn prepare_install(&mut self, args: &Args) -> Result<&Self, String> {
-        let Some(data) = self.data.take() else {
-            return Err(format!(
-                "{}: {}",
-                self.installer_name,
-                red!("No data downloaded"),
-            ));
-        };
-
         let extract_to = [staging_dir(), &self.name, "/"].concat();
         let _ = fs::remove_dir_all(&extract_to);
 
-        self.action
-            .take()
-            .stage_install(self, data, extract_to, args)?;
+        let mut staged = Vec::with_capacity(self.action.len());
+        for asset in &mut self.action {
+            let Some(data) = asset.data.take() else {
+                return Err(format!(
+                    "{}: {}",
+                    self.installer_name,
+                    red!("No data downloaded"),
+                ));
+            };
+
+            let files = asset
+                .action
+                .take()
+                .stage_install(&asset.url, data, &extract_to, args)?;
+            staged.push((asset.url.as_str(), files));
+        }
+        check_duplicate_files(&staged)?;
+
+        for file in staged.into_iter().flat_map(|(_, files)| files) {
+            if !self.files.contains(&file) {
+                self.files.push(file);
+            }
+        }
         Ok(self)
     }
 
-    /// Moves the files from `paths::staging_dir` into the installation directory
+    /// Moves the files from `paths::staging_dir` into the installation directory.
+    /// Once they are installed, the `claimed` files are no longer tracked by
+    /// the fonts which installed them before, so that removing those fonts
+    /// leaves them alone.
     pub fn finalize_install(
         &self,
         args: &Args,
         installed_fonts: &Arc<Mutex<InstalledFonts>>,
+        claimed: &[Conflict],
     ) -> Result<(), String> {
         let verbose = args.options.verbose | args.config.verbose_files;
 
         let staging_dir = format!("{}/{}/", staging_dir(), &self.name);
-        let (target_dir, old_files) = &installed_fonts
-            .lock()
-            .unwrap()
-            .installed
-            .get(&self.installer_name)
-            .map_or_else(
-                || (format!("{}/{}/", args.config.install_dir, &self.name), None),
-                |installed| (installed.get_dir(), Some(installed.files.clone())),
-            );
+        let (target_dir, old_files, was_dependency) = &self.target(args, installed_fonts);
 
         fs::create_dir_all(target_dir).map_err(|err| err.to_string())?;
 
@@ -213,6 +302,7 @@ impl Installer {
 
         let mut errors = false;
         let mut files_processed = 0.0;
+        let mut sums = BTreeMap::new();
         let mut progress_bar = ProgressBar::new("Installing:");
 
         // Move the files specified by the installer into the target directory
@@ -240,11 +330,12 @@ impl Installer {
                 ),
             }
 
-            match fs::rename(
-                format!("{staging_dir}/{file}"),
-                format!("{target_dir}/{file}"),
-            ) {
-                Ok(()) => {
+            let target = format!("{target_dir}/{file}");
+            match fs::rename(format!("{staging_dir}/{file}"), &target).and_then(|()| {
+                sha256_file(&target).map_err(io::Error::other)
+            }) {
+                Ok(sum) => {
+                    sums.insert(file.to_owned(), sum);
                     if verbose {
                         println_green!("Done");
                     }
@@ -264,15 +355,25 @@ impl Installer {
                     progress_bar.pass();
                 }
 
+                let mut installed_fonts = installed_fonts.lock().unwrap();
+                for conflict in claimed {
+                    installed_fonts.disown(&conflict.owner, &conflict.file);
+                }
                 installed_fonts
-                    .lock()
-                    .unwrap()
                     .update_entry(
                         &self.installer_name,
                         InstalledFont {
                             url: self.source.ref_direct_url()?.to_owned(),
+                            extra_urls: self.extra_urls(),
+                            tag: self.source.ref_tag()?.map(ToOwned::to_owned),
+                            requested_tag: self.requested_tag.clone(),
                             dir: collapse_home(target_dir),
                             files: self.files.clone(),
+                            sums,
+                            origin: self.origin.clone(),
+                            variant: self.variant.clone(),
+                            // Fonts which were installed explicitly remain so
+                            dependency: self.dependency && *was_dependency,
                         },
                     )
                     .cleanup(args, &self.installer_name, old_files.as_ref())
@@ -282,49 +383,267 @@ impl Installer {
                 if !verbose {
                     progress_bar.fail();
                 }
-                println!("\nErrors were encountered while installing {}", self.name)
+                return Err(format!(
+                    "Errors were encountered while installing {}",
+                    self.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether installing the font would overwrite files which other
+    /// fonts installed, and asks whether to take them over (unless `--force`
+    /// is passed). Returns the files to take over from the other fonts once
+    /// the font is installed (see `finalize_install`).
+    pub fn claim_files(
+        &self,
+        args: &Args,
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<Vec<Conflict>, String> {
+        let (target_dir, _, _) = self.target(args, installed_fonts);
+        let conflicts = self.conflicts(&target_dir, installed_fonts);
+        if conflicts.is_empty() {
+            return Ok(conflicts);
+        }
+
+        println!("Files installed by other fonts would be overwritten:");
+        print_conflicts(&conflicts);
+        if !args.options.force && !user_prompt("Overwrite them?", args) {
+            let _ = fs::remove_dir_all(format!("{}/{}/", staging_dir(), &self.name));
+            return Err(
+                "Refusing to overwrite files installed by other fonts (use `--force` to overwrite them)"
+                    .to_string(),
+            );
+        }
+
+        Ok(conflicts)
+    }
+
+    /// Returns the files which other fonts installed into `target_dir`,
+    /// and notes the fonts which share the directory without sharing files
+    fn conflicts(
+        &self,
+        target_dir: &str,
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Vec<Conflict> {
+        let ownership = installed_fonts.lock().unwrap().ownership();
+        let conflicts = ownership.conflicts(&self.installer_name, target_dir, &self.files);
+        for owner in ownership.sharing_dir(&self.installer_name, target_dir) {
+            if !conflicts.iter().any(|conflict| conflict.owner == owner) {
+                println_orange!("Note: {owner} is installed into the same directory");
             }
         }
+        conflicts
+    }
 
+    /// Shows the changes `finalize_install` would make, and removes the
+    /// files from `paths::staging_dir` instead of installing them
+    pub fn show_plan(
+        &self,
+        args: &Args,
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(), String> {
+        let (target_dir, old_files, _) = self.target(args, installed_fonts);
+        let conflicts = self.conflicts(&target_dir, installed_fonts);
+        let plan = FilePlan::new(
+            &self.files,
+            &old_files.unwrap_or_default(),
+            &list_files(&target_dir),
+        );
+        let _ = fs::remove_dir_all(format!("{}/{}/", staging_dir(), &self.name));
+
+        let target_dir = collapse_home(&target_dir);
+        match plan.is_empty() {
+            true => println!("No changes within {target_dir}"),
+            false => {
+                println!("Changes within {target_dir}:");
+                plan.print();
+            }
+        }
+        if !conflicts.is_empty() {
+            println!("Files installed by other fonts would be overwritten:");
+            print_conflicts(&conflicts);
+        }
+        Ok(())
+    }
+
+    /// Records the font as installed from the files already within its
+    /// directory, comparing them with the files in `paths::staging_dir`.
+    /// Asks before taking over files which other fonts track (unless `--force` is passed).
+    /// Returns the files which differ from the release, and those which are missing.
+    pub fn adopt(
+        &self,
+        args: &Args,
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> Result<(Vec<String>, Vec<String>), String> {
+        let staging_dir = format!("{}/{}/", staging_dir(), &self.name);
+        let (target_dir, _, _) = self.target(args, installed_fonts);
+
+        // Files which other fonts track would be removed along with either font
+        let claimed = self.conflicts(&target_dir, installed_fonts);
+        if !claimed.is_empty() {
+            println!("Files installed by other fonts would be taken over:");
+            print_conflicts(&claimed);
+            if !args.options.force && !user_prompt("Take them over?", args) {
+                let _ = fs::remove_dir_all(&staging_dir);
+                return Err(
+                    "Refusing to take over files installed by other fonts (use `--force` to take them over)"
+                        .to_string(),
+                );
+            }
+        }
+
+        let mut sums = BTreeMap::new();
+        let (mut differing, mut missing) = (Vec::new(), Vec::new());
+        for file in &self.files {
+            let expected = sha256_file(&format!("{staging_dir}/{file}"))?;
+            match sha256_file(&format!("{target_dir}/{file}")) {
+                Ok(sum) if sum == expected => (),
+                Ok(_) => differing.push(file.to_owned()),
+                Err(_) => missing.push(file.to_owned()),
+            }
+            sums.insert(file.to_owned(), expected);
+        }
+        let _ = fs::remove_dir_all(&staging_dir);
+
+        // Unrelated directories which happen to share the name are left alone
+        if missing.len() == self.files.len() {
+            return Err(format!(
+                "None of the files were found within {}",
+                collapse_home(&target_dir)
+            ));
+        }
+
+        // The sums of the release are recorded, so that `fin verify`
+        // keeps reporting the files which differ from it
+        let mut installed_fonts = installed_fonts.lock().unwrap();
+        for conflict in claimed {
+            installed_fonts.disown(&conflict.owner, &conflict.file);
+        }
+        installed_fonts.update_entry(
+            &self.installer_name,
+            InstalledFont {
+                url: self.source.ref_direct_url()?.to_owned(),
+                extra_urls: self.extra_urls(),
+                tag: self.source.ref_tag()?.map(ToOwned::to_owned),
+                requested_tag: self.requested_tag.clone(),
+                dir: collapse_home(&target_dir),
+                files: self.files.clone(),
+                sums,
+                origin: self.origin.clone(),
+                variant: self.variant.clone(),
+                dependency: false,
+            },
+        );
+        Ok((differing, missing))
+    }
+
+    /// Returns the directory the font is installed into, the files of its
+    /// previous installation, and whether it was only installed as a dependency
+    ///
+    /// Installed fonts are updated within their directory, unless `install_dir`
+    /// moves them into another one, which leaves the previous installation alone
+    fn target(
+        &self,
+        args: &Args,
+        installed_fonts: &Arc<Mutex<InstalledFonts>>,
+    ) -> (String, Option<Vec<String>>, bool) {
+        let install_dir = self.install_dir.as_ref().unwrap_or(&args.config.install_dir);
+        let target_dir = format!("{install_dir}/{}/", &self.name);
+        match installed_fonts.lock().unwrap().installed.get(&self.installer_name) {
+            Some(installed)
+                if self.install_dir.is_none()
+                    || manifest::same_dir(&installed.get_dir(), &target_dir) =>
+            {
+                (
+                    installed.get_dir(),
+                    Some(installed.files.clone()),
+                    installed.dependency,
+                )
+            }
+            Some(installed) => (target_dir, None, installed.dependency),
+            None => (target_dir, None, true),
+        }
+    }
+
+    /// Returns the tag of the release which is installed, if known
+    #[must_use]
+    pub fn version(&self) -> Option<String> {
+        self.release_tag
+            .clone()
+            .or_else(|| self.source.ref_tag().ok().flatten().map(ToOwned::to_owned))
+    }
+
+    /// Replaces the download links of the assets with those recorded by
+    /// the lockfile, whose SHA-256 sums the downloads must match
+    pub fn apply_lock(&mut self, locked: &LockedFont) -> Result<(), String> {
+        if locked.assets.len() != self.action.len() || locked.variant != self.variant {
+            return Err(format!(
+                "{}: The lockfile does not match the installer",
+                self.installer_name
+            ));
+        }
+        for (asset, locked) in self.action.iter_mut().zip(&locked.assets) {
+            // Checks which rely on the font page only apply to the resolved link
+            if asset.url != locked.url {
+                asset.check = None;
+            }
+            asset.url.clone_from(&locked.url);
+            asset.locked_sum = Some(locked.sha256.clone());
+        }
+        self.source = Source::Direct {
+            tag: locked.tag.clone(),
+            url: self.action[0].url.clone(),
+        };
         Ok(())
     }
 
     /// Returns the installer names of all available installers matched
     /// by any of the provided filter patterns
     pub fn filter_installers(filters: &[String]) -> Result<Vec<String>, String> {
+        let installer_dirs = repos::installer_dirs()?;
         let installers_dir = installers_dir();
-        if !Path::new(installers_dir).exists() {
+        if installer_dirs.len() == 1 && !Path::new(installers_dir).exists() {
             return Err(format!(
                 "Installers directory does not exist: {installers_dir}"
             ));
         }
 
-        let installers: Vec<String> = fs::read_dir(installers_dir)
-            .map_err(|e| e.to_string())?
-            .filter_map(|installer| {
-                installer.ok().and_then(|i| {
+        // Local installers override those of repositories with the same name
+        let mut installers = BTreeSet::new();
+        for (repo, dir) in &installer_dirs {
+            let Ok(entries) = fs::read_dir(dir) else {
+                if let Some(repo) = repo {
+                    println!("Repository has not been downloaded: {repo}");
+                    println!("Note: try running `fin repo update`");
+                }
+                continue;
+            };
+            // Directories such as `templates/` do not contain installers
+            installers.extend(entries.filter_map(|installer| {
+                installer.ok().filter(|i| i.path().is_file()).and_then(|i| {
                     i.path()
                         .file_name()
                         .and_then(|n| n.to_str().map(String::from))
                 })
-            })
-            .collect();
+            }));
+        }
 
         let mut matches = HashMap::<String, Vec<String>>::new();
         for filter in filters {
-            let (pattern, tag) = filter
-                .split_once(':')
-                .map_or_else(|| (filter.as_ref(), None), |s| (s.0, Some(s.1)));
+            // The variant and tag (`@variant:tag`) apply to each matched installer
+            let (pattern, suffix) = filter
+                .find(['@', ':'])
+                .map_or((filter.as_str(), ""), |i| filter.split_at(i));
 
             for input in &installers {
                 if !match_wildcard(input, pattern) {
                     continue;
                 }
 
-                let font = match tag {
-                    Some(tag) => input.to_string() + ":" + tag,
-                    None => input.to_string(),
-                };
+                let font = input.to_string() + suffix;
 
                 match matches.get_mut(filter) {
                     Some(entry) => entry.push(font),
@@ -373,7 +692,17 @@ impl Installer {
         installed.iter().map(ToString::to_string).collect()
     }
 
-    /// Returns `true` if the font's download URL changes,
+    /// Returns the download URLs of all assets except the first one
+    #[must_use]
+    pub fn extra_urls(&self) -> Vec<String> {
+        self.action
+            .iter()
+            .skip(1)
+            .map(|asset| asset.url.clone())
+            .collect()
+    }
+
+    /// Returns `true` if any of the font's download URLs change,
     /// or if the installation directory is missing.
     /// Otherwise returns `false`
     #[must_use]
@@ -385,7 +714,104 @@ impl Installer {
             .get(&self.installer_name)
             .is_none_or(|installed| {
                 self.source.ref_direct_url().unwrap() != installed.url
+                    || self.extra_urls() != installed.extra_urls
+                    || self.variant != installed.variant
                     || !fs::exists(installed.get_dir()).unwrap_or_default()
             })
     }
 }
+
+/// Overrides the fields of the action with those of the variant, whose
+/// `check` replaces the check of the action. Variants of installers with
+/// several actions override each of them within `[[variants.*.action]]`,
+/// in the same order.
+fn apply_variant(installer: &mut Table, variant: &str) -> Result<(), String> {
+    let Some(Value::Table(variants)) = installer.get("variants") else {
+        return Err(format!("Unknown variant: `{variant}` (the installer has no variants)"));
+    };
+    let Some(Value::Table(overrides)) = variants.get(variant) else {
+        let names = variants.keys().cloned().collect::<Vec<_>>();
+        return Err(format!(
+            "Unknown variant: `{variant}` (expected one of: {})",
+            names.join(", ")
+        ));
+    };
+    let mut overrides = overrides.clone();
+    let action_overrides = overrides.remove("action");
+
+    let entries = match installer.get_mut("action") {
+        Some(Value::Array(entries)) => entries.iter_mut().collect::<Vec<_>>(),
+        Some(entry) => vec![entry],
+        None => Vec::new(),
+    };
+    let overrides = match action_overrides {
+        Some(_) if !overrides.is_empty() => {
+            return Err(format!(
+                "Variant `{variant}` overrides each action, so its fields belong within `[[variants.{variant}.action]]`"
+            ));
+        }
+        Some(Value::Array(actions)) if actions.len() == entries.len() => actions
+            .into_iter()
+            .map(|action| match action {
+                Value::Table(action) => Ok(action),
+                _ => Err(format!("Variant `{variant}`: Each action must be a table")),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(Value::Array(actions)) => {
+            return Err(format!(
+                "Variant `{variant}` overrides {} action(s), but the installer has {}",
+                actions.len(),
+                entries.len()
+            ));
+        }
+        Some(_) => {
+            return Err(format!(
+                "Variant `{variant}`: `action` must be a list of tables (`[[variants.{variant}.action]]`)"
+            ));
+        }
+        None if entries.len() > 1 => {
+            return Err(format!(
+                "Variant `{variant}` must override each of the {} actions within `[[variants.{variant}.action]]`",
+                entries.len()
+            ));
+        }
+        None => vec![overrides; entries.len()],
+    };
+
+    for (entry, mut overrides) in entries.into_iter().zip(overrides) {
+        let Value::Table(entry) = entry else {
+            continue;
+        };
+        let check = overrides.remove("check");
+        for (key, fields) in entry.iter_mut() {
+            if let (false, Value::Table(fields)) = (key == "check", fields) {
+                template::merge(fields, overrides.clone());
+            }
+        }
+        if let Some(check) = check {
+            entry.insert("check".to_owned(), check);
+        }
+    }
+    Ok(())
+}
+
+/// Accepts either a single `[action.*]` table or an `[[action]]` array
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<Asset>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    match toml::Value::deserialize(deserializer)? {
+        value @ toml::Value::Array(_) => value.try_into(),
+        value => value.try_into().map(|asset| vec![asset]),
+    }
+    .map_err(D::Error::custom)
+}
+
+/// Lists each file which another font installed, along with that font
+fn print_conflicts(conflicts: &[Conflict]) {
+    for Conflict { file, owner } in conflicts {
+        println_orange!("   ~ {file} ({owner})");
+    }
+}
@@ -0,0 +1,86 @@
+use crate::font::Font;
+use crate::installer::Installer;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+
+/// Whether `--json` was specified, which replaces the human-readable output
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Whether a JSON document has already been printed
+static EMITTED: AtomicBool = AtomicBool::new(false);
+
+/// Suppresses the human-readable output in favor of JSON
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Prints `value` as a single line of JSON
+pub fn emit<T: Serialize + ?Sized>(value: &T) {
+    EMITTED.store(true, Ordering::Relaxed);
+    std::println!(
+        "{}",
+        serde_json::to_string(value).expect("Failed to serialize JSON output")
+    );
+}
+
+/// Prints the error, unless another document was already printed
+pub fn emit_error(error: &str) {
+    if !EMITTED.load(Ordering::Relaxed) {
+        emit(&ErrorRecord { error });
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorRecord<'a> {
+    error: &'a str,
+}
+
+/// A font listed by `fin list`
+#[derive(Serialize)]
+pub struct FontRecord {
+    pub name: String,
+    /// Installation directory, if installed
+    pub dir: Option<String>,
+    /// Installed tag, if known
+    pub version: Option<String>,
+    /// One of `installed`, `available`, `missing directory` or `missing installer`
+    pub status: &'static str,
+}
+
+/// Outcome of installing, updating, reinstalling or removing a font
+#[derive(Serialize)]
+pub struct OutcomeRecord {
+    pub name: String,
+    /// Tag of the font, if known
+    pub version: Option<String>,
+    /// One of `installed`, `updated`, `reinstalled`, `removed`, `outdated`,
+    /// `skipped`, `failed`, or `planned` (for `--dry-run`)
+    pub status: &'static str,
+    pub error: Option<String>,
+}
+
+impl OutcomeRecord {
+    /// Returns the records of fonts which were not acted upon, such as when
+    /// the prompt was declined or for a dry run, with the given `status`
+    pub(crate) fn with_status(fonts: &[Font], status: &'static str) -> Vec<Self> {
+        fonts
+            .iter()
+            .map(|font| Self {
+                name: font.name.clone(),
+                version: font
+                    .installer
+                    .as_ref()
+                    .and_then(Installer::version)
+                    .or_else(|| font.override_version.clone()),
+                status,
+                error: None,
+            })
+            .collect()
+    }
+}
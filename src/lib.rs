@@ -10,35 +10,72 @@ use std::io::{Write, stdin, stdout};
 use std::sync::{Arc, Mutex, mpsc};
 use std::thread;
 
+/// Prints human-readable output, unless `--json` was specified
+macro_rules! print {
+    ($($arg:tt)*) => {
+        if !$crate::json::is_enabled() {
+            std::print!($($arg)*)
+        }
+    };
+}
+/// Prints human-readable output, unless `--json` was specified
+macro_rules! println {
+    ($($arg:tt)*) => {
+        if !$crate::json::is_enabled() {
+            std::println!($($arg)*)
+        }
+    };
+}
+
 #[macro_use]
 pub mod colors;
 
 pub mod action;
 pub mod actions;
 pub mod args;
+pub mod asset;
 pub mod bar;
 pub mod checksum;
 pub mod config;
+pub mod dependencies;
+pub mod extract_rules;
 pub mod file_action;
 pub mod font_page;
+pub mod installed;
 pub mod installer;
+pub mod json;
+pub mod lint;
+pub mod lockfile;
+pub mod manifest;
+pub mod openpgp;
 pub mod options;
+pub mod ownership;
 pub mod paths;
+pub mod plan;
+pub mod repos;
+pub mod scaffold;
+pub mod signature;
 pub mod source;
+pub mod template;
 pub mod wildcards;
+pub mod woff;
 
 mod font;
-mod installed;
 
 pub fn run(lock_state: Option<String>) -> Result<(), String> {
     let (interrupt, result) = mpsc::channel::<Result<(), String>>();
     let installed_fonts = Arc::new(Mutex::new(InstalledFonts::read()?));
 
+    let dry_run;
     let handle = thread::Builder::new()
         .name("fin".to_string())
         .spawn({
             let interrupt = interrupt.clone();
             let (args, items) = Args::build()?;
+            if args.options.json {
+                json::enable();
+            }
+            dry_run = args.options.dry_run;
             let lock_state = lock_state.clone();
             let installed_fonts = Arc::clone(&installed_fonts);
             move || {
@@ -60,8 +97,16 @@ pub fn run(lock_state: Option<String>) -> Result<(), String> {
     if lock_state.is_none() {
         let _ = fs::remove_file(lock_file_path());
     }
-    installed_fonts.lock().unwrap().write()?;
+    // Dry runs never record changes (such as fonts becoming explicitly installed)
+    if !dry_run {
+        installed_fonts.lock().unwrap().write()?;
+    }
 
+    if let Err(e) = &result
+        && json::is_enabled()
+    {
+        json::emit_error(e);
+    }
     result
 }
 
@@ -106,3 +151,33 @@ pub fn format_size(mut num_bytes: f64) -> String {
     }
     format!("{num_bytes:.1} {}", UNITS[unit_index])
 }
+
+/// Returns the candidate closest to `input`, if any is close enough to
+/// likely be what the user meant
+#[must_use]
+pub fn suggest<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.chars().count() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Returns the Levenshtein distance between `a` and `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a != *b);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+
+    row[b.len()]
+}
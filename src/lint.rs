@@ -0,0 +1,453 @@
+use crate::dependencies;
+use crate::installer::Installer;
+use crate::repos;
+use crate::suggest;
+use crate::template;
+
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::Path;
+
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
+
+/// Fields accepted at the top level of an installer. These lists follow the
+/// fields which `Installer` accepts, so that typos can be located precisely.
+pub const INSTALLER_FIELDS: &[&str] = &[
+    "extends",
+    "variables",
+    "name",
+    "description",
+    "tags",
+    "source",
+    "action",
+    "check",
+    "variants",
+    "depends",
+];
+
+/// Sources, and the fields they accept
+pub const SOURCES: &[(&str, &[&str])] = &[
+    ("GitHub", &["tag", "author", "project"]),
+    ("Webpage", &["tag", "url"]),
+    ("Direct", &["tag", "url"]),
+];
+
+/// Actions, and the fields they accept
+pub const ACTIONS: &[(&str, &[&str])] = &[
+    (
+        "Extract",
+        &[
+            "file",
+            "include",
+            "exclude",
+            "keep_folders",
+            "strip_components",
+            "rename",
+            "on_conflict",
+            "convert_web_fonts",
+        ],
+    ),
+    ("SingleFile", &["file", "convert_web_fonts"]),
+];
+
+const SUM_FIELDS: &[&str] = &["file", "sum", "url"];
+const SIGNATURE_FIELDS: &[&str] = &["public_key", "key_file", "file", "url"];
+
+/// Checks, and the fields they accept
+pub const CHECKS: &[(&str, &[&str])] = &[
+    ("MD5", SUM_FIELDS),
+    ("SHA1", SUM_FIELDS),
+    ("SHA224", SUM_FIELDS),
+    ("SHA256", SUM_FIELDS),
+    ("SHA384", SUM_FIELDS),
+    ("SHA512", SUM_FIELDS),
+    ("BLAKE2b", SUM_FIELDS),
+    ("BLAKE2s", SUM_FIELDS),
+    ("BLAKE3", SUM_FIELDS),
+    ("Minisign", SIGNATURE_FIELDS),
+    ("PGP", SIGNATURE_FIELDS),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found within an installer, located by its line and column
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// Checks the installer without accessing the network, and returns the
+/// problems found within it. Templates it extends are searched for within
+/// `installer_dir` first (see `template::find`).
+#[must_use]
+pub fn lint(installer_dir: &str, installer_name: &str, contents: &str) -> Vec<Diagnostic> {
+    let mut linter = Linter {
+        contents,
+        diagnostics: Vec::new(),
+    };
+    if !linter.check_syntax() {
+        return linter.diagnostics;
+    }
+
+    // Errors within installers which extend templates cannot be located
+    // precisely, as they are reported for the resolved installer
+    let resolved = match template::resolve(contents, installer_dir, installer_name) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            linter.error_mention(installer_name, &e);
+            return linter.diagnostics;
+        }
+    };
+    let extends = resolved != contents;
+
+    // Unknown fields are reported above, which explains most of the errors
+    // reported when deserializing
+    if !linter.has_errors()
+        && let Err(e) = toml::from_str::<Installer>(&resolved)
+    {
+        match extends {
+            true => linter.error_mention(installer_name, e.message()),
+            false => linter.error(e.span().unwrap_or_default(), e.message()),
+        }
+    }
+    if !linter.has_errors()
+        && let Err(e) = Installer::from_str(&resolved, installer_name, None, None)
+    {
+        linter.error_mention(installer_name, &e);
+    }
+    if !linter.has_errors()
+        && let Ok(installer) = toml::from_str::<Installer>(&resolved)
+    {
+        for dependency in &installer.depends {
+            let name = dependencies::base_name(dependency);
+            if !Path::new(&[installer_dir, name].concat()).is_file()
+                && repos::find_installer(name).is_none()
+            {
+                let span = linter.find_mention(&format!("\"{dependency}\""));
+                linter.warning(span, &format!("Dependency not found: \"{dependency}\""));
+            }
+        }
+        // Each variant should result in a valid installer as well
+        for variant in installer.variants.keys() {
+            if let Err(e) = Installer::from_str(&resolved, installer_name, Some(variant), None) {
+                linter.error_mention(installer_name, &format!("Variant `{variant}`: {e}"));
+            }
+        }
+    }
+
+    linter.diagnostics
+}
+
+/// Checks the template for syntax errors and unknown fields. Templates may
+/// leave out any field, which is provided by the installers extending them.
+#[must_use]
+pub fn lint_template(contents: &str) -> Vec<Diagnostic> {
+    let mut linter = Linter {
+        contents,
+        diagnostics: Vec::new(),
+    };
+    linter.check_syntax();
+    linter.diagnostics
+}
+
+struct Linter<'a> {
+    contents: &'a str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Linter<'_> {
+    fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    fn error(&mut self, span: Range<usize>, message: &str) {
+        self.push(Severity::Error, span, message);
+    }
+
+    /// Reports an error without a span, located by the value it mentions
+    fn error_mention(&mut self, installer_name: &str, message: &str) {
+        let message = message
+            .strip_prefix(&format!("{installer_name}: "))
+            .unwrap_or(message);
+        self.error(self.find_mention(message), message);
+    }
+
+    /// Checks the syntax and the fields of the installer, and returns
+    /// `false` if it could not be parsed
+    fn check_syntax(&mut self) -> bool {
+        match DeTable::parse(self.contents) {
+            Ok(table) => {
+                self.check_fields(table.get_ref());
+                true
+            }
+            Err(e) => {
+                self.error(e.span().unwrap_or_default(), e.message());
+                false
+            }
+        }
+    }
+
+    fn warning(&mut self, span: Range<usize>, message: &str) {
+        self.push(Severity::Warning, span, message);
+    }
+
+    fn push(&mut self, severity: Severity, span: Range<usize>, message: &str) {
+        let before = &self.contents[..span.start.min(self.contents.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.diagnostics.push(Diagnostic {
+            severity,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.to_owned(),
+        });
+    }
+
+    /// Returns the span of the first value quoted by the message (such as
+    /// `Invalid URL: "…"`), or the start of the installer if there is none
+    fn find_mention(&self, message: &str) -> Range<usize> {
+        message
+            .split(['"', '`'])
+            .skip(1)
+            .step_by(2)
+            .filter(|mention| !mention.is_empty())
+            .find_map(|mention| self.contents.find(mention))
+            .map_or(0..0, |start| start..start)
+    }
+
+    fn check_fields(&mut self, installer: &DeTable) {
+        for (key, value) in installer {
+            match key.get_ref().as_ref() {
+                "source" => self.check_variant(value, "source", SOURCES),
+                "check" => self.check_variant(value, "check", CHECKS),
+                // Variables are named freely
+                "variables" => (),
+                "variants" => self.check_variants(value, installer.get("action")),
+                "action" => match value.get_ref() {
+                    DeValue::Array(actions) => {
+                        for action in actions.iter() {
+                            self.check_action(action);
+                        }
+                    }
+                    _ => self.check_action(value),
+                },
+                _ => self.check_known(key, INSTALLER_FIELDS),
+            }
+        }
+    }
+
+    /// Checks an entry of `action`, which may contain its own `check`
+    fn check_action(&mut self, action: &Spanned<DeValue>) {
+        let DeValue::Table(table) = action.get_ref() else {
+            return;
+        };
+        for (key, value) in table {
+            match key.get_ref().as_ref() {
+                "check" => self.check_variant(value, "check", CHECKS),
+                name => match ACTIONS.iter().find(|(action, _)| *action == name) {
+                    Some((_, fields)) => {
+                        self.check_table(value, fields);
+                        self.check_patterns(value);
+                    }
+                    None => {
+                        let names = ACTIONS
+                            .iter()
+                            .map(|(action, _)| *action)
+                            .collect::<Vec<_>>();
+                        self.unknown(key, "Unknown action", &names);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Checks the fields of each `[variants.*]` table against the action it
+    /// overrides, along with its `check`. Variants of installers with several
+    /// actions override each of them within `[[variants.*.action]]`.
+    fn check_variants(&mut self, variants: &Spanned<DeValue>, actions: Option<&Spanned<DeValue>>) {
+        let DeValue::Table(variants) = variants.get_ref() else {
+            return;
+        };
+        // Actions may also be provided by the template the installer extends,
+        // in which case the resolved installer is checked instead
+        let actions = match actions {
+            Some(actions) => match actions.get_ref() {
+                DeValue::Array(actions) => actions.iter().collect::<Vec<_>>(),
+                _ => vec![actions],
+            },
+            None => Vec::new(),
+        };
+
+        for (name, variant) in variants {
+            let DeValue::Table(table) = variant.get_ref() else {
+                continue;
+            };
+            let Some(overrides) = table.get("action") else {
+                match actions.len() {
+                    0 | 1 => self.check_override(variant, actions.first().copied()),
+                    count => self.error(
+                        name.span(),
+                        &format!(
+                            "Variant `{name}` must override each of the {count} actions within `[[variants.{name}.action]]`",
+                            name = name.get_ref()
+                        ),
+                    ),
+                }
+                continue;
+            };
+
+            for key in table.keys().filter(|key| key.get_ref() != "action") {
+                self.error(
+                    key.span(),
+                    &format!(
+                        "Variant `{name}` overrides each action, so `{key}` belongs within `[[variants.{name}.action]]`",
+                        name = name.get_ref(),
+                        key = key.get_ref()
+                    ),
+                );
+            }
+            let DeValue::Array(overrides) = overrides.get_ref() else {
+                self.error(
+                    overrides.span(),
+                    &format!(
+                        "`action` must be a list of tables (`[[variants.{}.action]]`)",
+                        name.get_ref()
+                    ),
+                );
+                continue;
+            };
+            if !actions.is_empty() && overrides.len() != actions.len() {
+                self.error(
+                    name.span(),
+                    &format!(
+                        "Variant `{}` overrides {} action(s), but the installer has {}",
+                        name.get_ref(),
+                        overrides.len(),
+                        actions.len()
+                    ),
+                );
+            }
+            for (i, fields) in overrides.iter().enumerate() {
+                self.check_override(fields, actions.get(i).copied());
+            }
+        }
+    }
+
+    /// Checks the fields which a variant overrides, which must be accepted
+    /// by the action (or by any action, if it is not known)
+    fn check_override(&mut self, fields: &Spanned<DeValue>, action: Option<&Spanned<DeValue>>) {
+        let DeValue::Table(table) = fields.get_ref() else {
+            return;
+        };
+        let kind = action.and_then(|action| match action.get_ref() {
+            DeValue::Table(action) => ACTIONS
+                .iter()
+                .find(|(name, _)| action.get(*name).is_some()),
+            _ => None,
+        });
+        let accepted = match kind {
+            Some((_, fields)) => fields.to_vec(),
+            None => {
+                let mut fields = ACTIONS
+                    .iter()
+                    .flat_map(|(_, fields)| fields.iter().copied())
+                    .collect::<Vec<_>>();
+                fields.sort_unstable();
+                fields.dedup();
+                fields
+            }
+        };
+
+        for (key, value) in table {
+            match key.get_ref().as_ref() {
+                "check" => self.check_variant(value, "check", CHECKS),
+                _ => self.check_known(key, &accepted),
+            }
+        }
+        self.check_patterns(fields);
+    }
+
+    /// Checks a table such as `[source.GitHub]`, which selects one of the
+    /// `variants` and its fields
+    fn check_variant(
+        &mut self,
+        value: &Spanned<DeValue>,
+        field: &str,
+        variants: &[(&str, &[&str])],
+    ) {
+        let DeValue::Table(table) = value.get_ref() else {
+            return;
+        };
+        for (key, value) in table {
+            match variants.iter().find(|(name, _)| *name == key.get_ref()) {
+                Some((_, fields)) => self.check_table(value, fields),
+                None => {
+                    let names = variants.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+                    self.unknown(key, &format!("Unknown {field}"), &names);
+                }
+            }
+        }
+    }
+
+    fn check_table(&mut self, value: &Spanned<DeValue>, fields: &[&str]) {
+        if let DeValue::Table(table) = value.get_ref() {
+            for key in table.keys() {
+                self.check_known(key, fields);
+            }
+        }
+    }
+
+    fn check_known(&mut self, key: &Spanned<Cow<str>>, fields: &[&str]) {
+        if !fields.contains(&key.get_ref().as_ref()) {
+            self.unknown(key, "Unknown field", fields);
+        }
+    }
+
+    fn unknown(&mut self, key: &Spanned<Cow<str>>, kind: &str, names: &[&str]) {
+        let name = key.get_ref();
+        let message = match suggest(name, names.iter().copied()) {
+            Some(suggestion) => format!("{kind}: `{name}` (did you mean `{suggestion}`?)"),
+            None => format!("{kind}: `{name}` (expected one of: {})", names.join(", ")),
+        };
+        self.error(key.span(), &message);
+    }
+
+    /// Checks the wildcard patterns of `include` and `exclude`
+    fn check_patterns(&mut self, action: &Spanned<DeValue>) {
+        let DeValue::Table(table) = action.get_ref() else {
+            return;
+        };
+        for field in ["include", "exclude"] {
+            let Some(DeValue::Array(patterns)) = table.get(field).map(Spanned::get_ref) else {
+                continue;
+            };
+            for pattern in patterns.iter() {
+                let DeValue::String(text) = pattern.get_ref() else {
+                    continue;
+                };
+                if text.is_empty() {
+                    self.error(pattern.span(), &format!("Empty pattern in `{field}`"));
+                } else if text.starts_with('/') {
+                    self.error(
+                        pattern.span(),
+                        &format!("Paths within archives never start with '/': \"{text}\""),
+                    );
+                } else if text.contains("**") {
+                    self.warning(
+                        pattern.span(),
+                        &format!("`*` already matches any characters, including '/': \"{text}\""),
+                    );
+                }
+            }
+        }
+    }
+}
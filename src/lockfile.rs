@@ -0,0 +1,104 @@
+use crate::dependencies::ReadDependencies;
+use crate::manifest::Manifest;
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+/// The exact downloads of the fonts listed by a manifest, written by
+/// `fin lock` next to the manifest (`fonts.toml` is locked by `fonts.lock`)
+#[derive(Default, Serialize, Deserialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    pub fonts: BTreeMap<String, LockedFont>,
+}
+
+#[derive(Default, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockedFont {
+    /// Tag the font resolved to, such as the tag of the latest release
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// `true` if the font is only locked as a dependency of another font
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dependency: bool,
+    /// Every asset of the installer's `action`, in the same order
+    pub assets: Vec<LockedAsset>,
+}
+
+#[derive(Default, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockedAsset {
+    pub url: String,
+    /// SHA-256 sum of the downloaded file (before extracting it)
+    pub sha256: String,
+}
+
+impl Lockfile {
+    /// Returns the path of the lockfile belonging to the manifest
+    #[must_use]
+    pub fn path(manifest_path: &str) -> String {
+        let stem = manifest_path.strip_suffix(".toml").unwrap_or(manifest_path);
+        format!("{stem}.lock")
+    }
+
+    pub fn read(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        Self::parse(&contents).map_err(|e| format!("{path}: {e}"))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.message().to_owned())
+    }
+
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let toml = toml::to_string(self).unwrap_or_default();
+        format!("# Written by `fin lock`, do not edit\n\n{toml}")
+    }
+
+    /// Checks that every font of the manifest is locked with the variant
+    /// and tag it lists, returning the first font which is not. Bundles are
+    /// not locked themselves, only their dependencies (see `expand_dependencies`).
+    pub fn check(
+        &self,
+        manifest: &Manifest,
+        read_dependencies: &ReadDependencies,
+    ) -> Result<(), String> {
+        for (name, font) in &manifest.fonts {
+            if read_dependencies(name).is_some_and(|(_, is_bundle)| is_bundle) {
+                continue;
+            }
+            let Some(locked) = self.fonts.get(name).filter(|locked| !locked.dependency) else {
+                return Err(format!("{name}: Not locked"));
+            };
+            if locked.variant != font.variant {
+                return Err(format!("{name}: Locked with another variant"));
+            }
+            if let Some(tag) = font.tag.as_ref().filter(|tag| *tag != "latest")
+                && locked.tag.as_ref() != Some(tag)
+            {
+                return Err(format!("{name}: Locked with another tag"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LockedFont {
+    /// Returns the font as accepted by `fin install` (`font@variant:tag`)
+    #[must_use]
+    pub fn to_font(&self, name: &str) -> String {
+        let mut font = name.to_owned();
+        if let Some(variant) = &self.variant {
+            font += &format!("@{variant}");
+        }
+        if let Some(tag) = &self.tag {
+            font += &format!(":{tag}");
+        }
+        font
+    }
+}
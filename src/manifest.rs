@@ -0,0 +1,119 @@
+use crate::installed::InstalledFonts;
+use crate::paths::{collapse_home, expand_home};
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+/// Fonts which should be installed, such as those listed by `fonts.toml`:
+///
+/// ```toml
+/// [fonts]
+/// inter = {}
+/// maple-mono = { variant = "nf", tag = "v7.0" }
+/// ```
+#[derive(Default, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub fonts: BTreeMap<String, ManifestFont>,
+}
+
+#[derive(Default, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestFont {
+    /// Tag to install, or the latest release when unspecified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// Location the font is installed into, instead of `install_dir`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_dir: Option<String>,
+}
+
+impl Manifest {
+    pub fn read(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        Self::parse(&contents).map_err(|e| format!("{path}: {e}"))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.message().to_owned())
+    }
+
+    /// Lists the installed fonts, leaving out those which were only installed
+    /// as dependencies. Install directories other than `install_dir` are kept.
+    #[must_use]
+    pub fn from_installed(installed_fonts: &InstalledFonts, install_dir: &str) -> Self {
+        let fonts = installed_fonts
+            .installed
+            .iter()
+            .filter(|(_, installed)| !installed.dependency)
+            .map(|(name, installed)| {
+                let parent = parent_dir(&installed.get_dir());
+                let font = ManifestFont {
+                    // Other fonts follow the tag of their installer
+                    tag: installed.requested_tag.clone(),
+                    variant: installed.variant.clone(),
+                    install_dir: (!same_dir(&parent, install_dir)).then(|| collapse_home(&parent)),
+                };
+                (name.to_owned(), font)
+            })
+            .collect();
+        Self { fonts }
+    }
+
+    /// Formats the manifest with one line per font
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let mut toml = "[fonts]\n".to_owned();
+        for (name, font) in &self.fonts {
+            let table = toml::Table::try_from(font).unwrap_or_default();
+            let key = match name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                true => name.to_owned(),
+                false => toml::Value::String(name.to_owned()).to_string(),
+            };
+            toml += &format!("{key} = {}\n", toml::Value::Table(table));
+        }
+        toml
+    }
+}
+
+impl ManifestFont {
+    /// Returns the font as accepted by `fin install` (`font@variant:tag`)
+    #[must_use]
+    pub fn to_font(&self, name: &str) -> String {
+        let mut font = name.to_owned();
+        if let Some(variant) = &self.variant {
+            font += &format!("@{variant}");
+        }
+        if let Some(tag) = self.tag.as_ref().filter(|tag| *tag != "latest") {
+            font += &format!(":{tag}");
+        }
+        font
+    }
+}
+
+/// Returns the directory containing the font directory `dir`
+#[must_use]
+pub fn parent_dir(dir: &str) -> String {
+    let dir = dir.trim_end_matches('/');
+    dir.rsplit_once('/')
+        .map_or_else(String::new, |(parent, _)| parent.to_owned())
+}
+
+/// Returns `true` if both paths refer to the same directory, ignoring `~/`
+/// and trailing slashes
+#[must_use]
+pub fn same_dir(a: &str, b: &str) -> bool {
+    let normalize = |path: &str| {
+        let mut path = path.to_owned();
+        expand_home(&mut path);
+        path.trim_end_matches('/').to_owned()
+    };
+    normalize(a) == normalize(b)
+}
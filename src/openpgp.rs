@@ -0,0 +1,370 @@
+use pgp::composed::{Deserializable, DetachedSignature, SignedPublicKey};
+use pgp::crypto::hash::HashAlgorithm;
+use pgp::crypto::public_key::PublicKeyAlgorithm;
+use pgp::packet::{self, Signature, SignatureType, SignatureVersion, SubpacketType};
+use pgp::types::{EddsaLegacyPublicParams, KeyDetails, KeyVersion, PublicParams, Tag};
+use rsa::traits::PublicKeyParts;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Subpackets which may be marked critical, as they are either understood
+/// or do not affect whether a signature is valid. Signatures containing any
+/// other critical subpacket are rejected.
+const KNOWN_SUBPACKETS: &[u8] = &[2, 3, 4, 9, 11, 16, 21, 22, 23, 25, 27, 28, 30, 32, 33, 34];
+
+/// Smaller RSA keys can be factored with enough resources
+const MIN_RSA_BITS: usize = 2048;
+
+/// A public key (or subkey) which may be used to verify signatures
+pub struct PublicKey {
+    pub key_id: [u8; 8],
+    pub fingerprint: [u8; 20],
+    key: Key,
+    created: u32,
+    /// Time the key expires at (in seconds since the epoch), if it does
+    expires: Option<u64>,
+    /// Reason the key may not be used to verify signatures, such as
+    /// a missing self-signature or a revocation
+    unusable: Option<&'static str>,
+}
+
+enum Key {
+    Primary(packet::PublicKey),
+    Subkey(packet::PublicSubkey),
+}
+
+/// Verifies the detached OpenPGP `signature` (armored or binary) of `data`,
+/// which must be made by one of the `keys`
+pub fn verify(keys: &[PublicKey], signature: &[u8], data: &[u8]) -> Result<(), String> {
+    let mut signatures = Vec::new();
+    for (block, armored) in blocks(signature) {
+        let parsed = match armored {
+            true => DetachedSignature::from_armor_many_buf(block).map(|(parsed, _)| parsed),
+            false => DetachedSignature::from_bytes_many(block),
+        };
+        for signature in parsed.map_err(|e| e.to_string())? {
+            signatures.push(signature.map_err(|e| e.to_string())?.signature);
+        }
+    }
+
+    if signatures.is_empty() {
+        return Err("No signature found".into());
+    }
+
+    let mut last_error = String::from("No matching public key found");
+    for signature in &signatures {
+        if signature.issuer_key_id().is_empty() && signature.issuer_fingerprint().is_empty() {
+            last_error = "Signature does not specify its issuer".into();
+            continue;
+        }
+        for key in keys.iter().filter(|key| key.is_issuer_of(signature)) {
+            match key.verify(signature, data) {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = e,
+            }
+        }
+    }
+    Err(last_error)
+}
+
+/// Reads every supported public key and subkey from the armored or binary `keyring`.
+/// Certificates which cannot be parsed, and keys using unsupported algorithms
+/// (e.g. encryption subkeys), are skipped. Keys which are not valid for signing
+/// (e.g. revoked keys) are kept, so that signatures made by them are reported as such.
+pub fn parse_keys(keyring: &[u8]) -> Result<Vec<PublicKey>, String> {
+    let mut keys = Vec::new();
+    for (block, armored) in blocks(keyring) {
+        let certificates = match armored {
+            true => {
+                SignedPublicKey::from_armor_many_buf(block).map(|(certificates, _)| certificates)
+            }
+            false => SignedPublicKey::from_bytes_many(block),
+        };
+        let Ok(certificates) = certificates else {
+            continue;
+        };
+        keys.extend(certificates.flatten().flat_map(certificate_keys));
+    }
+
+    if keys.is_empty() {
+        return Err("No supported public keys found".into());
+    }
+    Ok(keys)
+}
+
+/// Returns every ASCII armored block within `data` (as rPGP only reads the
+/// first one), or `data` itself if it is not armored
+fn blocks(data: &[u8]) -> Vec<(&[u8], bool)> {
+    const BEGIN: &[u8] = b"-----BEGIN PGP ";
+    let starts: Vec<usize> = (0..data.len())
+        .filter(|i| data[*i..].starts_with(BEGIN))
+        .collect();
+    if starts.is_empty() {
+        return vec![(data, false)];
+    }
+    let ends = starts.iter().skip(1).copied().chain([data.len()]);
+    starts
+        .iter()
+        .zip(ends)
+        .map(|(start, end)| (&data[*start..end], true))
+        .collect()
+}
+
+/// Returns the keys of the certificate, marking those which may not be
+/// used to verify signatures
+fn certificate_keys(certificate: SignedPublicKey) -> Vec<PublicKey> {
+    let Some(mut primary) = PublicKey::new(Key::Primary(certificate.primary_key.clone())) else {
+        return Vec::new();
+    };
+    let primary_key = &certificate.primary_key;
+    let details = &certificate.details;
+
+    let direct_signatures = details
+        .direct_signatures
+        .iter()
+        .filter(|signature| signature.typ() == Some(SignatureType::Key))
+        .filter(|signature| signature.verify_key(primary_key).is_ok());
+    let certifications = details.users.iter().flat_map(|user| {
+        user.signatures
+            .iter()
+            .filter(|signature| is_user_certification(signature))
+            .filter(|signature| {
+                signature
+                    .verify_certification(primary_key, Tag::UserId, &user.id)
+                    .is_ok()
+            })
+    });
+    let self_signature = latest(direct_signatures.chain(certifications));
+    let revoked = details.revocation_signatures.iter().any(|signature| {
+        signature.typ() == Some(SignatureType::KeyRevocation)
+            && check_signature(signature).is_ok()
+            && signature.verify_key(primary_key).is_ok()
+    });
+
+    match self_signature {
+        _ if revoked => primary.unusable = Some("has been revoked"),
+        None => primary.unusable = primary.unusable.or(Some("has no valid self-signature")),
+        Some(signature) => primary.expires = key_expiration(signature, primary.created),
+    }
+    // Primary keys which may only certify are still valid for their subkeys
+    let primary_valid = primary.unusable.is_none();
+
+    let mut keys = Vec::with_capacity(certificate.public_subkeys.len() + 1);
+    for signed in &certificate.public_subkeys {
+        let Some(mut subkey) = PublicKey::new(Key::Subkey(signed.key.clone())) else {
+            continue;
+        };
+        let bindings = signed.signatures.iter().filter(|signature| {
+            signature
+                .verify_subkey_binding(primary_key, &signed.key)
+                .is_ok()
+        });
+        let revoked = bindings
+            .clone()
+            .filter(|signature| signature.typ() == Some(SignatureType::SubkeyRevocation))
+            .any(|signature| check_signature(signature).is_ok());
+        let binding = latest(
+            bindings.filter(|signature| signature.typ() == Some(SignatureType::SubkeyBinding)),
+        );
+
+        let expires = binding.and_then(|binding| key_expiration(binding, subkey.created));
+        subkey.expires = expires.into_iter().chain(primary.expires).min();
+        subkey.unusable = match binding {
+            _ if !primary_valid && subkey.unusable.is_none() => {
+                Some("belongs to a primary key which is not valid")
+            }
+            _ if subkey.unusable.is_some() => subkey.unusable,
+            _ if revoked => Some("has been revoked"),
+            None => Some("is not bound to its primary key"),
+            Some(binding) if !allows_signing(binding) => Some("is not allowed to sign"),
+            // Signing subkeys must also sign the primary key, so that they
+            // cannot be bound to another primary key
+            Some(binding) if !is_cross_certified(binding, primary_key, &signed.key) => {
+                Some("is not cross-certified by its primary key")
+            }
+            Some(_) => None,
+        };
+        keys.push(subkey);
+    }
+    if primary_valid && !self_signature.is_some_and(allows_signing) {
+        primary.unusable = Some("is not allowed to sign");
+    }
+    keys.insert(0, primary);
+    keys
+}
+
+impl PublicKey {
+    /// Returns `None` for keys which cannot make signatures (e.g. encryption
+    /// subkeys) or use unsupported algorithms
+    fn new(key: Key) -> Option<Self> {
+        let details: &dyn KeyDetails = match &key {
+            Key::Primary(key) => key,
+            Key::Subkey(key) => key,
+        };
+        if details.version() != KeyVersion::V4 {
+            return None;
+        }
+        let unusable = match (details.algorithm(), details.public_params()) {
+            // Encrypt-only RSA keys (algorithm 2) must never verify signatures
+            (PublicKeyAlgorithm::RSA | PublicKeyAlgorithm::RSASign, PublicParams::RSA(params)) => {
+                (params.key.n().bits() < MIN_RSA_BITS)
+                    .then_some("is an RSA key of less than 2048 bits")
+            }
+            (_, PublicParams::EdDSALegacy(EddsaLegacyPublicParams::Ed25519 { .. }))
+            | (_, PublicParams::Ed25519(_)) => None,
+            _ => return None,
+        };
+        Some(Self {
+            key_id: details.legacy_key_id().as_ref().try_into().ok()?,
+            fingerprint: details.fingerprint().as_bytes().try_into().ok()?,
+            created: details.created_at().as_secs(),
+            expires: None,
+            unusable,
+            key,
+        })
+    }
+
+    fn is_issuer_of(&self, signature: &Signature) -> bool {
+        signature
+            .issuer_key_id()
+            .iter()
+            .any(|key_id| key_id.as_ref() == self.key_id)
+            || signature
+                .issuer_fingerprint()
+                .iter()
+                .any(|fingerprint| fingerprint.as_bytes() == self.fingerprint)
+    }
+
+    /// Verifies the signature of a document made by this key
+    fn verify(&self, signature: &Signature, data: &[u8]) -> Result<(), String> {
+        match signature.typ() {
+            Some(SignatureType::Binary | SignatureType::Text) => {}
+            signature_type => {
+                return Err(format!("Unsupported signature type: {signature_type:?}"));
+            }
+        }
+        let signed = check_signature(signature)?;
+        self.check_valid(signed)?;
+
+        let verified = match &self.key {
+            Key::Primary(key) => signature.verify(key, data),
+            Key::Subkey(key) => signature.verify(key, data),
+        };
+        verified.map_err(|_| "Signature does not match the data".to_string())
+    }
+
+    /// Checks that the key was valid for signing when the signature was made
+    fn check_valid(&self, signed: u32) -> Result<(), String> {
+        let key_id = self
+            .key_id
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect::<String>();
+        if let Some(reason) = self.unusable {
+            return Err(format!("Key {key_id} {reason}"));
+        }
+        if signed < self.created {
+            return Err(format!("Key {key_id} was created after the signature"));
+        }
+        if self
+            .expires
+            .is_some_and(|expires| u64::from(signed) >= expires)
+        {
+            return Err(format!(
+                "Key {key_id} had expired when the signature was made"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Checks the subpackets and hash algorithm of the signature, and that it
+/// has not expired, returning the time it was made at
+fn check_signature(signature: &Signature) -> Result<u32, String> {
+    let Some(config) = signature
+        .config()
+        .filter(|_| signature.version() == SignatureVersion::V4)
+    else {
+        return Err("Only version 4 signatures are supported".into());
+    };
+    if let Some(subpacket) = config.hashed_subpackets.iter().find(|subpacket| {
+        subpacket.is_critical && !KNOWN_SUBPACKETS.contains(&subpacket.typ().as_u8(false))
+    }) {
+        return Err(format!(
+            "Unsupported critical signature subpacket: {}",
+            subpacket.typ().as_u8(false)
+        ));
+    }
+    if !matches!(
+        config.hash_alg,
+        HashAlgorithm::Sha224
+            | HashAlgorithm::Sha256
+            | HashAlgorithm::Sha384
+            | HashAlgorithm::Sha512
+    ) {
+        return Err(format!("Unsupported hash algorithm: {}", config.hash_alg));
+    }
+    let Some(created) = signature.created().map(|created| created.as_secs()) else {
+        return Err("Signature does not specify its creation time".into());
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs());
+    let expires = signature
+        .signature_expiration_time()
+        .map_or(0, |expires| expires.as_secs());
+    if expires != 0 && now >= u64::from(created) + u64::from(expires) {
+        return Err("Signature has expired".into());
+    }
+    Ok(created)
+}
+
+/// Returns the latest of the (already verified) self-signatures, ignoring
+/// those which are not valid themselves
+fn latest<'a>(signatures: impl Iterator<Item = &'a Signature>) -> Option<&'a Signature> {
+    signatures
+        .filter_map(|signature| Some((check_signature(signature).ok()?, signature)))
+        .max_by_key(|(created, _)| *created)
+        .map(|(_, signature)| signature)
+}
+
+fn is_user_certification(signature: &Signature) -> bool {
+    matches!(
+        signature.typ(),
+        Some(
+            SignatureType::CertGeneric
+                | SignatureType::CertPersona
+                | SignatureType::CertCasual
+                | SignatureType::CertPositive
+        )
+    )
+}
+
+/// Returns the time the key expires at, according to its self-signature
+fn key_expiration(signature: &Signature, key_created: u32) -> Option<u64> {
+    let expires = signature.key_expiration_time()?.as_secs();
+    (expires != 0).then(|| u64::from(key_created) + u64::from(expires))
+}
+
+/// Keys without key flags may be used according to their algorithm
+fn allows_signing(signature: &Signature) -> bool {
+    let has_flags = signature.config().is_some_and(|config| {
+        config
+            .hashed_subpackets
+            .iter()
+            .any(|subpacket| subpacket.typ() == SubpacketType::KeyFlags)
+    });
+    !has_flags || signature.key_flags().sign()
+}
+
+/// Checks the primary key binding signature embedded within the subkey
+/// binding signature, which the subkey makes over the primary key
+fn is_cross_certified(
+    binding: &Signature,
+    primary: &packet::PublicKey,
+    subkey: &packet::PublicSubkey,
+) -> bool {
+    binding.embedded_signature().is_some_and(|embedded| {
+        check_signature(embedded).is_ok()
+            && embedded.verify_primary_key_binding(subkey, primary).is_ok()
+    })
+}
@@ -1,4 +1,4 @@
-use crate::{action::Action, actions::help::HelpAction, Config};
+use crate::{Config, action::Action};
 
 #[derive(Default, Clone)]
 pub struct Options {
@@ -7,9 +7,143 @@ pub struct Options {
     pub verbose: bool,
     pub answer: Option<bool>,
     pub force: bool,
+    pub repair: bool,
+    pub json: bool,
+    /// GitHub repository (`author/project`) of a new installer
+    pub github: Option<String>,
+    /// Remove installed fonts which are not listed by the manifest
+    pub prune: bool,
+    /// Install the exact downloads recorded by the lockfile
+    pub locked: bool,
+    /// Show the planned changes without making them
+    pub dry_run: bool,
+}
+
+/// A command-line argument accepted by `Options::build`
+pub struct Argument {
+    pub long: &'static str,
+    pub short: Option<char>,
+    /// Name of the value required by the argument (`--argument=value`)
+    pub value: Option<&'static str>,
+    /// Actions which accept the argument (all actions when empty)
+    pub actions: &'static [Action],
+    pub description: &'static str,
+}
+
+const fn argument(long: &'static str, short: Option<char>, description: &'static str) -> Argument {
+    Argument {
+        long,
+        short,
+        value: None,
+        actions: &[],
+        description,
+    }
+}
+
+impl Argument {
+    /// Requires a value, named `name` in the help messages
+    const fn with_value(mut self, name: &'static str) -> Self {
+        self.value = Some(name);
+        self
+    }
+
+    /// Restricts the argument to the specified actions
+    const fn only_for(mut self, actions: &'static [Action]) -> Self {
+        self.actions = actions;
+        self
+    }
+
+    /// Returns `true` if the argument may be used with `action`
+    #[must_use]
+    pub fn supports(&self, action: &Action) -> bool {
+        self.actions.is_empty() || self.actions.contains(action)
+    }
 }
 
 impl Options {
+    /// Every argument accepted by `build`
+    pub const ARGUMENTS: &[Argument] = &[
+        argument("--refresh", Some('r'), "Ignore cache and fetch new data"),
+        argument(
+            "--no-refresh",
+            Some('c'),
+            "Do not fetch new data if possible",
+        ),
+        argument("--cache-only", None, "Do not fetch new data if possible"),
+        argument(
+            "--reinstall",
+            Some('i'),
+            "Skip version checks and reinstall",
+        )
+        .only_for(&[Action::Install, Action::Update]),
+        argument("--verbose", Some('v'), "Show more detailed output"),
+        argument("--no-verbose", None, "Hide detailed output"),
+        argument("--verbose-files", None, "Show added and removed files"),
+        argument("--no-verbose-files", None, "Hide added and removed files"),
+        argument("--verbose-list", None, "Show installed paths when listing"),
+        argument(
+            "--no-verbose-list",
+            None,
+            "Hide installed paths when listing",
+        ),
+        argument("--verbose-urls", None, "Show URLs in the output"),
+        argument("--no-verbose-urls", None, "Hide URLs in the output"),
+        argument("--force", Some('F'), "Forcefully perform action (unsafe)"),
+        argument(
+            "--repair",
+            None,
+            "Reinstall fonts which failed verification",
+        )
+        .only_for(&[Action::Verify]),
+        argument("--json", None, "Print machine-readable output").only_for(&[
+            Action::Install,
+            Action::Reinstall,
+            Action::Update,
+            Action::Remove,
+            Action::List,
+        ]),
+        argument("--dry-run", None, "Show changes without making them").only_for(&[
+            Action::Install,
+            Action::Reinstall,
+            Action::Update,
+            Action::Remove,
+            Action::Clean,
+        ]),
+        argument("--yes", Some('y'), "Automatically accept prompts"),
+        argument("--no", Some('n'), "Automatically reject prompts"),
+        argument(
+            "--install-dir",
+            None,
+            "Location where new fonts will be installed",
+        )
+        .with_value("path"),
+        argument(
+            "--cache-timeout",
+            None,
+            "Minutes until cache is considered outdated",
+        )
+        .with_value("time"),
+        argument(
+            "--github",
+            None,
+            "GitHub repository of a new installer",
+        )
+        .with_value("author/project")
+        .only_for(&[Action::Installer]),
+        argument(
+            "--prune",
+            None,
+            "Remove fonts not listed by the manifest",
+        )
+        .only_for(&[Action::Sync]),
+        argument(
+            "--locked",
+            None,
+            "Install the exact downloads of the lockfile",
+        )
+        .only_for(&[Action::Sync]),
+    ];
+
     #[must_use]
     pub const fn help_options() -> &'static str {
         // Remember to update README.md
@@ -20,86 +154,83 @@ Arguments:
     --reinstall     -i    Skip version checks and reinstall
     --verbose       -v    Show more detailed output
     --force         -F    Forcefully perform action (unsafe)
+    --json                Print machine-readable output
+    --dry-run             Show changes without making them
     --yes           -y    Automatically accept prompts
     --no            -n    Automatically reject prompts
 "
     }
 
-    pub fn build(flags: &Vec<String>, config: &mut Config) -> Result<Self, String> {
+    /// Finds the argument by its long name (`--argument`)
+    #[must_use]
+    pub fn find_long(name: &str) -> Option<&'static Argument> {
+        Self::ARGUMENTS
+            .iter()
+            .find(|argument| argument.long == name)
+    }
+
+    /// Finds the argument by its short name (`-a`)
+    #[must_use]
+    pub fn find_short(name: char) -> Option<&'static Argument> {
+        Self::ARGUMENTS
+            .iter()
+            .find(|argument| argument.short == Some(name))
+    }
+
+    /// Applies the parsed arguments and their values (see `Args::parse`)
+    pub fn build(
+        flags: &[(&'static Argument, Option<String>)],
+        config: &mut Config,
+    ) -> Result<Self, String> {
         let mut options = Self {
             verbose: config.verbose_mode,
             ..Self::default()
         };
 
-        for flag in flags {
-            let opt_val = &mut flag.split('=');
-            let (opt, val) = (opt_val.next().unwrap(), opt_val.next());
-            match opt {
+        for (argument, value) in flags {
+            match (argument.long, value.as_deref()) {
                 // Arguments requiring a value (--argument=value)
-                "--install-dir" => config.install_dir = val.unwrap().to_string(),
-                "--cache-timeout" => {
-                    config.cache_timeout = val.unwrap().parse::<u64>().map_err(|e| e.to_string())?
+                ("--install-dir", Some(path)) => config.install_dir = path.to_string(),
+                ("--github", Some(repo)) => options.github = Some(repo.to_string()),
+                ("--cache-timeout", Some(time)) => {
+                    config.cache_timeout = time
+                        .parse::<u64>()
+                        .map_err(|e| format!("--cache-timeout: {e}: \"{time}\""))?;
                 }
 
-                opt if val.is_some() => return Err(format!("Unknown argument: {opt}=…")),
-
                 // Arguments not requiring a value (--argument)
-                "--reinstall" => options.reinstall = true,
-                "--refresh" => options.refresh = true,
-                "--no-refresh" | "--cache-only" => {
+                ("--reinstall", None) => options.reinstall = true,
+                ("--refresh", None) => options.refresh = true,
+                ("--no-refresh" | "--cache-only", None) => {
                     config.cache_timeout = u64::MAX;
                     options.refresh = false;
                 }
-                "--no-verbose" => {
+                ("--no-verbose", None) => {
                     config.verbose_urls = false;
                     config.verbose_list = false;
                     config.verbose_files = false;
                     options.verbose = false;
                 }
-                "--no-verbose-files" => config.verbose_files = false,
-                "--no-verbose-list" => config.verbose_list = false,
-                "--no-verbose-urls" => config.verbose_urls = false,
-                "--verbose-files" => config.verbose_files = true,
-                "--verbose-list" => config.verbose_list = true,
-                "--verbose-urls" => config.verbose_urls = true,
-                "--verbose" => options.verbose = true,
-                "--force" => options.force = true,
-                "--yes" => options.answer = Some(true),
-                "--no" => options.answer = Some(false),
+                ("--no-verbose-files", None) => config.verbose_files = false,
+                ("--no-verbose-list", None) => config.verbose_list = false,
+                ("--no-verbose-urls", None) => config.verbose_urls = false,
+                ("--verbose-files", None) => config.verbose_files = true,
+                ("--verbose-list", None) => config.verbose_list = true,
+                ("--verbose-urls", None) => config.verbose_urls = true,
+                ("--verbose", None) => options.verbose = true,
+                ("--force", None) => options.force = true,
+                ("--repair", None) => options.repair = true,
+                ("--prune", None) => options.prune = true,
+                ("--locked", None) => options.locked = true,
+                ("--dry-run", None) => options.dry_run = true,
+                ("--json", None) => options.json = true,
+                ("--yes", None) => options.answer = Some(true),
+                ("--no", None) => options.answer = Some(false),
 
-                // Short arguments and unknown argument errors
-                opt => {
-                    let mut opts = opt.chars();
-                    if opts.next().unwrap() != '-' {
-                        HelpAction::run(&Action::Help);
-                        println!();
-                        return Err(format!("Unknown argument: {opt}"));
-                    }
-                    for o in opts {
-                        match o {
-                            'i' => options.reinstall = true,
-                            'r' => options.refresh = true,
-                            'c' => {
-                                config.cache_timeout = u64::MAX;
-                                options.refresh = false;
-                            }
-                            'v' => options.verbose = true,
-                            'F' => options.force = true,
-                            'y' => options.answer = Some(true),
-                            'n' => options.answer = Some(false),
-                            '-' => {
-                                HelpAction::run(&Action::Help);
-                                println!();
-                                return Err(format!("Unknown argument: {opt}"));
-                            }
-                            o => {
-                                HelpAction::run(&Action::Help);
-                                println!();
-                                return Err(format!("Unknown argument: -{o}"));
-                            }
-                        }
-                    }
+                (long, Some(_)) if argument.value.is_none() => {
+                    return Err(format!("Argument does not take a value: {long}"));
                 }
+                (long, _) => return Err(format!("Invalid use of argument: {long}")),
             }
         }
 
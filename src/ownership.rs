@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use std::{fs, path};
+
+/// Which font installed each file (and directory), built from the installed
+/// fonts by `InstalledFonts::ownership`. Paths are absolute, with symlinks
+/// resolved (see `resolve`).
+#[derive(Default)]
+pub struct Ownership {
+    files: BTreeMap<String, String>,
+    dirs: BTreeMap<String, Vec<String>>,
+}
+
+/// A file which installing a font would overwrite, and the font which owns it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub file: String,
+    pub owner: String,
+}
+
+impl Ownership {
+    /// Records the font as the owner of its directory and files
+    pub fn insert(&mut self, font: &str, dir: &str, files: &[String]) {
+        let dir = normalize(dir);
+        for file in files {
+            self.files.insert(format!("{dir}/{file}"), font.to_owned());
+        }
+        self.dirs.entry(dir).or_default().push(font.to_owned());
+    }
+
+    /// Returns the fonts which installed the file, or whose directory it is
+    #[must_use]
+    pub fn owners(&self, path: &str) -> Vec<&str> {
+        let path = normalize(path);
+        match self.files.get(&path) {
+            Some(font) => vec![font.as_str()],
+            None => self
+                .dirs
+                .get(&path)
+                .map(|fonts| fonts.iter().map(String::as_str).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the files of `font` which other fonts already installed
+    /// into `dir`
+    #[must_use]
+    pub fn conflicts(&self, font: &str, dir: &str, files: &[String]) -> Vec<Conflict> {
+        let dir = normalize(dir);
+        files
+            .iter()
+            .filter_map(|file| {
+                let owner = self.files.get(&format!("{dir}/{file}"))?;
+                (owner != font).then(|| Conflict {
+                    file: file.to_owned(),
+                    owner: owner.to_owned(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the other fonts which were installed into `dir`
+    #[must_use]
+    pub fn sharing_dir(&self, font: &str, dir: &str) -> Vec<&str> {
+        self.dirs
+            .get(&normalize(dir))
+            .into_iter()
+            .flatten()
+            .filter(|owner| *owner != font)
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Returns the absolute path, resolving `..` and symlinks if the path exists
+#[must_use]
+pub fn resolve(path: &str) -> String {
+    fs::canonicalize(path)
+        .or_else(|_| path::absolute(path))
+        .map_or_else(|_| path.to_owned(), |path| path.to_string_lossy().into_owned())
+}
+
+fn normalize(path: &str) -> String {
+    resolve(path).trim_end_matches('/').replace("//", "/")
+}
@@ -44,6 +44,11 @@ pub fn installers_dir() -> &'static String {
     INSTALLERS_DIR.get_or_init(|| [config_dir(), "installers/"].concat())
 }
 
+pub fn keyring_dir() -> &'static String {
+    static KEYRING_DIR: OnceLock<String> = OnceLock::new();
+    KEYRING_DIR.get_or_init(|| [config_dir(), "keyring/"].concat())
+}
+
 pub fn page_cache_dir() -> &'static String {
     static PAGE_CACHE_DIR: OnceLock<String> = OnceLock::new();
     PAGE_CACHE_DIR.get_or_init(|| [cache_dir(), "page_cache/"].concat())
@@ -68,3 +73,18 @@ pub fn lock_file_path() -> &'static String {
     static LOCK_FILE_PATH: OnceLock<String> = OnceLock::new();
     LOCK_FILE_PATH.get_or_init(|| [config_dir(), "lock_state"].concat())
 }
+
+pub fn repos_file_path() -> &'static String {
+    static REPOS_FILE_PATH: OnceLock<String> = OnceLock::new();
+    REPOS_FILE_PATH.get_or_init(|| [config_dir(), "repos.toml"].concat())
+}
+
+pub fn repos_cache_dir() -> &'static String {
+    static REPOS_CACHE_DIR: OnceLock<String> = OnceLock::new();
+    REPOS_CACHE_DIR.get_or_init(|| [cache_dir(), "repos/"].concat())
+}
+
+pub fn manifest_file_path() -> &'static String {
+    static MANIFEST_FILE_PATH: OnceLock<String> = OnceLock::new();
+    MANIFEST_FILE_PATH.get_or_init(|| [config_dir(), "fonts.toml"].concat())
+}
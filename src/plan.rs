@@ -0,0 +1,52 @@
+/// Changes which installing a font would make to the files of its directory,
+/// as shown by `--dry-run`
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct FilePlan {
+    /// Staged files which do not exist yet
+    pub added: Vec<String>,
+    /// Staged files which replace existing files
+    pub overwritten: Vec<String>,
+    /// Files of the previous installation which are not staged,
+    /// and are cleaned up afterwards
+    pub removed: Vec<String>,
+}
+
+impl FilePlan {
+    /// Compares the `staged` files with the files of the previous installation
+    /// (`old_files`), and the files `existing` within the target directory
+    #[must_use]
+    pub fn new(staged: &[String], old_files: &[String], existing: &[String]) -> Self {
+        let (overwritten, added) = staged
+            .iter()
+            .cloned()
+            .partition(|file| existing.contains(file));
+        let removed = old_files
+            .iter()
+            .filter(|file| !staged.contains(file) && existing.contains(file))
+            .cloned()
+            .collect();
+        Self {
+            added,
+            overwritten,
+            removed,
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.overwritten.is_empty() && self.removed.is_empty()
+    }
+
+    /// Lists each file, prefixed by `+` (added), `~` (overwritten) or `-` (removed)
+    pub fn print(&self) {
+        self.added
+            .iter()
+            .for_each(|file| println_green!("   + {file}"));
+        self.overwritten
+            .iter()
+            .for_each(|file| println_orange!("   ~ {file}"));
+        self.removed
+            .iter()
+            .for_each(|file| println_red!("   - {file}"));
+    }
+}
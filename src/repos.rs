@@ -0,0 +1,356 @@
+use crate::installer::Installer;
+use crate::paths::{installers_dir, repos_cache_dir, repos_file_path};
+use crate::template::TEMPLATES_DIR;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use flate2::read::GzDecoder;
+use reqwest::header::USER_AGENT;
+use serde::{Deserialize, Serialize};
+
+/// A subscribed repository of installers
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Repo {
+    pub url: String,
+}
+
+/// The subscribed repositories, stored in `repos.toml`
+#[derive(Default)]
+pub struct Repos {
+    pub repos: BTreeMap<String, Repo>,
+}
+
+/// How the installers of a repository are obtained
+#[derive(Debug, PartialEq, Eq)]
+pub enum RepoKind {
+    /// A git repository, cloned with the `git` command
+    Git,
+    /// A `.tar`, `.tar.gz`, `.tgz` or `.zip` archive
+    Archive,
+    /// A plain text file listing installer names, which are downloaded
+    /// from the same location as the index
+    Index,
+}
+
+impl Repos {
+    pub fn read() -> Result<Self, String> {
+        let contents = fs::read_to_string(repos_file_path()).unwrap_or_default();
+        let repos = toml::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {e}", repos_file_path()))?;
+        Ok(Self { repos })
+    }
+
+    pub fn write(&self) -> Result<(), String> {
+        let contents = toml::to_string(&self.repos).map_err(|e| e.to_string())?;
+        fs::write(repos_file_path(), contents).map_err(|e| e.to_string())
+    }
+}
+
+impl Repo {
+    /// Downloads the installers and templates of the repository, replacing
+    /// its cached ones, and returns how many installers were found
+    pub fn update(&self, name: &str) -> Result<usize, String> {
+        let installers = match RepoKind::detect(&self.url) {
+            RepoKind::Git => fetch_git(name, &self.url)?,
+            RepoKind::Archive => fetch_archive(&self.url)?,
+            RepoKind::Index => fetch_index(&self.url)?,
+        };
+        let count = installers
+            .iter()
+            .filter(|(name, _)| !name.starts_with(TEMPLATES_DIR))
+            .count();
+        if count == 0 {
+            return Err(format!("No installers found in {}", self.url));
+        }
+
+        // Write the new installers next to the old ones, so that a failed
+        // update leaves the previous installers in place
+        let target = cached_dir(name);
+        let staging = [repos_cache_dir().as_str(), ".", name, ".new/"].concat();
+        let _ = fs::remove_dir_all(&staging);
+        fs::create_dir_all([staging.as_str(), TEMPLATES_DIR].concat())
+            .map_err(|e| e.to_string())?;
+        for (installer_name, contents) in &installers {
+            fs::write([staging.as_str(), installer_name].concat(), contents)
+                .map_err(|e| e.to_string())?;
+        }
+        if Path::new(&target).exists() {
+            fs::remove_dir_all(&target).map_err(|e| e.to_string())?;
+        }
+        fs::rename(&staging, &target).map_err(|e| e.to_string())?;
+
+        Ok(count)
+    }
+}
+
+impl RepoKind {
+    #[must_use]
+    pub fn detect(url: &str) -> Self {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        if path.ends_with(".git") || url.starts_with("git@") || url.starts_with("ssh://") {
+            RepoKind::Git
+        } else if [".tar", ".tar.gz", ".tgz", ".zip"]
+            .iter()
+            .any(|extension| path.ends_with(extension))
+        {
+            RepoKind::Archive
+        } else {
+            RepoKind::Index
+        }
+    }
+}
+
+/// Returns `true` if `name` may be used as the name of a repository
+#[must_use]
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Returns the directory containing the cached installers of the repository
+#[must_use]
+pub fn cached_dir(name: &str) -> String {
+    [repos_cache_dir().as_str(), name, "/"].concat()
+}
+
+/// Returns the directories installers are read from, in order of precedence,
+/// along with the repository each belongs to (`None` for local installers)
+pub fn installer_dirs() -> Result<Vec<(Option<String>, String)>, String> {
+    let mut dirs = vec![(None, installers_dir().to_owned())];
+    for name in Repos::read()?.repos.into_keys() {
+        let dir = cached_dir(&name);
+        dirs.push((Some(name), dir));
+    }
+    Ok(dirs)
+}
+
+/// Returns the directory containing the installer, and the repository it
+/// belongs to, with local installers overriding those of repositories
+#[must_use]
+pub fn find_installer(installer_name: &str) -> Option<(String, Option<String>)> {
+    if installer_name.is_empty() || installer_name.contains('/') {
+        return None;
+    }
+    installer_dirs()
+        .unwrap_or_else(|_| vec![(None, installers_dir().to_owned())])
+        .into_iter()
+        .find(|(_, dir)| Path::new(&[dir.as_str(), installer_name].concat()).is_file())
+        .map(|(repo, dir)| (dir, repo))
+}
+
+/// Parses an index, which lists one installer name per line (or the name
+/// of a template, such as `templates/nerd-font`), ignoring empty lines
+/// and `#` comments
+pub fn parse_index(index: &str) -> Result<Vec<&str>, String> {
+    let is_invalid = |name: &str| name.starts_with('.') || name.contains(['/', '\\']);
+    index
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(
+            |name| match is_invalid(name.strip_prefix(TEMPLATES_DIR).unwrap_or(name)) {
+                true => Err(format!("Invalid installer name in index: {name}")),
+                false => Ok(name),
+            },
+        )
+        .collect()
+}
+
+/// Selects the installers from the files of a repository, given their paths
+/// relative to its root. Files within `installers/` directories are preferred,
+/// otherwise files at the root are used. Files which are not valid installers,
+/// such as a README, are ignored. Templates found within `templates/` next to
+/// the installers are named `templates/<name>`.
+#[must_use]
+pub fn select_installers(files: Vec<(String, String)>) -> Vec<(String, String)> {
+    let files = files
+        .into_iter()
+        .filter(|(path, _)| !path.split('/').any(|part| part.starts_with('.')))
+        .collect::<Vec<_>>();
+
+    // Archives of repositories usually place everything within one directory
+    let top_level = files
+        .first()
+        .and_then(|(path, _)| path.split_once('/'))
+        .map(|(dir, _)| dir.to_owned() + "/")
+        .filter(|dir| files.iter().all(|(path, _)| path.starts_with(dir)));
+    let files = files.into_iter().map(|(path, contents)| match &top_level {
+        Some(dir) => (path[dir.len()..].to_owned(), contents),
+        None => (path, contents),
+    });
+
+    // Templates are kept next to the installers, within `templates/`
+    let (mut nested, mut root) = (Vec::new(), Vec::new());
+    for (path, contents) in files {
+        let (parent, name) = path.rsplit_once('/').unwrap_or(("", &path));
+        let (dir, template) = match parent.strip_suffix("templates") {
+            Some(dir) if dir.is_empty() || dir.ends_with('/') => (dir.trim_end_matches('/'), true),
+            _ => (parent, false),
+        };
+        let is_nested = dir == "installers" || dir.ends_with("/installers");
+        if !is_nested && !dir.is_empty() {
+            continue;
+        }
+        let is_valid = match template {
+            true => contents.parse::<toml::Table>().is_ok(),
+            false => is_installer(&contents),
+        };
+        if is_valid {
+            let name = match template {
+                true => [TEMPLATES_DIR, name].concat(),
+                false => name.to_owned(),
+            };
+            match is_nested {
+                true => nested.push((template, name, contents)),
+                false => root.push((template, name, contents)),
+            }
+        }
+    }
+
+    let candidates = match nested.iter().any(|(template, _, _)| !template) {
+        true => nested,
+        false => root,
+    };
+    let installers = candidates
+        .into_iter()
+        .map(|(_, name, contents)| (name, contents))
+        .collect::<BTreeMap<_, _>>();
+    installers.into_iter().collect()
+}
+
+/// Returns `true` if the file is an installer, or extends a template
+/// (in which case its fields are only known once it has been resolved)
+fn is_installer(contents: &str) -> bool {
+    toml::from_str::<Installer>(contents).is_ok()
+        || contents
+            .parse::<toml::Table>()
+            .is_ok_and(|table| table.contains_key("extends"))
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let response = reqwest::blocking::Client::new()
+        .get(url)
+        .header(USER_AGENT, "fin")
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .map_err(|e| e.to_string())?;
+    Ok(response.bytes().map_err(|e| e.to_string())?.to_vec())
+}
+
+fn fetch_git(name: &str, url: &str) -> Result<Vec<(String, String)>, String> {
+    let checkout = [repos_cache_dir().as_str(), ".", name, ".git/"].concat();
+    let _ = fs::remove_dir_all(&checkout);
+    fs::create_dir_all(repos_cache_dir()).map_err(|e| e.to_string())?;
+
+    let status = Command::new("git")
+        .args(["clone", "--depth", "1", "--quiet", "--", url, &checkout])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .status()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !status.success() {
+        let _ = fs::remove_dir_all(&checkout);
+        return Err(format!("Failed to clone {url}"));
+    }
+
+    let mut files = Vec::new();
+    read_files(Path::new(&checkout), "", &mut files);
+    let _ = fs::remove_dir_all(&checkout);
+    Ok(select_installers(files))
+}
+
+/// Reads the text files within `dir` recursively, skipping hidden files and
+/// symlinks (which may loop, or lead outside of the repository)
+fn read_files(dir: &Path, prefix: &str, files: &mut Vec<(String, String)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if name.starts_with('.') || file_type.is_symlink() {
+            continue;
+        }
+        let path = entry.path();
+        if file_type.is_dir() {
+            read_files(&path, &[prefix, &name, "/"].concat(), files);
+        } else if let Ok(contents) = fs::read_to_string(&path) {
+            files.push(([prefix, &name].concat(), contents));
+        }
+    }
+}
+
+fn fetch_archive(url: &str) -> Result<Vec<(String, String)>, String> {
+    let data = download(url)?;
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let files = match path.ends_with(".zip") {
+        true => read_zip(data)?,
+        false => match path.ends_with(".tar") {
+            true => read_tar(Cursor::new(data))?,
+            false => read_tar(GzDecoder::new(Cursor::new(data)))?,
+        },
+    };
+    Ok(select_installers(files))
+}
+
+/// Reads the text files of a tar archive
+pub fn read_tar<R: Read>(reader: R) -> Result<Vec<(String, String)>, String> {
+    let mut archive = tar::Archive::new(reader);
+    let mut files = Vec::new();
+    for entry in archive.entries().map_err(|e| e.to_string())? {
+        let mut entry = entry.map_err(|e| e.to_string())?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .map_err(|e| e.to_string())?
+            .to_string_lossy()
+            .into_owned();
+        let mut contents = String::new();
+        if entry.read_to_string(&mut contents).is_ok() {
+            files.push((path, contents));
+        }
+    }
+    Ok(files)
+}
+
+/// Reads the text files of a zip archive
+pub fn read_zip(data: Vec<u8>) -> Result<Vec<(String, String)>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).map_err(|e| e.to_string())?;
+    let mut files = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
+        if !file.is_file() {
+            continue;
+        }
+        let path = file.name().to_owned();
+        let mut contents = String::new();
+        if file.read_to_string(&mut contents).is_ok() {
+            files.push((path, contents));
+        }
+    }
+    Ok(files)
+}
+
+fn fetch_index(url: &str) -> Result<Vec<(String, String)>, String> {
+    let index = String::from_utf8(download(url)?).map_err(|e| e.to_string())?;
+    let base = &url[..=url.rfind('/').unwrap_or_default()];
+
+    parse_index(&index)?
+        .into_iter()
+        .map(|name| {
+            let contents = String::from_utf8(download(&[base, name].concat())?)
+                .map_err(|e| format!("{name}: {e}"))?;
+            Ok((name.to_owned(), contents))
+        })
+        .collect()
+}
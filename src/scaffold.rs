@@ -0,0 +1,333 @@
+use crate::file_action::{FileAction, FileType};
+use crate::source::Source;
+use crate::wildcards::match_any_wildcard;
+
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+
+use flate2::read::GzDecoder;
+
+/// A file which may be downloaded, found within a release or webpage
+pub struct Asset {
+    pub name: String,
+    pub url: String,
+}
+
+/// The fields proposed for a new installer
+pub struct Scaffold {
+    pub name: String,
+    /// Either a `GitHub` or `Webpage` source
+    pub source: Source,
+    pub file: String,
+    /// `None` for single files
+    pub include: Option<Vec<String>>,
+    pub exclude: Vec<String>,
+    /// Checksum algorithm, and the name of the file listing the sum
+    pub check: Option<(&'static str, String)>,
+}
+
+/// Extensions of the files which may be installed directly
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "woff", "woff2"];
+
+/// File names of licenses, which are installed alongside the fonts
+const LICENSE_PREFIXES: &[&str] = &["LICENSE", "LICENCE", "OFL", "COPYING"];
+
+/// Directories containing copies of the fonts which should not be installed
+const UNWANTED_DIRS: &[&str] = &["unhinted", "webfont", "__macosx"];
+
+/// Returns `true` if the asset is a font, or an archive which may contain fonts
+#[must_use]
+pub fn is_font_asset(name: &str) -> bool {
+    !matches!(FileAction::get_file_type(name), FileType::Unsupported)
+        || FONT_EXTENSIONS.contains(&extension(name).as_str())
+}
+
+/// Returns the assets listed by a GitHub API release, and its tag
+pub fn release_assets(release: &str) -> Result<(Vec<Asset>, Option<String>), String> {
+    let release = serde_json::from_str::<serde_json::Value>(release)
+        .map_err(|e| format!("Failed to parse the release: {e}"))?;
+    let assets = release["assets"]
+        .as_array()
+        .ok_or("The release does not list any assets")?
+        .iter()
+        .filter_map(|asset| {
+            Some(Asset {
+                name: asset["name"].as_str()?.to_owned(),
+                url: asset["browser_download_url"].as_str()?.to_owned(),
+            })
+        })
+        .collect();
+    let tag = release["tag_name"].as_str().map(ToOwned::to_owned);
+    Ok((assets, tag))
+}
+
+/// Returns the files linked from a webpage
+#[must_use]
+pub fn page_links(page: &str) -> Vec<Asset> {
+    let mut assets = Vec::<Asset>::new();
+    for url in page
+        .split(['"', '\''])
+        .filter(|s| s.starts_with("https://"))
+    {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let Some((_, name)) = path.rsplit_once('/') else {
+            continue;
+        };
+        if name.contains('.') && !assets.iter().any(|asset| asset.url == url) {
+            assets.push(Asset {
+                name: name.to_owned(),
+                url: url.to_owned(),
+            });
+        }
+    }
+    assets
+}
+
+/// Replaces the version within the file name with a wildcard, so that
+/// the installer keeps working for later releases
+#[must_use]
+pub fn generalize_file(file: &str, tag: Option<&str>) -> String {
+    let Some(tag) = tag else {
+        return file.to_owned();
+    };
+    [tag, tag.trim_start_matches(['v', 'V'])]
+        .into_iter()
+        .filter(|version| version.chars().any(|c| c.is_ascii_digit()))
+        .find(|version| file.contains(version))
+        .map_or_else(|| file.to_owned(), |version| file.replacen(version, "*", 1))
+}
+
+/// Finds the asset listing the checksum of `file`, and returns the
+/// algorithm and the name of the checksum asset
+#[must_use]
+pub fn detect_checksum(file: &str, assets: &[String]) -> Option<(&'static str, String)> {
+    const ALGORITHMS: &[(&str, &str)] = &[
+        ("sha512", "SHA512"),
+        ("sha384", "SHA384"),
+        ("sha256", "SHA256"),
+        ("sha224", "SHA224"),
+        ("sha1", "SHA1"),
+        ("md5", "MD5"),
+        ("b3", "BLAKE3"),
+        ("blake3", "BLAKE3"),
+    ];
+    let algorithm = |name: &str| {
+        let name = name.to_lowercase();
+        ALGORITHMS
+            .iter()
+            .find(|(extension, _)| name.contains(extension))
+            .map(|(_, algorithm)| *algorithm)
+    };
+
+    // A checksum file of the asset itself, followed by one listing every asset
+    let own = assets.iter().find(|name| {
+        name.strip_prefix(file)
+            .and_then(|suffix| suffix.strip_prefix('.'))
+            .is_some_and(|suffix| algorithm(suffix).is_some() || suffix == "sum")
+    });
+    let listing = || {
+        assets.iter().find(|name| {
+            let lower = name.to_lowercase();
+            lower.contains("checksum") || lower.contains("sums")
+        })
+    };
+    let name = own.or_else(listing)?;
+    Some((algorithm(name).unwrap_or("SHA256"), name.to_owned()))
+}
+
+/// Returns the paths of the files within an archive
+pub fn archive_paths(data: Vec<u8>, file_type: &FileType) -> Result<Vec<String>, String> {
+    let tar_paths = |reader: &mut dyn Read| -> Result<Vec<String>, String> {
+        let mut archive = tar::Archive::new(reader);
+        let mut paths = Vec::new();
+        for entry in archive.entries().map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            if entry.header().entry_type().is_file() {
+                let path = entry.path().map_err(|e| e.to_string())?;
+                paths.push(path.to_string_lossy().into_owned());
+            }
+        }
+        Ok(paths)
+    };
+
+    match file_type {
+        FileType::Zip => {
+            let archive = zip::ZipArchive::new(Cursor::new(data)).map_err(|e| e.to_string())?;
+            Ok(archive
+                .file_names()
+                .filter(|name| !name.ends_with('/'))
+                .map(ToOwned::to_owned)
+                .collect())
+        }
+        FileType::Tar => tar_paths(&mut Cursor::new(data)),
+        FileType::TarGz => tar_paths(&mut GzDecoder::new(Cursor::new(data))),
+        FileType::TarXz | FileType::Unsupported => Err("Unsupported archive".to_string()),
+    }
+}
+
+/// Proposes the `include` and `exclude` patterns of an archive, given the
+/// paths of its files. TrueType fonts are preferred over OpenType fonts,
+/// and licenses are included alongside them.
+#[must_use]
+pub fn propose_rules(paths: &[String]) -> (Vec<String>, Vec<String>) {
+    let fonts_of = |wanted: &str| {
+        paths
+            .iter()
+            .filter(|path| extension(path) == wanted)
+            .collect::<Vec<_>>()
+    };
+    let fonts = ["ttf", "otf", "ttc"]
+        .into_iter()
+        .map(fonts_of)
+        .find(|fonts| !fonts.is_empty())
+        .unwrap_or_default();
+
+    let mut exclude = Vec::new();
+    for path in &fonts {
+        for dir in path.split('/').rev().skip(1) {
+            let lower = dir.to_lowercase();
+            let pattern = format!("*{dir}/*");
+            if UNWANTED_DIRS
+                .iter()
+                .any(|unwanted| lower.contains(unwanted))
+                && !exclude.contains(&pattern)
+            {
+                exclude.push(pattern);
+            }
+        }
+    }
+    let fonts = fonts
+        .into_iter()
+        .filter(|path| !match_any_wildcard(path, &exclude))
+        .collect::<Vec<_>>();
+
+    let mut include = Vec::new();
+    if let Some(first) = fonts.first() {
+        let extension = &first[first.rfind('.').unwrap_or_default() + 1..];
+        include.push(match has_duplicate_names(&fonts) {
+            // Fonts sharing a name would overwrite each other, so only
+            // the fonts of one directory are included
+            true => format!("*{}/*.{extension}", preferred_dir(&fonts)),
+            false => format!("*.{extension}"),
+        });
+    }
+
+    let licenses = paths
+        .iter()
+        .filter(|path| !match_any_wildcard(path, &exclude))
+        .filter(|path| {
+            let name = file_name(path).to_uppercase();
+            LICENSE_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        })
+        .collect::<Vec<_>>();
+    let mut by_name = BTreeMap::<&str, Vec<&String>>::new();
+    for path in &licenses {
+        by_name.entry(file_name(path)).or_default().push(path);
+    }
+    for (name, mut paths) in by_name {
+        match paths.len() {
+            1 => include.push(format!("*{name}")),
+            // Only include the outermost of the licenses sharing a name
+            _ => {
+                paths.sort_by_key(|path| path.matches('/').count());
+                include.push(paths[0].to_string());
+            }
+        }
+    }
+
+    (include, exclude)
+}
+
+impl Scaffold {
+    /// Formats the installer the same way as the bundled installers
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let list = |items: &[String]| {
+            let items = items.iter().map(|item| quote(item)).collect::<Vec<_>>();
+            format!("[ {} ]", items.join(", "))
+        };
+
+        let mut toml = format!("name = {}\n\n", quote(&self.name));
+        match &self.source {
+            Source::GitHub {
+                author, project, ..
+            } => {
+                toml += &format!("[source.GitHub]\nauthor = {}\n", quote(author));
+                toml += &format!("project = {}\n", quote(project));
+            }
+            Source::Webpage { url, .. } | Source::Direct { url, .. } => {
+                toml += &format!("[source.Webpage]\nurl = {}\n", quote(url));
+            }
+            Source::None => (),
+        }
+
+        match &self.include {
+            Some(include) => {
+                toml += &format!("\n[action.Extract]\nfile = {}\n", quote(&self.file));
+                toml += &format!("include = {}\n", list(include));
+                if !self.exclude.is_empty() {
+                    toml += &format!("exclude = {}\n", list(&self.exclude));
+                }
+            }
+            None => toml += &format!("\n[action.SingleFile]\nfile = {}\n", quote(&self.file)),
+        }
+
+        if let Some((algorithm, file)) = &self.check {
+            toml += &format!("\n[check.{algorithm}]\nfile = {}\n", quote(file));
+        }
+        toml
+    }
+}
+
+/// Turns an installer name such as `maple-mono` into `Maple Mono`
+#[must_use]
+pub fn display_name(installer_name: &str) -> String {
+    installer_name
+        .split(['-', '_', ' '])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect::<String>()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn quote(value: &str) -> String {
+    toml::Value::String(value.to_owned()).to_string()
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn extension(path: &str) -> String {
+    file_name(path)
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default()
+}
+
+fn has_duplicate_names(paths: &[&String]) -> bool {
+    let mut names = paths.iter().map(|path| file_name(path)).collect::<Vec<_>>();
+    names.sort_unstable();
+    names.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+/// Returns the name of the directory whose fonts should be installed,
+/// preferring variable fonts, followed by the directory with the most fonts
+fn preferred_dir(paths: &[&String]) -> String {
+    let mut dirs = BTreeMap::<&str, usize>::new();
+    for path in paths {
+        let dir = path.rsplit('/').nth(1).unwrap_or_default();
+        *dirs.entry(dir).or_default() += 1;
+    }
+    dirs.iter()
+        .max_by_key(|(dir, count)| (dir.to_lowercase().contains("variable"), **count))
+        .map(|(dir, _)| (*dir).to_owned())
+        .unwrap_or_default()
+}
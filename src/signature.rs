@@ -0,0 +1,176 @@
+use crate::file_action::FileAction;
+use crate::openpgp;
+use crate::paths::keyring_dir;
+use crate::source::Source;
+
+use std::fs;
+
+use serde::Deserialize;
+
+/// Where to obtain the public key and the detached signature from
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureCheck {
+    /// The public key itself
+    pub public_key: Option<String>,
+    /// Name of the public key file within the keyring directory
+    pub key_file: Option<String>,
+    /// Name of the signature file linked from the font page
+    pub file: Option<String>,
+    /// Direct link to the signature file
+    pub url: Option<String>,
+
+    /// Contents of the signature file, once obtained
+    #[serde(skip)]
+    pub signature: Option<Vec<u8>>,
+}
+
+/// Supported detached signature formats
+#[derive(Debug, Clone, Copy)]
+pub enum SignatureKind {
+    Minisign,
+    PGP,
+}
+
+impl SignatureKind {
+    /// Extension of the signature file, which is appended to the downloaded
+    /// file's URL when neither `file` nor `url` are specified
+    const fn extension(self) -> &'static str {
+        match self {
+            SignatureKind::Minisign => ".minisig",
+            SignatureKind::PGP => ".asc",
+        }
+    }
+}
+
+impl SignatureCheck {
+    /// Ensures exactly one of `public_key` or `key_file`, and at most one of
+    /// `file` or `url` is specified, and substitutes `$tag`
+    pub fn validate(
+        &mut self,
+        tag: Option<&str>,
+        has_font_page: bool,
+        name: &str,
+    ) -> Result<(), String> {
+        match (&self.public_key, &self.key_file) {
+            (Some(_), None) => {}
+            (None, Some(key_file)) if !key_file.contains('/') && !key_file.is_empty() => {}
+            (None, Some(key_file)) => {
+                return Err(format!("{name}: Invalid `key_file`: \"{key_file}\""));
+            }
+            _ => {
+                return Err(format!(
+                    "{name}: Signatures require one of `public_key` or `key_file`"
+                ));
+            }
+        }
+
+        match (&mut self.file, &mut self.url) {
+            (Some(file), None) if has_font_page => FileAction::validate_file(file, tag, name),
+            (Some(_), None) => Err(format!(
+                "{name}: Direct links require the signature `url` instead of `file`"
+            )),
+            (None, Some(url)) => {
+                if let Some(tag) = tag {
+                    *url = url.replace("$tag", tag);
+                } else if url.contains("$tag") {
+                    return Err(format!("{name}: Use of missing field: `$tag`"));
+                }
+                Ok(())
+            }
+            (None, None) => Ok(()),
+            (Some(_), Some(_)) => Err(format!(
+                "{name}: Only one of the signature `file` or `url` may be specified"
+            )),
+        }
+    }
+
+    /// Downloads the detached signature of the file at `asset_url`,
+    /// and assigns it to `signature`
+    pub async fn obtain(
+        &mut self,
+        kind: SignatureKind,
+        font_page: Option<String>,
+        asset_url: &str,
+        reqwest_client: &reqwest::Client,
+        installer_name: &str,
+    ) -> Result<(), String> {
+        let asset_file = asset_url.rsplit_once('/').unwrap_or_default().1;
+        let signature_link = match (&self.file, &self.url) {
+            (_, Some(url)) => url.replace("$file", asset_file),
+            (Some(file), _) => Source::find_direct_link(
+                font_page.as_ref().expect("Font page is not available"),
+                file,
+                installer_name,
+            )?,
+            (None, None) => asset_url.to_owned() + kind.extension(),
+        };
+
+        self.signature = Some(
+            reqwest_client
+                .get(&signature_link)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .error_for_status()
+                .map_err(|e| e.to_string())?
+                .bytes()
+                .await
+                .map_err(|e| e.to_string())?
+                .to_vec(),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the public key, reading it from the keyring directory if needed
+    fn public_key(&self) -> Result<Vec<u8>, String> {
+        match (&self.public_key, &self.key_file) {
+            (Some(public_key), _) => Ok(public_key.as_bytes().to_vec()),
+            (None, Some(key_file)) => {
+                let path = [keyring_dir().as_str(), key_file].concat();
+                fs::read(&path).map_err(|e| format!("{path}: {e}"))
+            }
+            (None, None) => Err("No public key specified".into()),
+        }
+    }
+
+    /// Verifies the obtained signature of `data`.
+    /// Returns `Ok` if it was made by the public key, or `Err` if not.
+    pub fn verify(&mut self, kind: SignatureKind, data: &[u8]) -> Result<(), String> {
+        let signature = self.signature.take().ok_or("Signature was not obtained")?;
+        let public_key = self.public_key()?;
+
+        match kind {
+            SignatureKind::Minisign => verify_minisign(&public_key, &signature, data),
+            SignatureKind::PGP => {
+                let keys = openpgp::parse_keys(&public_key)?;
+                openpgp::verify(&keys, &signature, data)
+            }
+        }
+    }
+}
+
+/// Verifies the minisign `signature` of `data`. The public key may either be
+/// the base64 encoded key, or the contents of a `.pub` file.
+pub fn verify_minisign(public_key: &[u8], signature: &[u8], data: &[u8]) -> Result<(), String> {
+    let public_key = std::str::from_utf8(public_key)
+        .map_err(|e| format!("Invalid public key: {e}"))?
+        .trim();
+    let public_key = match public_key.lines().count() {
+        1 => minisign_verify::PublicKey::from_base64(public_key),
+        _ => minisign_verify::PublicKey::decode(public_key),
+    }
+    .map_err(|e| format!("Invalid public key: {e}"))?;
+
+    let signature = std::str::from_utf8(signature)
+        .map_err(|e| e.to_string())
+        .and_then(|signature| {
+            minisign_verify::Signature::decode(signature).map_err(|e| e.to_string())
+        })
+        .map_err(|e| format!("Invalid signature: {e}"))?;
+
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|e| e.to_string())
+}
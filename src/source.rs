@@ -8,7 +8,8 @@ use std::sync::{Arc, Mutex};
 
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub enum Source {
     GitHub {
         tag: Option<String>,
@@ -23,11 +24,12 @@ pub enum Source {
         tag: Option<String>,
         url: String,
     },
+    #[default]
     None,
 }
 
 impl Source {
-    pub fn validate(&mut self, file: &str, name: &str) -> Result<(), String> {
+    pub fn validate(&mut self, name: &str) -> Result<(), String> {
         match self {
             Source::GitHub {
                 author, project, ..
@@ -59,9 +61,6 @@ impl Source {
                 if !url.ends_with("$file") {
                     return Err(format!("{name}: Direct URLs must end with `$file`"));
                 }
-
-                // TODO: Get the redirected URL for direct links
-                *url = url.replace("$file", file);
             }
             Source::None => return Err(format!("{name}: A valid source must be provided")),
         }
@@ -84,6 +83,21 @@ impl Source {
         }
     }
 
+    /// Returns the path of a GitHub release within the releases API
+    /// (`releases/{path}`). Release tags (as recorded when installing) are
+    /// accepted without the `tags/` prefix, while `latest` and numeric
+    /// release IDs are left as is.
+    #[must_use]
+    pub fn github_release(tag: &str) -> String {
+        let is_id = tag.bytes().all(|b| b.is_ascii_digit());
+        match tag == "latest" || tag.starts_with("tags/") || is_id {
+            true => tag.to_owned(),
+            false => format!("tags/{tag}"),
+        }
+    }
+
+    /// Resolves the download URL of every asset, and turns `self`
+    /// into a `Direct` source pointing at the first one
     pub fn into_direct_url(
         &mut self,
         installer: &mut Installer,
@@ -97,28 +111,48 @@ impl Source {
                 *self = Source::Webpage {
                     url: format!("https://api.github.com/repos/{author}/{project}/releases/$tag"),
                     tag: if let Source::GitHub { tag, .. } = self.take() {
-                        tag
+                        tag.as_deref().map(Self::github_release)
                     } else {
                         unreachable!()
                     },
                 };
-                self.validate(installer.action.ref_file()?, &installer.installer_name)?;
-                self.into_direct_url(installer, args, cached_pages)
+                self.validate(&installer.installer_name)?;
+                self.into_direct_url(installer, args, cached_pages)?;
+
+                // Note the tag of the release, which may have been "latest"
+                installer.release_tag = installer.font_page.as_deref().and_then(|page| {
+                    let release = serde_json::from_str::<serde_json::Value>(page).ok()?;
+                    release["tag_name"].as_str().map(ToOwned::to_owned)
+                });
+                Ok(())
             }
             Source::Webpage { url, .. } => {
                 installer.font_page = Self::get_font_page(args, url, cached_pages)?.contents;
-                let url = Self::find_direct_link(
-                    installer.font_page.as_ref().unwrap(),
-                    installer.action.ref_file()?,
-                    &installer.installer_name,
-                )?;
+                for asset in &mut installer.action {
+                    asset.url = Self::find_direct_link(
+                        installer.font_page.as_ref().unwrap(),
+                        asset.action.ref_file()?,
+                        &installer.installer_name,
+                    )?;
+                }
                 let Source::Webpage { tag, .. } = self.take() else {
                     unreachable!()
                 };
-                *self = Self::Direct { url, tag };
+                *self = Self::Direct {
+                    url: installer.action[0].url.clone(),
+                    tag,
+                };
+                Ok(())
+            }
+            Source::Direct { url, .. } => {
+                // TODO: Get the redirected URL for direct links
+                for asset in &mut installer.action {
+                    asset.url = url.replace("$file", asset.action.ref_file()?);
+                }
+                *url = installer.action[0].url.clone();
                 Ok(())
             }
-            Source::Direct { .. } | Source::None => Ok(()),
+            Source::None => Ok(()),
         }
     }
     fn get_font_page(
@@ -0,0 +1,181 @@
+use crate::repos;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use toml::{Table, Value};
+
+/// Directory containing the templates, within each installers directory
+pub const TEMPLATES_DIR: &str = "templates/";
+
+/// Variables which are substituted later on, and cannot be defined
+const RESERVED_VARIABLES: &[&str] = &["tag", "file"];
+
+/// How many templates may extend each other before giving up
+const MAX_DEPTH: usize = 8;
+
+/// Reads the installer, and resolves the template it extends (if any)
+pub fn read(installer_dir: &str, installer_name: &str) -> Result<String, String> {
+    let contents = fs::read_to_string([installer_dir, installer_name].concat())
+        .map_err(|e| format!("{installer_name}: {e}"))?;
+    resolve(&contents, installer_dir, installer_name)
+}
+
+/// Merges the installer over the template named by its `extends` field,
+/// and substitutes its `variables`. Installers without either field are
+/// returned unchanged.
+pub fn resolve(
+    contents: &str,
+    installer_dir: &str,
+    installer_name: &str,
+) -> Result<String, String> {
+    let table = contents
+        .parse::<Table>()
+        .map_err(|e| format!("{installer_name}: {}", e.message()))?;
+    if !table.contains_key("extends") && !table.contains_key("variables") {
+        return Ok(contents.to_owned());
+    }
+
+    let mut table = resolve_table(table, installer_dir, installer_name, 0)?;
+    let variables = match table.remove("variables") {
+        Some(Value::Table(variables)) => variables,
+        Some(_) => return Err(format!("{installer_name}: `variables` must be a table")),
+        None => Table::new(),
+    };
+
+    let mut substitutions = HashMap::new();
+    for (variable, value) in variables {
+        if RESERVED_VARIABLES.contains(&variable.as_str()) {
+            return Err(format!(
+                "{installer_name}: Variable `${variable}` is reserved"
+            ));
+        }
+        let Value::String(value) = value else {
+            return Err(format!(
+                "{installer_name}: Variable `${variable}` must be a string"
+            ));
+        };
+        substitutions.insert(variable, value);
+    }
+    substitute(&mut table, &substitutions);
+
+    toml::to_string(&table).map_err(|e| format!("{installer_name}: {e}"))
+}
+
+fn resolve_table(
+    mut table: Table,
+    installer_dir: &str,
+    installer_name: &str,
+    depth: usize,
+) -> Result<Table, String> {
+    let template_name = match table.remove("extends") {
+        Some(Value::String(template_name)) => template_name,
+        Some(_) => return Err(format!("{installer_name}: `extends` must be a string")),
+        None => return Ok(table),
+    };
+    if depth >= MAX_DEPTH {
+        return Err(format!(
+            "{installer_name}: Too many templates extend each other (reached `{template_name}`)"
+        ));
+    }
+
+    let path = find(&template_name, installer_dir)
+        .ok_or_else(|| format!("{installer_name}: Template not found: `{template_name}`"))?;
+    let template = fs::read_to_string(&path)
+        .map_err(|e| format!("{installer_name}: {e}"))?
+        .parse::<Table>()
+        .map_err(|e| {
+            format!(
+                "{installer_name}: Template `{template_name}`: {}",
+                e.message()
+            )
+        })?;
+
+    let mut template = resolve_table(template, installer_dir, installer_name, depth + 1)?;
+    merge(&mut template, table);
+    Ok(template)
+}
+
+/// Returns the path of the template, preferring the templates next to
+/// the installer, followed by the local templates and those of repositories
+#[must_use]
+pub fn find(template_name: &str, installer_dir: &str) -> Option<String> {
+    if template_name.is_empty() || template_name.starts_with('.') || template_name.contains('/') {
+        return None;
+    }
+    let dirs = repos::installer_dirs().unwrap_or_default();
+    std::iter::once(installer_dir)
+        .chain(dirs.iter().map(|(_, dir)| dir.as_str()))
+        .map(|dir| [dir, TEMPLATES_DIR, template_name].concat())
+        .find(|path| Path::new(path).is_file())
+}
+
+/// Merges `overrides` into `base`: tables are merged recursively,
+/// and any other value replaces the value of `base`. Tables selecting
+/// a different variant (such as `[source.Webpage]` over `[source.GitHub]`)
+/// replace the table of `base`.
+pub fn merge(base: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overrides))
+                if !is_other_variant(base, &overrides) =>
+            {
+                merge(base, overrides);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn is_other_variant(base: &Table, overrides: &Table) -> bool {
+    let is_variant = |table: &Table| {
+        table.len() == 1
+            && table
+                .keys()
+                .all(|key| key.starts_with(|c: char| c.is_ascii_uppercase()))
+    };
+    is_variant(base) && is_variant(overrides) && base.keys().ne(overrides.keys())
+}
+
+fn substitute(table: &mut Table, substitutions: &HashMap<String, String>) {
+    for (_, value) in table.iter_mut() {
+        substitute_value(value, substitutions);
+    }
+}
+
+fn substitute_value(value: &mut Value, substitutions: &HashMap<String, String>) {
+    match value {
+        Value::String(text) => *text = substitute_text(text, substitutions),
+        Value::Array(values) => {
+            for value in values {
+                substitute_value(value, substitutions);
+            }
+        }
+        Value::Table(table) => substitute(table, substitutions),
+        _ => (),
+    }
+}
+
+/// Replaces every `$name` whose whole name is a variable, so that `$t` does
+/// not replace the start of `$tag`. Other names (such as `$tag` and `$file`,
+/// which are substituted later on) are kept.
+fn substitute_text(text: &str, substitutions: &HashMap<String, String>) -> String {
+    let is_name = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let name = &after[..after.find(|c| !is_name(c)).unwrap_or(after.len())];
+        match substitutions.get(name) {
+            Some(value) => result.push_str(value),
+            None => result.push_str(&rest[start..=start + name.len()]),
+        }
+        rest = &after[name.len()..];
+    }
+    result.push_str(rest);
+    result
+}
@@ -0,0 +1,630 @@
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+/// Tags of the tables known to WOFF2, in the order of their index
+/// within the table directory flags
+const KNOWN_TAGS: [&[u8; 4]; 63] = [
+    b"cmap", b"head", b"hhea", b"hmtx", b"maxp", b"name", b"OS/2", b"post", b"cvt ", b"fpgm",
+    b"glyf", b"loca", b"prep", b"CFF ", b"VORG", b"EBDT", b"EBLC", b"gasp", b"hdmx", b"kern",
+    b"LTSH", b"PCLT", b"VDMX", b"vhea", b"vmtx", b"BASE", b"GDEF", b"GPOS", b"GSUB", b"EBSC",
+    b"JSTF", b"MATH", b"CBDT", b"CBLC", b"COLR", b"CPAL", b"SVG ", b"sbix", b"acnt", b"avar",
+    b"bdat", b"bloc", b"bsln", b"cvar", b"fdsc", b"feat", b"fmtx", b"fvar", b"gvar", b"hsty",
+    b"just", b"lcar", b"mort", b"morx", b"opbd", b"prop", b"trak", b"Zapf", b"Silf", b"Glat",
+    b"Gloc", b"Feat", b"Sill",
+];
+
+/// Tables of a font, as pairs of their tag and contents
+type Tables = Vec<([u8; 4], Vec<u8>)>;
+
+/// Reconstructed `glyf` and `loca` tables, and the `xMin` of every glyph
+type GlyfLoca = (Vec<u8>, Vec<u8>, Vec<i16>);
+
+/// Returns `true` if the file name has a WOFF or WOFF2 extension
+#[must_use]
+pub fn is_web_font(file: &str) -> bool {
+    let file = file.to_lowercase();
+    file.ends_with(".woff") || file.ends_with(".woff2")
+}
+
+/// Decodes a WOFF or WOFF2 font into an sfnt (TrueType/OpenType) font.
+/// Returns the decoded font and its file extension (`ttf` or `otf`).
+pub fn decode(data: &[u8]) -> Result<(Vec<u8>, &'static str), String> {
+    let mut reader = Reader::new(data);
+    let (flavor, tables) = match &reader.tag()? {
+        b"wOFF" => decode_woff(&mut reader)?,
+        b"wOF2" => decode_woff2(&mut reader)?,
+        _ => return Err("Not a WOFF or WOFF2 font".to_string()),
+    };
+
+    let extension = match &flavor.to_be_bytes() {
+        b"OTTO" => "otf",
+        b"ttcf" => return Err("Font collections are not supported".to_string()),
+        _ => "ttf",
+    };
+
+    Ok((build_sfnt(flavor, tables)?, extension))
+}
+
+/// Reads the tables of a WOFF font (after its signature)
+fn decode_woff(reader: &mut Reader) -> Result<(u32, Tables), String> {
+    let flavor = reader.u32()?;
+    reader.skip(4)?; // length
+    let num_tables = reader.u16()?;
+    reader.skip(2 + 4 + 2 + 2 + 5 * 4)?;
+    if num_tables == 0 {
+        return Err("WOFF font has no tables".to_string());
+    }
+
+    let mut tables = Vec::with_capacity(num_tables.into());
+    for _ in 0..num_tables {
+        let tag = reader.tag()?;
+        let offset = reader.u32()? as usize;
+        let comp_length = reader.u32()? as usize;
+        let orig_length = reader.u32()? as usize;
+        reader.skip(4)?; // origChecksum
+
+        let compressed = reader
+            .data
+            .get(offset..offset + comp_length)
+            .ok_or("WOFF table data is out of bounds")?;
+
+        let table = match comp_length < orig_length {
+            true => {
+                let mut table = Vec::with_capacity(orig_length);
+                ZlibDecoder::new(compressed)
+                    .read_to_end(&mut table)
+                    .map_err(|e| e.to_string())?;
+                table
+            }
+            false => compressed.to_vec(),
+        };
+        if table.len() != orig_length {
+            return Err("WOFF table has an unexpected length".to_string());
+        }
+        tables.push((tag, table));
+    }
+
+    Ok((flavor, tables))
+}
+
+struct Woff2Entry {
+    tag: [u8; 4],
+    orig_length: usize,
+    length: usize,
+    transformed: bool,
+}
+
+/// Reads and reconstructs the tables of a WOFF2 font (after its signature)
+fn decode_woff2(reader: &mut Reader) -> Result<(u32, Tables), String> {
+    let flavor = reader.u32()?;
+    reader.skip(4)?; // length
+    let num_tables = reader.u16()?;
+    reader.skip(2 + 4)?; // reserved, totalSfntSize
+    let compressed_size = reader.u32()? as usize;
+    reader.skip(2 + 2 + 5 * 4)?;
+
+    if flavor.to_be_bytes() == *b"ttcf" {
+        return Err("Font collections are not supported".to_string());
+    }
+    if num_tables == 0 {
+        return Err("WOFF2 font has no tables".to_string());
+    }
+
+    let mut entries = Vec::with_capacity(num_tables.into());
+    for _ in 0..num_tables {
+        let flags = reader.u8()?;
+        let tag = match flags & 0x3f {
+            0x3f => reader.tag()?,
+            index => *KNOWN_TAGS[usize::from(index)],
+        };
+        let version = flags >> 6;
+        let orig_length = reader.base128()? as usize;
+
+        // Version 0 is the transformation for `glyf`/`loca`, but the null
+        // transformation for all other tables (and vice versa for version 3)
+        let transformed = match &tag {
+            b"glyf" | b"loca" => version != 3,
+            _ => version != 0,
+        };
+        let length = match transformed {
+            true => reader.base128()? as usize,
+            false => orig_length,
+        };
+        entries.push(Woff2Entry {
+            tag,
+            orig_length,
+            length,
+            transformed,
+        });
+    }
+
+    let compressed = reader
+        .data
+        .get(reader.pos..reader.pos + compressed_size)
+        .ok_or("WOFF2 compressed data is out of bounds")?;
+    let mut stream = Vec::new();
+    brotli_decompressor::Decompressor::new(compressed, 4096)
+        .read_to_end(&mut stream)
+        .map_err(|e| format!("Failed to decompress WOFF2 data: {e}"))?;
+
+    let mut offset = 0;
+    let mut raw = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let table = stream
+            .get(offset..offset + entry.length)
+            .ok_or("WOFF2 table data is out of bounds")?;
+        raw.push(table);
+        offset += entry.length;
+    }
+
+    let find = |tag: &[u8; 4]| entries.iter().position(|entry| &entry.tag == tag);
+
+    let mut tables: Tables = Vec::with_capacity(entries.len());
+    let mut glyf_loca = None;
+    let mut x_mins = Vec::new();
+    if let Some(glyf) = find(b"glyf")
+        && entries[glyf].transformed
+    {
+        if find(b"loca").is_none_or(|loca| !entries[loca].transformed) {
+            return Err("WOFF2 transformed `glyf` requires a transformed `loca`".to_string());
+        }
+        let (glyf, loca, mins) = reconstruct_glyf(raw[glyf])?;
+        x_mins = mins;
+        glyf_loca = Some((glyf, loca));
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        let table = match (&entry.tag, entry.transformed) {
+            (_, false) => raw[index].to_vec(),
+            (b"glyf", true) => glyf_loca.as_ref().unwrap().0.clone(),
+            (b"loca", true) => {
+                let Some((_, loca)) = glyf_loca.as_ref() else {
+                    return Err("WOFF2 transformed `loca` requires a transformed `glyf`".into());
+                };
+                if loca.len() != entry.orig_length {
+                    return Err("WOFF2 reconstructed `loca` has an unexpected length".into());
+                }
+                loca.clone()
+            }
+            (b"hmtx", true) => {
+                let table = |tag| find(tag).map(|i| raw[i]).ok_or("Missing table");
+                reconstruct_hmtx(raw[index], table(b"hhea")?, table(b"maxp")?, &x_mins)?
+            }
+            (tag, true) => {
+                return Err(format!(
+                    "Unsupported WOFF2 transformation of `{}`",
+                    String::from_utf8_lossy(tag)
+                ));
+            }
+        };
+        tables.push((entry.tag, table));
+    }
+
+    Ok((flavor, tables))
+}
+
+/// Reconstructs the `glyf` and `loca` tables from a transformed `glyf` table.
+/// Also returns the `xMin` of every glyph, needed to reconstruct `hmtx`.
+fn reconstruct_glyf(data: &[u8]) -> Result<GlyfLoca, String> {
+    let mut header = Reader::new(data);
+    header.skip(2)?; // reserved
+    let option_flags = header.u16()?;
+    let num_glyphs = usize::from(header.u16()?);
+    let index_format = header.u16()?;
+
+    let mut offset = header.pos + 7 * 4;
+    let mut next_stream = || -> Result<Reader, String> {
+        let size = header.u32()? as usize;
+        let stream = data
+            .get(offset..offset + size)
+            .ok_or("WOFF2 `glyf` stream is out of bounds")?;
+        offset += size;
+        Ok(Reader::new(stream))
+    };
+    let mut n_contours = next_stream()?;
+    let mut n_points = next_stream()?;
+    let mut flags = next_stream()?;
+    let mut glyphs = next_stream()?;
+    let mut composites = next_stream()?;
+    let mut bboxes = next_stream()?;
+    let mut instructions = next_stream()?;
+
+    let overlap_bitmap = match option_flags & 1 {
+        1 => data
+            .get(offset..offset + num_glyphs.div_ceil(8))
+            .ok_or("WOFF2 overlap bitmap is out of bounds")?,
+        _ => &[],
+    };
+    let bbox_bitmap = bboxes.bytes(num_glyphs.div_ceil(32) * 4)?;
+    let has_bit = |bitmap: &[u8], i: usize| {
+        bitmap
+            .get(i / 8)
+            .is_some_and(|b| b & (0x80 >> (i % 8)) != 0)
+    };
+
+    let mut glyf = Vec::new();
+    let mut offsets = Vec::with_capacity(num_glyphs + 1);
+    let mut x_mins = Vec::with_capacity(num_glyphs);
+
+    for i in 0..num_glyphs {
+        offsets.push(glyf.len());
+        let contours = n_contours.u16()? as i16;
+
+        match contours {
+            0 => x_mins.push(0),
+            -1 => {
+                if !has_bit(bbox_bitmap, i) {
+                    return Err("WOFF2 composite glyph is missing a bounding box".to_string());
+                }
+                let bbox = bboxes.bytes(8)?;
+                x_mins.push(i16::from_be_bytes([bbox[0], bbox[1]]));
+
+                let start = composites.pos;
+                let mut has_instructions = false;
+                loop {
+                    let component_flags = composites.u16()?;
+                    has_instructions |= component_flags & 0x0100 != 0;
+                    let args = match component_flags & 0x0001 {
+                        0 => 2,
+                        _ => 4,
+                    };
+                    let scale = match component_flags {
+                        f if f & 0x0008 != 0 => 2,
+                        f if f & 0x0040 != 0 => 4,
+                        f if f & 0x0080 != 0 => 8,
+                        _ => 0,
+                    };
+                    composites.skip(2 + args + scale)?;
+                    if component_flags & 0x0020 == 0 {
+                        break;
+                    }
+                }
+
+                glyf.extend_from_slice(&contours.to_be_bytes());
+                glyf.extend_from_slice(bbox);
+                glyf.extend_from_slice(&composites.data[start..composites.pos]);
+                if has_instructions {
+                    let length = glyphs.u255()?;
+                    glyf.extend_from_slice(&length.to_be_bytes());
+                    glyf.extend_from_slice(instructions.bytes(length.into())?);
+                }
+            }
+            contours if contours > 0 => {
+                let mut end_points = Vec::with_capacity(contours as usize);
+                let mut total_points = 0usize;
+                for _ in 0..contours {
+                    let contour_points = usize::from(n_points.u255()?);
+                    if contour_points == 0 {
+                        return Err("WOFF2 contour has no points".to_string());
+                    }
+                    total_points += contour_points;
+                    end_points.push(
+                        u16::try_from(total_points - 1)
+                            .map_err(|_| "WOFF2 glyph has too many points")?,
+                    );
+                }
+
+                let mut points = Vec::with_capacity(total_points);
+                let (mut x, mut y) = (0i32, 0i32);
+                for _ in 0..total_points {
+                    let flag = flags.u8()?;
+                    let (dx, dy) = decode_triplet(flag & 0x7f, &mut glyphs)?;
+                    x += dx;
+                    y += dy;
+                    points.push((x, y, flag & 0x80 == 0));
+                }
+
+                let instruction_length = glyphs.u255()?;
+
+                let bbox = match has_bit(bbox_bitmap, i) {
+                    true => bboxes.bytes(8)?.to_vec(),
+                    false => {
+                        let min_x = points.iter().map(|p| p.0).min().unwrap_or(0);
+                        let min_y = points.iter().map(|p| p.1).min().unwrap_or(0);
+                        let max_x = points.iter().map(|p| p.0).max().unwrap_or(0);
+                        let max_y = points.iter().map(|p| p.1).max().unwrap_or(0);
+                        [min_x, min_y, max_x, max_y]
+                            .iter()
+                            .flat_map(|v| (*v as i16).to_be_bytes())
+                            .collect()
+                    }
+                };
+                x_mins.push(i16::from_be_bytes([bbox[0], bbox[1]]));
+
+                glyf.extend_from_slice(&contours.to_be_bytes());
+                glyf.extend_from_slice(&bbox);
+                end_points
+                    .iter()
+                    .for_each(|p| glyf.extend_from_slice(&p.to_be_bytes()));
+                glyf.extend_from_slice(&instruction_length.to_be_bytes());
+                glyf.extend_from_slice(instructions.bytes(instruction_length.into())?);
+                encode_points(&mut glyf, &points, has_bit(overlap_bitmap, i));
+            }
+            _ => return Err("WOFF2 glyph has an invalid number of contours".to_string()),
+        }
+
+        glyf.resize(glyf.len().next_multiple_of(4), 0);
+    }
+    offsets.push(glyf.len());
+
+    let loca = match index_format {
+        0 => offsets
+            .iter()
+            .flat_map(|offset| ((offset / 2) as u16).to_be_bytes())
+            .collect(),
+        _ => offsets
+            .iter()
+            .flat_map(|offset| (*offset as u32).to_be_bytes())
+            .collect(),
+    };
+
+    Ok((glyf, loca, x_mins))
+}
+
+/// Decodes a point coordinate delta from the glyph stream
+fn decode_triplet(flag: u8, glyphs: &mut Reader) -> Result<(i32, i32), String> {
+    let with_sign = |flag: u8, value: i32| match flag & 1 {
+        0 => -value,
+        _ => value,
+    };
+    let flag_i = i32::from(flag);
+
+    Ok(match flag {
+        0..10 => {
+            let b0 = i32::from(glyphs.u8()?);
+            (0, with_sign(flag, ((flag_i & 14) << 7) + b0))
+        }
+        10..20 => {
+            let b0 = i32::from(glyphs.u8()?);
+            (with_sign(flag, (((flag_i - 10) & 14) << 7) + b0), 0)
+        }
+        20..84 => {
+            let b0 = flag_i - 20;
+            let b1 = i32::from(glyphs.u8()?);
+            (
+                with_sign(flag, 1 + (b0 & 0x30) + (b1 >> 4)),
+                with_sign(flag >> 1, 1 + ((b0 & 0x0c) << 2) + (b1 & 0x0f)),
+            )
+        }
+        84..120 => {
+            let b0 = flag_i - 84;
+            let b = glyphs.bytes(2)?;
+            (
+                with_sign(flag, 1 + ((b0 / 12) << 8) + i32::from(b[0])),
+                with_sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + i32::from(b[1])),
+            )
+        }
+        120..124 => {
+            let b = glyphs.bytes(3)?;
+            (
+                with_sign(flag, (i32::from(b[0]) << 4) + (i32::from(b[1]) >> 4)),
+                with_sign(flag >> 1, ((i32::from(b[1]) & 0x0f) << 8) + i32::from(b[2])),
+            )
+        }
+        _ => {
+            let b = glyphs.bytes(4)?;
+            (
+                with_sign(flag, (i32::from(b[0]) << 8) + i32::from(b[1])),
+                with_sign(flag >> 1, (i32::from(b[2]) << 8) + i32::from(b[3])),
+            )
+        }
+    })
+}
+
+/// Writes the flags and coordinates of a simple glyph in the `glyf` table format
+fn encode_points(glyf: &mut Vec<u8>, points: &[(i32, i32, bool)], overlap: bool) {
+    const ON_CURVE: u8 = 0x01;
+    const X_SHORT: u8 = 0x02;
+    const Y_SHORT: u8 = 0x04;
+    const X_SAME_OR_POSITIVE: u8 = 0x10;
+    const Y_SAME_OR_POSITIVE: u8 = 0x20;
+    const OVERLAP_SIMPLE: u8 = 0x40;
+
+    let mut flags = Vec::with_capacity(points.len());
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    let (mut last_x, mut last_y) = (0, 0);
+
+    for (x, y, on_curve) in points {
+        let mut flag = if *on_curve { ON_CURVE } else { 0 };
+        for (delta, coords, short, same) in [
+            (x - last_x, &mut xs, X_SHORT, X_SAME_OR_POSITIVE),
+            (y - last_y, &mut ys, Y_SHORT, Y_SAME_OR_POSITIVE),
+        ] {
+            match delta {
+                0 => flag |= same,
+                -255..=255 => {
+                    flag |= short;
+                    if delta > 0 {
+                        flag |= same;
+                    }
+                    coords.push(delta.unsigned_abs() as u8);
+                }
+                _ => coords.extend_from_slice(&(delta as i16).to_be_bytes()),
+            }
+        }
+        flags.push(flag);
+        (last_x, last_y) = (*x, *y);
+    }
+
+    if overlap && let Some(flag) = flags.first_mut() {
+        *flag |= OVERLAP_SIMPLE;
+    }
+
+    glyf.extend_from_slice(&flags);
+    glyf.extend_from_slice(&xs);
+    glyf.extend_from_slice(&ys);
+}
+
+/// Reconstructs the `hmtx` table from its transformed version
+fn reconstruct_hmtx(
+    data: &[u8],
+    hhea: &[u8],
+    maxp: &[u8],
+    x_mins: &[i16],
+) -> Result<Vec<u8>, String> {
+    let num_h_metrics =
+        usize::from(Reader::new(hhea.get(34..36).ok_or("Invalid `hhea` table")?).u16()?);
+    let num_glyphs = usize::from(Reader::new(maxp.get(4..6).ok_or("Invalid `maxp` table")?).u16()?);
+    if num_h_metrics > num_glyphs || x_mins.len() < num_glyphs {
+        return Err("WOFF2 `hmtx` does not match the number of glyphs".to_string());
+    }
+
+    let mut reader = Reader::new(data);
+    let flags = reader.u8()?;
+
+    let mut advances = Vec::with_capacity(num_h_metrics);
+    for _ in 0..num_h_metrics {
+        advances.push(reader.u16()?);
+    }
+
+    let mut lsbs = Vec::with_capacity(num_glyphs);
+    for (i, x_min) in x_mins.iter().take(num_glyphs).enumerate() {
+        let reconstruct = match i < num_h_metrics {
+            true => flags & 1 != 0,
+            false => flags & 2 != 0,
+        };
+        lsbs.push(match reconstruct {
+            true => *x_min,
+            false => reader.u16()? as i16,
+        });
+    }
+
+    let mut hmtx = Vec::with_capacity(num_h_metrics * 4 + (num_glyphs - num_h_metrics) * 2);
+    for (i, lsb) in lsbs.iter().enumerate() {
+        if let Some(advance) = advances.get(i) {
+            hmtx.extend_from_slice(&advance.to_be_bytes());
+        }
+        hmtx.extend_from_slice(&lsb.to_be_bytes());
+    }
+    Ok(hmtx)
+}
+
+/// Assembles the tables into an sfnt font file, computing the table
+/// checksums and the `head` table's `checkSumAdjustment`
+fn build_sfnt(flavor: u32, mut tables: Tables) -> Result<Vec<u8>, String> {
+    tables.sort_by_key(|table| table.0);
+
+    // The header stores `numTables * 16` within 16 bits
+    let too_many_tables = |_| "Font has too many tables".to_string();
+    let num_tables = u32::try_from(tables.len()).map_err(too_many_tables)?;
+    let entry_selector = num_tables.max(1).ilog2();
+    let search_range = (1 << entry_selector) * 16;
+    let range_shift = (num_tables * 16).saturating_sub(search_range);
+
+    let mut font = Vec::new();
+    font.extend_from_slice(&flavor.to_be_bytes());
+    for value in [num_tables, search_range, entry_selector, range_shift] {
+        let value = u16::try_from(value).map_err(too_many_tables)?;
+        font.extend_from_slice(&value.to_be_bytes());
+    }
+
+    let mut offset = 12 + tables.len() * 16;
+    let mut head_offset = None;
+    for (tag, table) in &mut tables {
+        if tag == b"head" && table.len() >= 12 {
+            table[8..12].fill(0);
+            head_offset = Some(offset);
+        }
+        font.extend_from_slice(tag);
+        font.extend_from_slice(&checksum(table).to_be_bytes());
+        font.extend_from_slice(&(offset as u32).to_be_bytes());
+        font.extend_from_slice(&(table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+
+    for (_, table) in &tables {
+        font.extend_from_slice(table);
+        font.resize(font.len().next_multiple_of(4), 0);
+    }
+
+    if let Some(head_offset) = head_offset {
+        let adjustment = 0xB1B0_AFBA_u32.wrapping_sub(checksum(&font));
+        font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    }
+
+    Ok(font)
+}
+
+/// Computes the sfnt checksum of the data (sum of big-endian `u32` values)
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Reads big-endian values from a byte slice
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + count)
+            .ok_or("Unexpected end of font data")?;
+        self.pos += count;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), String> {
+        self.bytes(count).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn tag(&mut self) -> Result<[u8; 4], String> {
+        let b = self.bytes(4)?;
+        Ok([b[0], b[1], b[2], b[3]])
+    }
+
+    /// Reads a WOFF2 `UIntBase128` value
+    fn base128(&mut self) -> Result<u32, String> {
+        let mut value: u32 = 0;
+        for i in 0..5 {
+            let byte = self.u8()?;
+            if i == 0 && byte == 0x80 {
+                return Err("Invalid WOFF2 `UIntBase128` value".to_string());
+            }
+            if value & 0xFE00_0000 != 0 {
+                return Err("WOFF2 `UIntBase128` value overflows".to_string());
+            }
+            value = (value << 7) | u32::from(byte & 0x7f);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("WOFF2 `UIntBase128` value is too long".to_string())
+    }
+
+    /// Reads a WOFF2 `255UInt16` value
+    fn u255(&mut self) -> Result<u16, String> {
+        Ok(match self.u8()? {
+            253 => self.u16()?,
+            254 => u16::from(self.u8()?) + 253 * 2,
+            255 => u16::from(self.u8()?) + 253,
+            code => u16::from(code),
+        })
+    }
+}
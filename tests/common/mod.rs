@@ -0,0 +1,30 @@
+use std::fs;
+
+/// An empty directory for a test, which is deleted once the test finishes
+pub struct TempDir {
+    path: String,
+}
+
+impl TempDir {
+    /// Creates the directory, named after the test and the process
+    pub fn new(test: &str) -> Self {
+        let path = std::env::temp_dir()
+            .join(format!("fin-test-{test}-{}", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    /// Returns the path of the directory, without a trailing '/'
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -22,7 +22,7 @@ mod test_action {
     #[test]
     fn help_includes_all_actions() {
         let all_actions = ensure_exhaustive!(
-            Action, Install, Reinstall, Update, Remove, List, Clean, Config, Version, Help
+            Action, Install, Reinstall, Update, Remove, Adopt, List, Info, Search, Verify, Owns, Sync, Export, Lock, Clean, Doctor, Config, Repo, Installer, Completions, Version, Help
         );
         let help_actions = Action::help_actions();
         print!("{help_actions}");
@@ -41,7 +41,7 @@ mod test_action {
     #[test]
     fn help_for_every_action() {
         let all_actions = ensure_exhaustive!(
-            Action, Install, Reinstall, Update, Remove, List, Clean, Config, Version, Help
+            Action, Install, Reinstall, Update, Remove, Adopt, List, Info, Search, Verify, Owns, Sync, Export, Lock, Clean, Doctor, Config, Repo, Installer, Completions, Version, Help
         );
 
         all_actions.iter().for_each(|action| {
@@ -53,7 +53,7 @@ mod test_action {
             if action == "help" {
                 return;
             }
-            if !help.contains("Usage") || !help.contains(&format!("{action}")) {
+            if !help.contains("Usage") || !help.contains(&action) {
                 panic!("The '{action}' action help message is missing a usage section",);
             }
             if !help.contains("Action") {
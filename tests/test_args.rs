@@ -0,0 +1,100 @@
+mod test_args {
+    #![cfg(test)]
+
+    use fin::action::Action;
+    use fin::args::Args;
+    use fin::config::Config;
+
+    fn parse(args: &[&str]) -> Result<(Args, Vec<String>), String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()), Config::default())
+    }
+
+    #[test]
+    fn action_and_items() {
+        let (args, items) = parse(&["-v", "install", "font-a", "font-b:latest"]).unwrap();
+        assert_eq!(args.action, Action::Install);
+        assert_eq!(items, ["font-a", "font-b:latest"]);
+        assert!(args.options.verbose);
+
+        let (args, items) = parse(&[]).unwrap();
+        assert_eq!(args.action, Action::Help);
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn option_values() {
+        let (args, _) = parse(&["list", "--install-dir=/tmp/a", "--cache-timeout", "5"]).unwrap();
+        assert_eq!(args.config.install_dir, "/tmp/a");
+        assert_eq!(args.config.cache_timeout, 5);
+
+        let (args, items) = parse(&["list", "--install-dir", "/tmp/b", "all"]).unwrap();
+        assert_eq!(args.config.install_dir, "/tmp/b");
+        assert_eq!(items, ["all"]);
+
+        assert!(parse(&["list", "--install-dir"]).is_err());
+        assert!(parse(&["list", "--cache-timeout=soon"]).is_err());
+        assert!(parse(&["list", "--verbose=yes"]).is_err());
+    }
+
+    #[test]
+    fn short_arguments() {
+        let (args, _) = parse(&["update", "-iry"]).unwrap();
+        assert!(args.options.reinstall);
+        assert!(args.options.refresh);
+        assert_eq!(args.options.answer, Some(true));
+
+        assert!(parse(&["update", "-x"]).is_err());
+        assert!(parse(&["update", "-i-"]).is_err());
+    }
+
+    #[test]
+    fn end_of_arguments() {
+        let (args, items) = parse(&["remove", "-y", "--", "-font-", "--yes"]).unwrap();
+        assert_eq!(args.action, Action::Remove);
+        assert_eq!(items, ["-font-", "--yes"]);
+
+        let (args, items) = parse(&["-n", "install", "--", "-font-"]).unwrap();
+        assert_eq!(args.action, Action::Install);
+        assert_eq!(items, ["-font-"]);
+        assert_eq!(args.options.answer, Some(false));
+
+        let (args, items) = parse(&["install", "--", "remove"]).unwrap();
+        assert_eq!(args.action, Action::Install);
+        assert_eq!(items, ["remove"]);
+
+        let error = parse(&["-n", "--", "install", "-font-"]).err().unwrap();
+        assert_eq!(error, "Missing action before `--`");
+        assert!(parse(&["--", "install"]).is_err());
+        assert_eq!(parse(&["--"]).unwrap().0.action, Action::Help);
+    }
+
+    #[test]
+    fn per_action_arguments() {
+        assert!(parse(&["verify", "--repair"]).is_ok());
+        assert!(parse(&["install", "--repair"]).is_err());
+        assert!(parse(&["install", "-i"]).is_ok());
+        assert!(parse(&["remove", "--reinstall"]).is_err());
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(parse(&[""]).is_err());
+        assert!(parse(&["install", ""]).is_err());
+        assert!(parse(&["install", "--", ""]).is_err());
+
+        let error = parse(&["install", "--forse"]).err().unwrap();
+        assert!(error.contains("`--force`"), "{error}");
+        let error = parse(&["instal", "font"]).err().unwrap();
+        assert!(error.contains("`install`"), "{error}");
+        let error = parse(&["install", "--something-else"]).err().unwrap();
+        assert!(!error.contains("did you mean"), "{error}");
+    }
+
+    #[test]
+    fn json_output() {
+        assert!(parse(&["list", "--json"]).unwrap().0.options.json);
+        assert!(parse(&["remove", "font", "--json", "--yes"]).is_ok());
+        assert!(parse(&["remove", "font", "--json"]).is_err());
+        assert!(parse(&["verify", "--json"]).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+mod test_checksum {
+    #![cfg(test)]
+
+    use fin::checksum::{Checksum, ExpectedSum, find_expected_sum};
+
+    const MANIFEST: &str = "\
+aaaa  Font-Regular.zip
+bbbb *dist/Font-Bold.zip
+SHA256 (Font-Italic.zip) = cccc
+SHA512 (Font-Italic.zip) = dddd
+";
+
+    #[test]
+    fn bare_sum() {
+        assert_eq!(
+            find_expected_sum(" ABCD\n", "any.zip", "SHA256").unwrap(),
+            "ABCD"
+        );
+    }
+
+    #[test]
+    fn coreutils_manifest() {
+        let sum = |file| find_expected_sum(MANIFEST, file, "SHA256");
+        assert_eq!(sum("Font-Regular.zip").unwrap(), "aaaa");
+        assert_eq!(sum("Font-Bold.zip").unwrap(), "bbbb");
+        assert!(sum("Regular.zip").is_err());
+        assert!(sum("Font.zip").is_err());
+    }
+
+    #[test]
+    fn bsd_manifest() {
+        assert_eq!(
+            find_expected_sum(MANIFEST, "Font-Italic.zip", "SHA256").unwrap(),
+            "cccc"
+        );
+        assert_eq!(
+            find_expected_sum(MANIFEST, "Font-Italic.zip", "SHA512").unwrap(),
+            "dddd"
+        );
+        assert!(find_expected_sum(MANIFEST, "Font-Italic.zip", "SHA384").is_err());
+    }
+
+    #[test]
+    fn conflicting_entries() {
+        let manifest = "aaaa  Font.zip\nbbbb  other/Font.zip\n";
+        assert!(find_expected_sum(manifest, "Font.zip", "SHA256").is_err());
+    }
+
+    #[test]
+    fn page_without_entry() {
+        let page = "<html>Checksum: aaaa (for Font.zip)</html>";
+        assert!(find_expected_sum(page, "Font.zip", "SHA256").is_err());
+    }
+
+    #[test]
+    fn github_release_digest() {
+        let release = r#"{
+            "tag_name": "v1.0",
+            "assets": [
+                { "name": "Font.zip", "digest": "sha256:aaaa" },
+                { "name": "Other.zip", "digest": null }
+            ]
+        }"#;
+        assert_eq!(
+            find_expected_sum(release, "Font.zip", "SHA256").unwrap(),
+            "aaaa"
+        );
+        assert!(find_expected_sum(release, "Font.zip", "SHA512").is_err());
+        assert!(find_expected_sum(release, "Other.zip", "SHA256").is_err());
+        assert!(find_expected_sum(release, "Missing.zip", "SHA256").is_err());
+    }
+
+    fn expecting(sum: &str) -> ExpectedSum {
+        ExpectedSum {
+            contents: Some(sum.to_owned()),
+            ..ExpectedSum::default()
+        }
+    }
+
+    #[test]
+    fn algorithms() {
+        let data = b"fin";
+        let url = "https://example.com/fin.txt";
+        let mut checksums = [
+            Checksum::MD5(expecting("d79695776a5b40f7cadbee1f91a85c82")),
+            Checksum::SHA1(expecting("b76049e61518de9781f18a52d2d89b5f4ac6ae9f")),
+            Checksum::BLAKE2b(expecting(
+                "80fa9f1e1fcabb57731b1e00aa12945f49918ff4bd77c851565b07a9d1716e65\
+                 67bfe9ab7595fbcf3dd361cf5f7999b102c7052ee8823b26177f0fc3910a73d9",
+            )),
+            Checksum::BLAKE2s(expecting(
+                "8c04b0e05bf5606b7d296b6cdd1961dc948b69dcf7cb40b7251d731a12070a83",
+            )),
+        ];
+        for checksum in &mut checksums {
+            assert!(checksum.check(data, 3.0, url).is_ok(), "{checksum:?}");
+        }
+
+        let mut blake3 = Checksum::BLAKE3(expecting(
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+        ));
+        assert!(blake3.check(&[], 0.0, url).is_ok());
+
+        let mut mismatch = Checksum::SHA256(expecting(&"0".repeat(64)));
+        assert!(mismatch.check(data, 3.0, url).is_err());
+    }
+
+    #[test]
+    fn weak_algorithms() {
+        assert!(Checksum::MD5(ExpectedSum::default()).is_weak());
+        assert!(Checksum::SHA1(ExpectedSum::default()).is_weak());
+        assert!(!Checksum::SHA256(ExpectedSum::default()).is_weak());
+        assert!(!Checksum::BLAKE3(ExpectedSum::default()).is_weak());
+    }
+
+    #[test]
+    fn optional_github_digest() {
+        let check = |release: &str| {
+            let mut checksum = Checksum::github_digest();
+            if let Checksum::SHA256(expected) = &mut checksum {
+                expected.contents = Some(release.to_owned());
+            }
+            checksum.check(b"fin", 3.0, "https://example.com/fin.txt")
+        };
+        assert!(check(r#"{ "assets": [{ "name": "fin.txt", "digest": null }] }"#).is_ok());
+        assert!(check(r#"{ "assets": [{ "name": "fin.txt" }] }"#).is_ok());
+
+        // Only files listed without a digest skip verification
+        assert!(check(r#"{ "assets": [{ "name": "other.txt", "digest": null }] }"#).is_err());
+        assert!(check(r#"{ "assets": [{ "name": "fin.txt", "digest": "sha512:aa" }] }"#).is_err());
+        assert!(check(r#"{ "assets": [{ "name": "fin.txt", "digest": "sha256:aa" }] }"#).is_err());
+    }
+
+    #[test]
+    fn descriptions() {
+        let file = Checksum::SHA256(ExpectedSum {
+            file: Some("SHA256SUMS".to_owned()),
+            ..ExpectedSum::default()
+        });
+        assert_eq!(file.describe(), "SHA256 (SHA256SUMS)");
+
+        let sum = Checksum::MD5(ExpectedSum {
+            sum: Some("aaaa".to_owned()),
+            ..ExpectedSum::default()
+        });
+        assert_eq!(sum.describe(), "MD5 (sum specified by the installer)");
+
+        assert!(Checksum::github_digest().describe().contains("digest"));
+        assert!(
+            Checksum::BLAKE3(ExpectedSum::default())
+                .describe()
+                .contains("font page")
+        );
+    }
+}
@@ -0,0 +1,105 @@
+mod test_completions {
+    #![cfg(test)]
+
+    use fin::action::Action;
+    use fin::actions::completions::CompletionsAction;
+    use fin::args::Args;
+    use fin::config::Config;
+    use fin::options::Options;
+
+    fn scripts() -> [(&'static str, String); 3] {
+        [
+            ("bash", CompletionsAction::bash()),
+            ("zsh", CompletionsAction::zsh()),
+            ("fish", CompletionsAction::fish()),
+        ]
+    }
+
+    #[test]
+    fn scripts_include_all_actions() {
+        for (shell, script) in scripts() {
+            for action in &Action::ALL {
+                let name = action.aliases()[0];
+                assert!(script.contains(name), "{shell}: Missing action: {name}");
+                for item in action.items() {
+                    assert!(
+                        script.contains(item),
+                        "{shell}: Missing item of {name}: {item}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scripts_include_all_arguments() {
+        for (shell, script) in scripts() {
+            for argument in Options::ARGUMENTS {
+                assert!(
+                    script.contains(&argument.long[2..]),
+                    "{shell}: Missing argument: {}",
+                    argument.long
+                );
+                if let Some(short) = argument.short {
+                    assert!(
+                        script.contains(&format!("-{short}"))
+                            || script.contains(&format!("-s {short}")),
+                        "{shell}: Missing argument: -{short}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scripts_complete_font_names() {
+        for (shell, script) in scripts() {
+            for item in ["installers", "installed", "tags"] {
+                assert!(
+                    script.contains(&format!("fin completions {item}")),
+                    "{shell}: Missing completion of {item}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn all_arguments_are_accepted() {
+        for argument in Options::ARGUMENTS {
+            let action = argument.actions.first().unwrap_or(&Action::Install);
+            let value = match argument.value {
+                Some("time") => Some("10".to_string()),
+                Some(_) => Some("/tmp".to_string()),
+                None => None,
+            };
+
+            let mut names = vec![argument.long.to_string()];
+            names.extend(argument.short.map(|short| format!("-{short}")));
+            for name in names {
+                // Some arguments (such as `--json`) require prompts to be answered
+                let args = [action.aliases()[0].to_string(), name.clone()]
+                    .into_iter()
+                    .chain(value.clone())
+                    .chain(["--yes".to_string()]);
+                assert!(
+                    Args::parse(args, Config::default()).is_ok(),
+                    "Not accepted: {name}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn all_aliases_are_parsed() {
+        for action in &Action::ALL {
+            for alias in action.aliases() {
+                let parsed = Action::parse(Some(&alias.to_string())).unwrap();
+                assert_eq!(
+                    std::mem::discriminant(&parsed),
+                    std::mem::discriminant(action),
+                    "Alias parsed as the wrong action: {alias}"
+                );
+            }
+        }
+    }
+}
@@ -42,4 +42,19 @@ mod test_config {
             panic!("The default config in `default_config!()` macro has missing fields.");
         }
     }
+
+    #[test]
+    fn problems() {
+        assert!(Config::problems(default_config!()).is_empty());
+        assert_eq!(
+            Config::problems("install_dirr = \"~/fonts\""),
+            ["Unknown field: `install_dirr` (did you mean `install_dir`?)"]
+        );
+        assert_eq!(
+            Config::problems("install_dir = \"/\""),
+            ["The installation directory is invalid: \"/\""]
+        );
+        assert_eq!(Config::problems("cache_timeout = \"90\"").len(), 1);
+        assert_eq!(Config::problems("install_dir =").len(), 1);
+    }
 }
@@ -0,0 +1,98 @@
+mod test_dependencies {
+    #![cfg(test)]
+
+    use fin::dependencies::{self, dependency_names, expand_dependencies};
+
+    /// Dependencies of the installers used by the tests below
+    fn read_dependencies(name: &str) -> Option<(Vec<String>, bool)> {
+        let (depends, is_bundle): (&[&str], bool) = match name {
+            "workstation" => (&["jetbrains-mono", "inter@static", "symbols"], true),
+            "symbols" => (&["nerd-fonts-symbols-only"], true),
+            "jetbrains-mono-nf" => (&["nerd-fonts-symbols-only"], false),
+            "cycle-a" => (&["cycle-b"], true),
+            "cycle-b" => (&["cycle-a"], true),
+            "broken" => (&["missing"], false),
+            "pinned" => (&["x:1.0", "inter@static:v4.0"], false),
+            "missing" => return None,
+            _ => (&[], false),
+        };
+        Some((depends.iter().map(ToString::to_string).collect(), is_bundle))
+    }
+
+    fn expand(fonts: &[&str]) -> Result<Vec<(String, bool)>, String> {
+        let fonts = fonts.iter().map(ToString::to_string).collect::<Vec<_>>();
+        expand_dependencies(&fonts, &read_dependencies)
+    }
+
+    fn font(name: &str, dependency: bool) -> (String, bool) {
+        (name.to_owned(), dependency)
+    }
+
+    #[test]
+    fn bundles_are_expanded() {
+        assert_eq!(
+            expand(&["workstation"]).unwrap(),
+            [
+                font("jetbrains-mono", true),
+                font("inter@static", true),
+                font("nerd-fonts-symbols-only", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn dependencies_are_installed_first() {
+        assert_eq!(
+            expand(&["jetbrains-mono-nf", "nerd-fonts-symbols-only:v3.0"]).unwrap(),
+            [
+                font("nerd-fonts-symbols-only:v3.0", false),
+                font("jetbrains-mono-nf", false),
+            ]
+        );
+        assert_eq!(
+            expand(&["inter", "workstation"]).unwrap(),
+            [
+                font("inter", false),
+                font("jetbrains-mono", true),
+                font("nerd-fonts-symbols-only", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_dependencies() {
+        let e = expand(&["cycle-a"]).unwrap_err();
+        assert_eq!(e, "Dependency cycle: cycle-a -> cycle-b -> cycle-a");
+
+        let e = expand(&["broken"]).unwrap_err();
+        assert_eq!(e, "broken: Dependency not found: missing");
+
+        // Missing installers are reported when parsing them instead
+        assert_eq!(expand(&["missing"]).unwrap(), [font("missing", false)]);
+    }
+
+    #[test]
+    fn pinned_dependencies() {
+        let expanded = expand(&["pinned"]).unwrap();
+        assert_eq!(
+            expanded,
+            [
+                font("x:1.0", true),
+                font("inter@static:v4.0", true),
+                font("pinned", false),
+            ]
+        );
+        // Compared with the names of the parsed fonts, without variant or tag
+        assert_eq!(
+            dependency_names(&expanded),
+            ["inter", "x"].map(String::from).into()
+        );
+    }
+
+    #[test]
+    fn base_names() {
+        assert_eq!(dependencies::base_name("maple-mono@nf:v7.0"), "maple-mono");
+        assert_eq!(dependencies::base_name("maple-mono:v7.0"), "maple-mono");
+        assert_eq!(dependencies::base_name("maple-mono"), "maple-mono");
+    }
+}
@@ -0,0 +1,93 @@
+mod test_extract_rules {
+    #![cfg(test)]
+
+    use fin::extract_rules::*;
+
+    fn paths(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn strip_components() {
+        let rules = ExtractRules {
+            keep_folders: true,
+            strip_components: 2,
+            ..ExtractRules::default()
+        };
+        assert_eq!(
+            rules.destination("release/v3.1/ttf/Font.ttf"),
+            Some("ttf/Font.ttf".to_string())
+        );
+        assert_eq!(
+            rules.destination("./release/v3.1/Font.ttf"),
+            Some("Font.ttf".to_string())
+        );
+        assert_eq!(rules.destination("release/README.md"), None);
+
+        let rules = ExtractRules {
+            keep_folders: false,
+            strip_components: 1,
+            ..ExtractRules::default()
+        };
+        assert_eq!(
+            rules.destination("release/ttf/Font.ttf"),
+            Some("Font.ttf".to_string())
+        );
+    }
+
+    #[test]
+    fn rename() {
+        let rules = ExtractRules {
+            rename: [
+                ("*/LICENSE.txt".to_string(), "LICENSE-1.0.txt".to_string()),
+                ("*".to_string(), "ignored".to_string()),
+            ]
+            .into(),
+            ..ExtractRules::default()
+        };
+        assert_eq!(
+            rules.destination("release/LICENSE.txt"),
+            Some("LICENSE-1.0.txt".to_string())
+        );
+        assert_eq!(rules.destination("Font.ttf"), Some("ignored".to_string()));
+    }
+
+    #[test]
+    fn conflicts_fail_by_default() {
+        let rules = ExtractRules::default();
+        let error = rules
+            .resolve(&paths(&["b/Font.ttf", "a/Font.ttf", "a/Other.ttf"]))
+            .unwrap_err();
+        assert!(error.contains("a/Font.ttf"));
+        assert!(error.contains("b/Font.ttf"));
+        assert!(!error.contains("Other.ttf"));
+    }
+
+    #[test]
+    fn conflicts_suffixed() {
+        let rules = ExtractRules {
+            on_conflict: OnConflict::Suffix,
+            ..ExtractRules::default()
+        };
+        assert_eq!(
+            rules
+                .resolve(&paths(&[
+                    "c/Font.ttf",
+                    "a/Font.ttf",
+                    "Font-1.ttf",
+                    "b/Font.ttf",
+                    "LICENSE",
+                    "x/LICENSE"
+                ]))
+                .unwrap(),
+            [
+                Some("Font-3.ttf".to_string()),
+                Some("Font.ttf".to_string()),
+                Some("Font-1.ttf".to_string()),
+                Some("Font-2.ttf".to_string()),
+                Some("LICENSE".to_string()),
+                Some("LICENSE-1".to_string()),
+            ]
+        );
+    }
+}
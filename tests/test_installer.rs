@@ -1,42 +1,352 @@
+mod common;
+
 mod test_installer {
+    use crate::common::TempDir;
+    use fin::actions::install::install_status;
+    use fin::asset::check_duplicate_files;
+    use fin::checksum::{Checksum, ExpectedSum};
     use fin::config::Config;
     use fin::font_page::FontPage;
+    use fin::installed::InstalledFonts;
     use fin::installer::Installer;
+    use fin::lint;
     use fin::options::Options;
-    use fin::paths::page_cache_dir;
     use std::collections::HashMap;
     use std::fs;
     use std::sync::{Arc, Mutex};
 
-    #[test]
-    fn valid_default_installers() {
-        let installers_dir = env!("CARGO_MANIFEST_DIR").to_owned() + "/installers/";
-
-        let cached_pages = Arc::new(Mutex::new(HashMap::<String, FontPage>::new()));
-        let _ = fs::create_dir_all(page_cache_dir()).inspect_err(|_| {
-            panic!("Failed to create directory: {}", page_cache_dir());
-        });
-
-        let args = Arc::new(fin::args::Args {
+    fn test_args() -> Arc<fin::args::Args> {
+        Arc::new(fin::args::Args {
             action: fin::action::Action::Install,
             config: Config {
                 cache_timeout: u64::MAX,
                 ..Config::default()
             },
             options: Options::default(),
-        });
+        })
+    }
+
+    /// Writes the installer into a temporary directory and parses it
+    fn parse_installer(name: &str, contents: &str) -> Result<Installer, String> {
+        parse_variant(name, None, contents)
+    }
+
+    fn parse_variant(
+        name: &str,
+        variant: Option<&str>,
+        contents: &str,
+    ) -> Result<Installer, String> {
+        let temp_dir = TempDir::new(&format!("installer-{name}"));
+        let installers_dir = format!("{}/", temp_dir.path());
+        fs::write([&installers_dir, name].concat(), contents).unwrap();
+
+        Installer::parse(
+            &test_args(),
+            &installers_dir,
+            name,
+            variant,
+            None,
+            Arc::new(Mutex::new(HashMap::<String, FontPage>::new())),
+        )
+    }
+
+    #[test]
+    fn valid_default_installers() {
+        let installers_dir = env!("CARGO_MANIFEST_DIR").to_owned() + "/installers/";
 
         let read_dir = fs::read_dir(&installers_dir).unwrap();
         for file in read_dir {
-            if let Err(e) = Installer::parse(
-                &args,
-                &installers_dir,
-                &file.unwrap().file_name().into_string().unwrap(),
-                None,
-                Arc::clone(&cached_pages),
-            ) {
-                panic!("{e}");
+            let path = file.unwrap().path();
+            if !path.is_file() {
+                continue;
             }
+            let name = path.file_name().unwrap().to_str().unwrap();
+            let diagnostics = lint::lint(&installers_dir, name, &fs::read_to_string(&path).unwrap());
+            assert!(diagnostics.is_empty(), "{name}: {diagnostics:?}");
         }
     }
+
+    #[test]
+    fn multiple_actions() {
+        let installer = parse_installer(
+            "multiple-actions",
+            r#"
+name = "Multiple Actions"
+
+[source.Direct]
+tag = "1.0"
+url = "https://example.com/$file"
+
+[[action]]
+[action.Extract]
+file = "font-$tag.zip"
+include = [ "*.ttf" ]
+
+[[action]]
+[action.SingleFile]
+file = "LICENSE.txt"
+[action.check.SHA256]
+url = "https://example.com/$file.sha256"
+
+[check.SHA512]
+sum = "0123456789abcdef-$tag"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(installer.action.len(), 2);
+        assert_eq!(installer.action[0].url, "https://example.com/font-1.0.zip");
+        assert_eq!(installer.action[1].url, "https://example.com/LICENSE.txt");
+        assert_eq!(
+            installer.source.ref_direct_url().unwrap(),
+            installer.action[0].url
+        );
+        assert_eq!(installer.extra_urls(), ["https://example.com/LICENSE.txt"]);
+        assert!(matches!(
+            &installer.action[0].check,
+            Some(Checksum::SHA512(ExpectedSum { sum: Some(sum), .. }))
+                if sum == "0123456789abcdef-1.0"
+        ));
+        assert!(matches!(
+            &installer.action[1].check,
+            Some(Checksum::SHA256(ExpectedSum { url: Some(url), .. }))
+                if url == "https://example.com/$file.sha256"
+        ));
+    }
+
+    #[test]
+    fn duplicate_files_of_actions() {
+        let files = |files: &[&str]| files.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        let zip = "https://example.com/font.zip";
+        let license = "https://example.com/LICENSE.txt";
+
+        let staged = [
+            (zip, files(&["Font.ttf", "LICENSE.txt"])),
+            (license, files(&["LICENSE.txt"])),
+        ];
+        let error = check_duplicate_files(&staged).unwrap_err();
+        assert!(error.contains("LICENSE.txt\n    ↪ https://example.com/font.zip"));
+        assert!(error.contains(license));
+        assert!(!error.contains("Font.ttf"));
+
+        let staged = [
+            (zip, files(&["Font.ttf"])),
+            (license, files(&["LICENSE.txt"])),
+        ];
+        assert!(check_duplicate_files(&staged).is_ok());
+    }
+
+    #[test]
+    fn direct_link_checksums() {
+        let installer = |check: &str| {
+            parse_installer(
+                "direct-link-checksums",
+                &format!(
+                    r#"
+name = "Direct Link Checksums"
+
+[source.Direct]
+url = "https://example.com/$file"
+
+[action.SingleFile]
+file = "font.ttf"
+
+[check.SHA256]
+{check}
+"#
+                ),
+            )
+        };
+
+        assert!(installer(r#"sum = "0123456789abcdef""#).is_ok());
+        assert!(installer(r#"url = "https://example.com/SHA256SUMS""#).is_ok());
+        assert!(installer(r#"file = "SHA256SUMS""#).is_err());
+        assert!(installer("").is_err());
+        assert!(installer("sum = \"0123\"\nurl = \"https://example.com/SHA256SUMS\"").is_err());
+        assert!(installer(r#"sum = "$tag""#).is_err());
+    }
+
+    #[test]
+    fn single_action() {
+        let installer = parse_installer(
+            "single-action",
+            r#"
+name = "Single Action"
+
+[source.Direct]
+url = "https://example.com/$file"
+
+[action.SingleFile]
+file = "font.ttf"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(installer.action.len(), 1);
+        assert_eq!(installer.action[0].url, "https://example.com/font.ttf");
+        assert!(installer.extra_urls().is_empty());
+    }
+
+    #[test]
+    fn no_actions() {
+        assert!(
+            parse_installer(
+                "no-actions",
+                r#"
+name = "No Actions"
+action = []
+
+[source.Direct]
+url = "https://example.com/$file"
+"#,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let installer = |top: &str, source: &str, action: &str| {
+            format!(
+                "name = \"Font\"\n{top}\n[source.Direct]\nurl = \"https://example.com/$file\"\n{source}\n[action.SingleFile]\nfile = \"font.ttf\"\n{action}"
+            )
+        };
+        assert!(Installer::from_str(&installer("", "", ""), "font", None, None).is_ok());
+
+        // Misspelled fields are rejected instead of being ignored
+        for contents in [
+            installer("descripton = \"A font\"", "", ""),
+            installer("", "author = \"author\"", ""),
+            installer("", "", "convert_webfonts = true"),
+            installer("", "", "[check.SHA256]\nsums = \"aaaa\""),
+            installer("", "", "[check.PGP]\nkey = \"aaaa\""),
+        ] {
+            let Err(error) = Installer::from_str(&contents, "font", None, None) else {
+                panic!("{contents} was accepted");
+            };
+            assert!(
+                error.contains("unknown field") || error.contains("unexpected keys"),
+                "{error}"
+            );
+        }
+    }
+
+    #[test]
+    fn variants() {
+        let contents = r#"
+name = "Variants"
+
+[source.Direct]
+url = "https://example.com/$file"
+
+[action.Extract]
+file = "Font-Variable.zip"
+include = [ "*.ttf" ]
+
+[check.SHA256]
+sum = "0123456789abcdef"
+
+[variants.static]
+file = "Font-Static.zip"
+include = [ "static/*.ttf" ]
+check.SHA512.sum = "fedcba9876543210"
+"#;
+        let installer = parse_variant("variants", None, contents).unwrap();
+        assert_eq!(installer.action[0].url, "https://example.com/Font-Variable.zip");
+        assert_eq!(installer.variant, None);
+
+        let installer = parse_variant("variants", Some("static"), contents).unwrap();
+        assert_eq!(installer.action[0].url, "https://example.com/Font-Static.zip");
+        assert_eq!(installer.variant.as_deref(), Some("static"));
+        assert!(matches!(
+            &installer.action[0].check,
+            Some(Checksum::SHA512(ExpectedSum { sum: Some(sum), .. }))
+                if sum == "fedcba9876543210"
+        ));
+
+        let e = parse_variant("variants", Some("nf"), contents).err().unwrap();
+        assert!(e.contains("Unknown variant: `nf`"), "{e}");
+    }
+
+    #[test]
+    fn variants_of_multiple_actions() {
+        let installer = |variant: &str| {
+            format!(
+                r#"
+name = "Variants Of Multiple Actions"
+
+[source.Direct]
+url = "https://example.com/$file"
+
+[[action]]
+[action.Extract]
+file = "Font.zip"
+include = [ "*.ttf" ]
+
+[[action]]
+[action.SingleFile]
+file = "OFL.txt"
+
+{variant}
+"#
+            )
+        };
+
+        let contents = installer(
+            r#"
+[[variants.nf.action]]
+file = "Font-NF.zip"
+include = [ "nf/*.ttf" ]
+
+[[variants.nf.action]]
+file = "OFL-NF.txt"
+"#,
+        );
+        let installer_nf = parse_variant("multiple-variants", Some("nf"), &contents).unwrap();
+        assert_eq!(installer_nf.action[0].url, "https://example.com/Font-NF.zip");
+        assert_eq!(installer_nf.action[1].url, "https://example.com/OFL-NF.txt");
+
+        // Fields for every action would also replace the license
+        let contents = installer("[variants.nf]\nfile = \"Font-NF.zip\"");
+        let e = parse_variant("multiple-variants", Some("nf"), &contents)
+            .err()
+            .unwrap();
+        assert!(e.contains("[[variants.nf.action]]"), "{e}");
+
+        let contents = installer("[[variants.nf.action]]\nfile = \"Font-NF.zip\"");
+        let e = parse_variant("multiple-variants", Some("nf"), &contents)
+            .err()
+            .unwrap();
+        assert!(e.contains("overrides 1 action(s), but the installer has 2"), "{e}");
+    }
+
+    #[test]
+    fn failed_installs() {
+        let mut installer = parse_installer(
+            "failed-installs",
+            r#"
+name = "Failed Installs"
+
+[source.Direct]
+url = "https://example.com/$file"
+
+[action.SingleFile]
+file = "font.ttf"
+"#,
+        )
+        .unwrap();
+
+        // The file was never staged, so it cannot be moved
+        let install_dir = TempDir::new("failed-installs");
+        installer.install_dir = Some(install_dir.path().to_owned());
+        installer.files = vec!["font.ttf".to_string()];
+
+        let args = test_args();
+        let installed_fonts = Arc::new(Mutex::new(InstalledFonts::default()));
+        let result = installer.finalize_install(&args, &installed_fonts, &[]);
+
+        assert!(result.is_err());
+        assert_eq!(install_status(&args, &result), "failed");
+        assert!(installed_fonts.lock().unwrap().installed.is_empty());
+    }
 }
@@ -0,0 +1,41 @@
+mod test_json {
+    #![cfg(test)]
+
+    use fin::json::{FontRecord, OutcomeRecord};
+
+    #[test]
+    fn outcome_records() {
+        let records = [
+            OutcomeRecord {
+                name: "inter".to_string(),
+                version: Some("v4.0".to_string()),
+                status: "planned",
+                error: None,
+            },
+            OutcomeRecord {
+                name: "fira-code".to_string(),
+                version: None,
+                status: "failed",
+                error: Some("Checksum mismatch".to_string()),
+            },
+        ];
+        assert_eq!(
+            serde_json::to_string(&records).unwrap(),
+            r#"[{"name":"inter","version":"v4.0","status":"planned","error":null},{"name":"fira-code","version":null,"status":"failed","error":"Checksum mismatch"}]"#
+        );
+    }
+
+    #[test]
+    fn font_records() {
+        let record = FontRecord {
+            name: "inter".to_string(),
+            dir: Some("~/.local/share/fonts/Inter".to_string()),
+            version: Some("v4.0".to_string()),
+            status: "installed",
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"name":"inter","dir":"~/.local/share/fonts/Inter","version":"v4.0","status":"installed"}"#
+        );
+    }
+}
@@ -0,0 +1,325 @@
+mod test_lint {
+    #![cfg(test)]
+
+    use fin::checksum::Checksum;
+    use fin::file_action::FileAction;
+    use fin::installer::Installer;
+    use fin::lint::{self, Diagnostic, Severity};
+    use fin::source::Source;
+    use serde::de::DeserializeOwned;
+
+    fn lint(contents: &str) -> Vec<Diagnostic> {
+        lint::lint("/nonexistent/", "test-font", contents)
+    }
+
+    /// Returns the line and message of the only diagnostic
+    fn single(contents: &str) -> (usize, String) {
+        let diagnostics = lint(contents);
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        (diagnostics[0].line, diagnostics[0].message.clone())
+    }
+
+    #[test]
+    fn valid_installer() {
+        let diagnostics = lint(
+            r#"
+name = "Test Font"
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[[action]]
+[action.Extract]
+file = "Font-*.zip"
+include = [ "*.ttf", "*OFL.txt" ]
+[action.check.SHA256]
+file = "Font-*.zip.sha256"
+"#,
+        );
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let (line, message) = single(
+            r#"name = "Test Font"
+descripton = "A font"
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[action.SingleFile]
+file = "Font.ttf"
+"#,
+        );
+        assert_eq!(line, 2);
+        assert!(message.contains("did you mean `description`?"), "{message}");
+
+        let (line, message) = single(
+            r#"name = "Test Font"
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[action.Extract]
+file = "Font.zip"
+include = [ "*.ttf" ]
+exclud = [ "*.otf" ]
+"#,
+        );
+        assert_eq!(line, 10);
+        assert!(message.contains("`exclud`"), "{message}");
+    }
+
+    #[test]
+    fn unknown_variants() {
+        let (line, message) = single(
+            r#"name = "Test Font"
+
+[source.Github]
+author = "author"
+project = "font"
+
+[action.SingleFile]
+file = "Font.ttf"
+"#,
+        );
+        assert_eq!(line, 3);
+        assert!(message.contains("Unknown source"), "{message}");
+
+        let (line, message) = single(
+            r#"name = "Test Font"
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[action.SingleFile]
+file = "Font.ttf"
+
+[check.SHA265]
+sum = "0123"
+"#,
+        );
+        assert_eq!(line, 10);
+        assert!(message.contains("Unknown check"), "{message}");
+    }
+
+    #[test]
+    fn validation_errors() {
+        let (line, message) = single(
+            r#"name = "Test Font"
+
+[source.Webpage]
+url = "https://example.com/fonts"
+
+[action.SingleFile]
+file = "Font-$tag.ttf"
+"#,
+        );
+        assert_eq!(line, 7);
+        assert!(message.contains("$tag"), "{message}");
+
+        let (line, message) = single(
+            r#"name = "Test Font"
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[action.Extract]
+file = "Font.rar"
+include = [ "*.ttf" ]
+"#,
+        );
+        assert_eq!(line, 8);
+        assert!(message.contains("Unsupported file extension"), "{message}");
+
+        let (_, message) = single(
+            r#"name = ".."
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[action.SingleFile]
+file = "Font.ttf"
+"#,
+        );
+        assert!(message.contains("Invalid name"), "{message}");
+    }
+
+    #[test]
+    fn patterns() {
+        let diagnostics = lint(
+            r#"name = "Test Font"
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[action.Extract]
+file = "Font.zip"
+include = [ "/fonts/*.ttf", "**.otf" ]
+"#,
+        );
+        assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.line == 9));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let (line, _) = single("name = \"Test Font\"\n\n[source.GitHub\n");
+        assert_eq!(line, 3);
+    }
+
+    #[test]
+    fn font_variants() {
+        let installer = |variant: &str| {
+            format!(
+                r#"name = "Test Font"
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[action.Extract]
+file = "Font.zip"
+include = [ "*.ttf" ]
+
+[variants.static]
+{variant}
+"#
+            )
+        };
+        assert!(lint(&installer(r#"file = "Font-Static.zip""#)).is_empty());
+
+        let (line, message) = single(&installer(r#"fille = "Font-Static.zip""#));
+        assert_eq!(line, 12);
+        assert!(message.contains("`fille`"), "{message}");
+
+        let (_, message) = single(&installer(r#"file = "Font-Static.exe""#));
+        assert!(message.starts_with("Variant `static`:"), "{message}");
+    }
+
+    #[test]
+    fn variants_of_multiple_actions() {
+        let installer = |variant: &str| {
+            format!(
+                r#"name = "Test Font"
+
+[source.GitHub]
+author = "author"
+project = "font"
+
+[[action]]
+[action.Extract]
+file = "Font.zip"
+include = [ "*.ttf" ]
+
+[[action]]
+[action.SingleFile]
+file = "OFL.txt"
+
+{variant}
+"#
+            )
+        };
+        let diagnostics = lint(&installer(
+            r#"[[variants.nf.action]]
+file = "Font-NF.zip"
+include = [ "nf/*.ttf" ]
+
+[[variants.nf.action]]
+file = "OFL-NF.txt""#,
+        ));
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+        let (line, message) = single(&installer("[variants.nf]\nfile = \"Font-NF.zip\""));
+        assert_eq!(line, 16);
+        assert!(
+            message.contains("must override each of the 2 actions"),
+            "{message}"
+        );
+
+        // `SingleFile` does not accept `include`
+        let (line, message) = single(&installer(
+            r#"[[variants.nf.action]]
+file = "Font-NF.zip"
+
+[[variants.nf.action]]
+include = [ "*.txt" ]"#,
+        ));
+        assert_eq!(line, 20);
+        assert!(message.contains("Unknown field: `include`"), "{message}");
+
+        let (_, message) = single(&installer("[[variants.nf.action]]\nfile = \"Font-NF.zip\""));
+        assert!(
+            message.contains("overrides 1 action(s), but the installer has 2"),
+            "{message}"
+        );
+    }
+
+    /// Returns the names which serde expects instead of the unknown field or
+    /// variant within `contents`, sorted
+    fn expected<T: DeserializeOwned>(contents: &str) -> Vec<String> {
+        let Err(error) = toml::from_str::<T>(contents) else {
+            panic!("{contents} was accepted");
+        };
+        let message = error.message();
+        // Fields of sources and actions are reported by `toml` itself
+        let names = match message.split_once("available keys: ") {
+            Some((_, keys)) => keys.split(", ").collect::<Vec<_>>(),
+            None => {
+                let (_, expected) = message.split_once("expected").expect(message);
+                expected.split('`').skip(1).step_by(2).collect()
+            }
+        };
+        let mut names = names
+            .into_iter()
+            // `None` sources and actions are rejected while validating
+            .filter(|name| *name != "None")
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    fn sorted(names: &[&str]) -> Vec<String> {
+        let mut names = names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Checks the variants lint accepts, and their fields, against `T`
+    fn check_variants<T: DeserializeOwned>(variants: &[(&str, &[&str])]) {
+        let names = variants.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        assert_eq!(expected::<T>("[Unknown]"), sorted(&names));
+        for (name, fields) in variants {
+            assert_eq!(
+                expected::<T>(&format!("[{name}]\nunknown = 0")),
+                sorted(fields),
+                "{name}"
+            );
+        }
+    }
+
+    /// Checks the fields lint accepts against those the installer accepts
+    #[test]
+    fn fields_match_installer() {
+        // Templates and their variables are resolved before parsing
+        let mut fields = expected::<Installer>("unknown = 0");
+        fields.extend(["extends".to_owned(), "variables".to_owned()]);
+        fields.sort();
+        assert_eq!(fields, sorted(lint::INSTALLER_FIELDS));
+
+        check_variants::<Source>(lint::SOURCES);
+        check_variants::<FileAction>(lint::ACTIONS);
+        check_variants::<Checksum>(lint::CHECKS);
+    }
+}
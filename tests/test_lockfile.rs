@@ -0,0 +1,116 @@
+mod test_lockfile {
+    #![cfg(test)]
+
+    use fin::dependencies::{base_name, expand_dependencies};
+    use fin::lockfile::{LockedAsset, LockedFont, Lockfile};
+    use fin::manifest::Manifest;
+
+    const LOCKFILE: &str = r#"
+[[fonts.inter.assets]]
+url = "https://example.com/Inter-4.0.zip"
+sha256 = "2d711642b726b04401627ca9fbac32f5c8530fb1903cc4db02258717921a4881"
+
+[fonts.maple-mono]
+tag = "v7.0"
+variant = "nf"
+
+[[fonts.maple-mono.assets]]
+url = "https://example.com/MapleMono-NF.zip"
+sha256 = "a1fce4363854ff888cff4b8e7875d600c2682390412a8cf79b37d0b11148b0fa"
+"#;
+
+    /// Dependencies of the installers used by the tests below
+    fn read_dependencies(name: &str) -> Option<(Vec<String>, bool)> {
+        match name {
+            "workstation" => Some((vec!["inter".into(), "maple-mono@nf".into()], true)),
+            _ => Some((Vec::new(), false)),
+        }
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            Lockfile::path("~/.config/fin/fonts.toml"),
+            "~/.config/fin/fonts.lock"
+        );
+        assert_eq!(Lockfile::path("fonts"), "fonts.lock");
+    }
+
+    #[test]
+    fn locked_fonts() {
+        let lockfile = Lockfile::parse(LOCKFILE).unwrap();
+        assert_eq!(lockfile.fonts["inter"].to_font("inter"), "inter");
+        assert_eq!(
+            lockfile.fonts["maple-mono"].to_font("maple-mono"),
+            "maple-mono@nf:v7.0"
+        );
+        assert_eq!(
+            lockfile.fonts["maple-mono"].assets,
+            [LockedAsset {
+                url: "https://example.com/MapleMono-NF.zip".to_string(),
+                sha256: "a1fce4363854ff888cff4b8e7875d600c2682390412a8cf79b37d0b11148b0fa"
+                    .to_string(),
+            }]
+        );
+
+        let reparsed = Lockfile::parse(&lockfile.to_toml()).unwrap();
+        assert_eq!(reparsed.fonts, lockfile.fonts);
+    }
+
+    #[test]
+    fn outdated_lockfiles() {
+        let lockfile = Lockfile::parse(LOCKFILE).unwrap();
+        let check = |manifest: &str| {
+            lockfile.check(&Manifest::parse(manifest).unwrap(), &read_dependencies)
+        };
+
+        assert!(check("[fonts]\ninter = {}\nmaple-mono = { variant = \"nf\" }").is_ok());
+        assert!(check("[fonts]\nmaple-mono = { variant = \"nf\", tag = \"v7.0\" }").is_ok());
+        assert_eq!(
+            check("[fonts]\nmaple-mono = { variant = \"nf\", tag = \"v7.1\" }"),
+            Err("maple-mono: Locked with another tag".to_string())
+        );
+        assert_eq!(
+            check("[fonts]\nmaple-mono = {}"),
+            Err("maple-mono: Locked with another variant".to_string())
+        );
+        assert_eq!(
+            check("[fonts]\nfira-code = {}"),
+            Err("fira-code: Not locked".to_string())
+        );
+    }
+
+    #[test]
+    fn dependencies_are_not_listed_fonts() {
+        let mut lockfile = Lockfile::default();
+        lockfile.fonts.insert(
+            "inter".to_string(),
+            LockedFont {
+                dependency: true,
+                ..LockedFont::default()
+            },
+        );
+        let manifest = Manifest::parse("[fonts]\ninter = {}").unwrap();
+        assert!(lockfile.check(&manifest, &read_dependencies).is_err());
+        assert!(!lockfile.to_toml().contains("dependency = false"));
+    }
+
+    #[test]
+    fn bundles_are_not_locked() {
+        // Locks the fonts the way `fin lock` does
+        let manifest = Manifest::parse("[fonts]\nworkstation = {}").unwrap();
+        let listed = ["workstation".to_string()];
+        let mut lockfile = Lockfile::default();
+        for (font, dependency) in expand_dependencies(&listed, &read_dependencies).unwrap() {
+            let locked = LockedFont {
+                dependency,
+                ..LockedFont::default()
+            };
+            lockfile.fonts.insert(base_name(&font).to_owned(), locked);
+        }
+
+        assert!(!lockfile.fonts.contains_key("workstation"));
+        assert!(lockfile.fonts["inter"].dependency);
+        assert!(lockfile.check(&manifest, &read_dependencies).is_ok());
+    }
+}
@@ -0,0 +1,96 @@
+mod test_manifest {
+    #![cfg(test)]
+
+    use fin::installed::{InstalledFont, InstalledFonts};
+    use fin::manifest::{self, Manifest, ManifestFont};
+
+    const MANIFEST: &str = r#"
+[fonts]
+inter = {}
+maple-mono = { variant = "nf", tag = "v7.0" }
+jetbrains-mono = { install_dir = "~/fonts", tag = "latest" }
+"#;
+
+    #[test]
+    fn fonts_to_install() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        let fonts = manifest
+            .fonts
+            .iter()
+            .map(|(name, font)| font.to_font(name))
+            .collect::<Vec<_>>();
+        assert_eq!(fonts, ["inter", "jetbrains-mono", "maple-mono@nf:v7.0"]);
+        assert_eq!(
+            manifest.fonts["jetbrains-mono"].install_dir.as_deref(),
+            Some("~/fonts")
+        );
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let e = Manifest::parse("[fonts]\ninter = { version = \"4.0\" }")
+            .err()
+            .unwrap();
+        assert!(e.contains("version"), "{e}");
+    }
+
+    #[test]
+    fn consistent_format() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        let toml = manifest.to_toml();
+        assert_eq!(
+            toml,
+            r#"[fonts]
+inter = {}
+jetbrains-mono = { install_dir = "~/fonts", tag = "latest" }
+maple-mono = { tag = "v7.0", variant = "nf" }
+"#
+        );
+        let parsed = Manifest::parse(&toml).unwrap();
+        assert_eq!(parsed.fonts, manifest.fonts);
+
+        let mut manifest = Manifest::default();
+        manifest
+            .fonts
+            .insert("my font".to_string(), ManifestFont::default());
+        assert_eq!(manifest.to_toml(), "[fonts]\n\"my font\" = {}\n");
+    }
+
+    #[test]
+    fn directories() {
+        assert_eq!(manifest::parent_dir("~/fonts/Inter/"), "~/fonts");
+        assert!(manifest::same_dir("/tmp/fonts/", "/tmp/fonts"));
+        assert!(!manifest::same_dir("/tmp/fonts", "/tmp/fonts/Inter"));
+    }
+
+    fn installed_font(tag: &str, requested_tag: Option<&str>) -> InstalledFont {
+        InstalledFont {
+            url: "https://example.com/font.zip".to_string(),
+            extra_urls: Vec::new(),
+            tag: Some(tag.to_string()),
+            requested_tag: requested_tag.map(ToString::to_string),
+            dir: "/tmp/fonts/Font".to_string(),
+            files: Vec::new(),
+            sums: Default::default(),
+            origin: None,
+            variant: None,
+            dependency: false,
+        }
+    }
+
+    #[test]
+    fn only_requested_tags_are_exported() {
+        let mut installed_fonts = InstalledFonts::default();
+        installed_fonts
+            .installed
+            .insert("inter".to_string(), installed_font("latest", None));
+        installed_fonts.installed.insert(
+            "maple-mono".to_string(),
+            installed_font("tags/v7.0", Some("v7.0")),
+        );
+        assert_eq!(
+            Manifest::from_installed(&installed_fonts, "/tmp/fonts").to_toml(),
+            "[fonts]\ninter = {}\nmaple-mono = { tag = \"v7.0\" }\n"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+mod common;
+
+mod test_ownership {
+    #![cfg(test)]
+
+    use std::fs;
+
+    use crate::common::TempDir;
+
+    use fin::ownership::{Conflict, Ownership};
+
+    fn files(files: &[&str]) -> Vec<String> {
+        files.iter().map(ToString::to_string).collect()
+    }
+
+    fn ownership() -> Ownership {
+        let mut ownership = Ownership::default();
+        ownership.insert("font-a", "/fonts/Font/", &files(&["A.ttf", "OFL.txt"]));
+        ownership.insert("font-b", "/fonts/Other", &files(&["B.ttf"]));
+        ownership
+    }
+
+    #[test]
+    fn owners() {
+        let ownership = ownership();
+        assert_eq!(ownership.owners("/fonts/Font/A.ttf"), ["font-a"]);
+        assert_eq!(ownership.owners("/fonts/Other/B.ttf"), ["font-b"]);
+        // Directories belong to the fonts installed into them
+        assert_eq!(ownership.owners("/fonts/Font/"), ["font-a"]);
+        assert_eq!(ownership.owners("/fonts/Font"), ["font-a"]);
+        assert!(ownership.owners("/fonts/Font/B.ttf").is_empty());
+        assert!(ownership.owners("/fonts").is_empty());
+    }
+
+    #[test]
+    fn conflicts() {
+        let ownership = ownership();
+        assert_eq!(
+            ownership.conflicts("font-c", "/fonts/Font", &files(&["C.ttf", "OFL.txt"])),
+            [Conflict {
+                file: "OFL.txt".to_string(),
+                owner: "font-a".to_string(),
+            }]
+        );
+        // Reinstalling a font does not conflict with itself
+        assert!(
+            ownership
+                .conflicts("font-a", "/fonts/Font/", &files(&["A.ttf", "OFL.txt"]))
+                .is_empty()
+        );
+        assert!(
+            ownership
+                .conflicts("font-c", "/fonts/Other/Font", &files(&["A.ttf"]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn sharing_dir() {
+        let ownership = ownership();
+        assert_eq!(ownership.sharing_dir("font-c", "/fonts/Font/"), ["font-a"]);
+        assert!(ownership.sharing_dir("font-a", "/fonts/Font/").is_empty());
+        assert!(ownership.sharing_dir("font-c", "/fonts/New/").is_empty());
+    }
+
+    #[test]
+    fn resolved_paths() {
+        let temp_dir = TempDir::new("ownership");
+        let dir = temp_dir.path();
+        fs::create_dir_all(format!("{dir}/fonts/Font")).unwrap();
+        fs::create_dir_all(format!("{dir}/fonts/Other")).unwrap();
+        fs::write(format!("{dir}/fonts/Font/A.ttf"), b"").unwrap();
+        std::os::unix::fs::symlink(format!("{dir}/fonts"), format!("{dir}/link")).unwrap();
+
+        let mut ownership = Ownership::default();
+        ownership.insert("font-a", &format!("{dir}/link/Font/"), &files(&["A.ttf"]));
+        for path in [
+            format!("{dir}/fonts/Font/A.ttf"),
+            format!("{dir}/link/Font/A.ttf"),
+            format!("{dir}/fonts/Other/../Font/A.ttf"),
+            format!("{dir}/fonts/Font"),
+        ] {
+            assert_eq!(ownership.owners(&path), ["font-a"], "{path}");
+        }
+    }
+}
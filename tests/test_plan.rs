@@ -0,0 +1,38 @@
+mod test_plan {
+    #![cfg(test)]
+
+    use fin::plan::FilePlan;
+
+    fn files(files: &[&str]) -> Vec<String> {
+        files.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn new_installation() {
+        let plan = FilePlan::new(&files(&["A.ttf", "OFL.txt"]), &[], &[]);
+        assert_eq!(plan.added, ["A.ttf", "OFL.txt"]);
+        assert!(plan.overwritten.is_empty());
+        assert!(plan.removed.is_empty());
+    }
+
+    #[test]
+    fn updates() {
+        let plan = FilePlan::new(
+            &files(&["A-Regular.ttf", "A-Bold.ttf", "OFL.txt"]),
+            &files(&["A-Regular.ttf", "A-Italic.ttf", "OFL.txt", "missing.ttf"]),
+            &files(&["A-Regular.ttf", "A-Italic.ttf", "OFL.txt", "notes.txt"]),
+        );
+        assert_eq!(plan.added, ["A-Bold.ttf"]);
+        assert_eq!(plan.overwritten, ["A-Regular.ttf", "OFL.txt"]);
+        // Files which were already removed, or which fin did not install, are kept
+        assert_eq!(plan.removed, ["A-Italic.ttf"]);
+    }
+
+    #[test]
+    fn files_not_installed_by_fin_are_overwritten() {
+        let plan = FilePlan::new(&files(&["A.ttf"]), &[], &files(&["A.ttf"]));
+        assert_eq!(plan.overwritten, ["A.ttf"]);
+        assert!(!plan.is_empty());
+        assert!(FilePlan::new(&[], &[], &files(&["A.ttf"])).is_empty());
+    }
+}
@@ -6,7 +6,8 @@ mod test_readme {
     #[inline]
     fn readme() -> String {
         let readme_path = env!("CARGO_MANIFEST_DIR").to_owned() + "/README.md";
-        fs::read_to_string(&readme_path).expect(&format!("`{readme_path}` is not a valid path"))
+        fs::read_to_string(&readme_path)
+            .unwrap_or_else(|_| panic!("`{readme_path}` is not a valid path"))
     }
 
     #[test]
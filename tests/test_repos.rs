@@ -0,0 +1,131 @@
+mod test_repos {
+    #![cfg(test)]
+
+    use fin::repos::{self, RepoKind};
+
+    const INSTALLER: &str = r#"
+name = "Test"
+
+[source.Direct]
+url = "https://example.com/$file"
+
+[action.SingleFile]
+file = "test.ttf"
+"#;
+
+    fn files(paths: &[&str]) -> Vec<(String, String)> {
+        paths
+            .iter()
+            .map(|path| (path.to_string(), INSTALLER.to_string()))
+            .collect()
+    }
+
+    fn names(installers: &[(String, String)]) -> Vec<&str> {
+        installers.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn kinds() {
+        for (url, kind) in [
+            ("https://example.com/fonts.git", RepoKind::Git),
+            ("git@example.com:team/fonts.git", RepoKind::Git),
+            ("ssh://git@example.com/team/fonts", RepoKind::Git),
+            ("https://example.com/fonts.tar.gz", RepoKind::Archive),
+            ("https://example.com/fonts.tgz?ref=main", RepoKind::Archive),
+            ("https://example.com/fonts.zip", RepoKind::Archive),
+            ("https://example.com/fonts/index", RepoKind::Index),
+            ("https://example.com/fonts/index.txt", RepoKind::Index),
+        ] {
+            assert_eq!(RepoKind::detect(url), kind, "{url}");
+        }
+    }
+
+    #[test]
+    fn names_are_validated() {
+        for name in ["team", "team-fonts", "team_2"] {
+            assert!(repos::is_valid_name(name), "{name}");
+        }
+        for name in ["", ".", "..", "team/fonts", "team fonts", "~"] {
+            assert!(!repos::is_valid_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn index() {
+        let index = "\
+# Fonts of the team
+inter
+jetbrains-mono  # Used by the editor
+
+maple-mono
+";
+        assert_eq!(
+            repos::parse_index(index).unwrap(),
+            ["inter", "jetbrains-mono", "maple-mono"]
+        );
+        assert!(repos::parse_index("../inter").is_err());
+        assert!(repos::parse_index(".hidden").is_err());
+    }
+
+    #[test]
+    fn installers_directory_is_preferred() {
+        let mut files = files(&[
+            "fonts/installers/inter",
+            "fonts/installers/geist",
+            "fonts/geist-mono",
+        ]);
+        files.push(("fonts/README".to_string(), "# Fonts".to_string()));
+        assert_eq!(names(&repos::select_installers(files)), ["geist", "inter"]);
+    }
+
+    #[test]
+    fn root_installers() {
+        let mut files = files(&["inter", "geist", "docs/geist-mono", ".github/inter-nf"]);
+        files.push(("LICENSE".to_string(), "MIT License".to_string()));
+        assert_eq!(names(&repos::select_installers(files)), ["geist", "inter"]);
+    }
+
+    #[test]
+    fn templates_next_to_installers() {
+        let mut files = files(&["fonts/installers/inter", "fonts/geist"]);
+        files.push((
+            "fonts/installers/templates/nerd-font".to_string(),
+            "tags = [ \"nerd-font\" ]".to_string(),
+        ));
+        files.push((
+            "fonts/installers/geist-mono-nf".to_string(),
+            "extends = \"nerd-font\"".to_string(),
+        ));
+        files.push((
+            "fonts/templates/other".to_string(),
+            "name = \"Other\"".to_string(),
+        ));
+        assert_eq!(
+            names(&repos::select_installers(files)),
+            ["geist-mono-nf", "inter", "templates/nerd-font"]
+        );
+        assert!(repos::parse_index("templates/nerd-font").is_ok());
+        assert!(repos::parse_index("templates/../inter").is_err());
+    }
+
+    #[test]
+    fn tar_archives() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for path in ["fonts-main/installers/inter", "fonts-main/README.md"] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(INSTALLER.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, INSTALLER.as_bytes())
+                .unwrap();
+        }
+        let archive = builder.into_inner().unwrap();
+
+        let files = repos::read_tar(archive.as_slice()).unwrap();
+        assert_eq!(files.len(), 2);
+        let installers = repos::select_installers(files);
+        assert_eq!(names(&installers), ["inter"]);
+        assert_eq!(installers[0].1, INSTALLER);
+    }
+}
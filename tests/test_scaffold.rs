@@ -0,0 +1,138 @@
+mod test_scaffold {
+    #![cfg(test)]
+
+    use fin::installer::Installer;
+    use fin::scaffold::{self, Scaffold};
+    use fin::source::Source;
+
+    fn paths(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn truetype_fonts_are_preferred() {
+        let (include, exclude) = scaffold::propose_rules(&paths(&[
+            "Font-1.0/otf/Font-Regular.otf",
+            "Font-1.0/ttf/Font-Regular.ttf",
+            "Font-1.0/ttf/Font-Bold.ttf",
+            "Font-1.0/OFL.txt",
+            "Font-1.0/README.md",
+        ]));
+        assert_eq!(include, ["*.ttf", "*OFL.txt"]);
+        assert!(exclude.is_empty());
+    }
+
+    #[test]
+    fn opentype_fonts() {
+        let (include, _) = scaffold::propose_rules(&paths(&["Font-Regular.otf", "LICENSE"]));
+        assert_eq!(include, ["*.otf", "*LICENSE"]);
+    }
+
+    #[test]
+    fn unwanted_copies_are_excluded() {
+        let (include, exclude) = scaffold::propose_rules(&paths(&[
+            "fonts/ttf/Font-Regular.ttf",
+            "fonts/ttf-unhinted/Font-Regular.ttf",
+            "__MACOSX/fonts/ttf/Font-Regular.ttf",
+        ]));
+        assert_eq!(include, ["*.ttf"]);
+        assert_eq!(exclude, ["*ttf-unhinted/*", "*__MACOSX/*"]);
+    }
+
+    #[test]
+    fn conflicting_fonts_prefer_variable() {
+        let (include, _) = scaffold::propose_rules(&paths(&[
+            "fonts/ttf/Font-Regular.ttf",
+            "fonts/ttf/Font-Bold.ttf",
+            "fonts/variable/Font-Regular.ttf",
+            "LICENSE.txt",
+            "fonts/LICENSE.txt",
+        ]));
+        assert_eq!(include, ["*variable/*.ttf", "LICENSE.txt"]);
+    }
+
+    #[test]
+    fn no_fonts() {
+        let (include, exclude) = scaffold::propose_rules(&paths(&["README.md", "Font.woff2"]));
+        assert!(include.is_empty());
+        assert!(exclude.is_empty());
+    }
+
+    #[test]
+    fn versions_are_generalized() {
+        for (file, tag, expected) in [
+            ("Font-1.2.zip", Some("v1.2"), "Font-*.zip"),
+            ("Font-v1.2.zip", Some("v1.2"), "Font-*.zip"),
+            ("Font.zip", Some("v1.2"), "Font.zip"),
+            ("Font-1.2.zip", None, "Font-1.2.zip"),
+            ("Font-latest.zip", Some("latest"), "Font-latest.zip"),
+        ] {
+            assert_eq!(scaffold::generalize_file(file, tag), expected, "{file}");
+        }
+    }
+
+    #[test]
+    fn checksums() {
+        let assets = paths(&["Font.zip", "Font.zip.sha256", "checksums.txt"]);
+        assert_eq!(
+            scaffold::detect_checksum("Font.zip", &assets),
+            Some(("SHA256", "Font.zip.sha256".to_string()))
+        );
+
+        let assets = paths(&["Font.zip", "SHA512SUMS"]);
+        assert_eq!(
+            scaffold::detect_checksum("Font.zip", &assets),
+            Some(("SHA512", "SHA512SUMS".to_string()))
+        );
+
+        let assets = paths(&["Font.zip", "Other.zip.sha256"]);
+        assert_eq!(scaffold::detect_checksum("Font.zip", &assets), None);
+    }
+
+    #[test]
+    fn assets_and_links() {
+        let release = r#"{
+            "tag_name": "v1.0",
+            "assets": [
+                { "name": "Font-1.0.zip", "browser_download_url": "https://example.com/Font-1.0.zip" }
+            ]
+        }"#;
+        let (assets, tag) = scaffold::release_assets(release).unwrap();
+        assert_eq!(assets[0].name, "Font-1.0.zip");
+        assert_eq!(tag.as_deref(), Some("v1.0"));
+
+        let page = r#"<a href="https://example.com/dl/Font.zip?v=1">Font</a>
+            <a href="https://example.com/about">About</a>
+            <a href='https://example.com/Font.ttf'>Font</a>"#;
+        let names = scaffold::page_links(page)
+            .into_iter()
+            .map(|asset| asset.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Font.zip", "Font.ttf"]);
+
+        assert!(scaffold::is_font_asset("Font.tar.gz"));
+        assert!(scaffold::is_font_asset("Font.TTF"));
+        assert!(!scaffold::is_font_asset("Font.zip.sha256"));
+    }
+
+    #[test]
+    fn scaffolds_are_valid_installers() {
+        assert_eq!(scaffold::display_name("maple-mono_nf"), "Maple Mono Nf");
+
+        let scaffold = Scaffold {
+            name: "Font \"Sans\"".to_string(),
+            source: Source::GitHub {
+                tag: None,
+                author: "author".to_string(),
+                project: "font".to_string(),
+            },
+            file: "Font-*.zip".to_string(),
+            include: Some(paths(&["*.ttf", "*OFL.txt"])),
+            exclude: paths(&["*unhinted/*"]),
+            check: Some(("SHA256", "Font-*.zip.sha256".to_string())),
+        };
+        let installer = toml::from_str::<Installer>(&scaffold.to_toml()).unwrap();
+        assert_eq!(installer.name, "Font \"Sans\"");
+        assert_eq!(installer.action.len(), 1);
+    }
+}
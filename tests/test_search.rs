@@ -0,0 +1,60 @@
+mod test_search {
+    #![cfg(test)]
+
+    use fin::actions::search::{SearchAction, SearchResult};
+
+    fn result(installer_name: &str, name: &str, description: &str, tags: &[&str]) -> SearchResult {
+        SearchResult {
+            installer_name: installer_name.to_owned(),
+            name: Some(name.to_owned()),
+            description: Some(description.to_owned()),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            score: 0,
+        }
+    }
+
+    #[test]
+    fn match_quality() {
+        let font = result(
+            "jetbrains-mono",
+            "JetBrains Mono",
+            "A typeface made for developers",
+            &["monospace", "variable"],
+        );
+
+        let exact = font.score_term("jetbrains-mono").unwrap();
+        let prefix = font.score_term("jetbrains").unwrap();
+        let word = font.score_term("mono").unwrap();
+        let fuzzy = font.score_term("jbm").unwrap();
+        assert!(exact > prefix && prefix > word && word > fuzzy);
+
+        assert!(font.score_term("variable").is_some());
+        assert!(font.score_term("developers").is_some());
+        assert!(font.score_term("serif").is_none());
+    }
+
+    #[test]
+    fn ignores_case() {
+        let font = result("inter", "Inter", "", &["Sans-Serif"]);
+        assert!(font.score_term("inter").is_some());
+        assert!(font.score_term("sans-serif").is_some());
+    }
+
+    #[test]
+    fn fuzzy_matches_names_only() {
+        let font = result("cozette", "Cozette", "A bitmap programming font", &[]);
+        assert!(font.score_term("czt").is_some());
+        assert!(font.score_term("bpf").is_none());
+    }
+
+    #[test]
+    fn unknown_installers_match_by_name() {
+        // The installer files do not exist, so only the names can be matched
+        let names = ["missing-mono".to_string(), "missing-sans".to_string()];
+        let results = SearchAction::search(&names, &["mono".to_string()]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].installer_name, "missing-mono");
+
+        assert!(SearchAction::search(&names, &["missing".into(), "zzz".into()]).is_empty());
+    }
+}
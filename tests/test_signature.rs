@@ -0,0 +1,390 @@
+mod test_signature {
+    #![cfg(test)]
+
+    use fin::openpgp;
+    use fin::signature::verify_minisign;
+
+    const DATA: &[u8] = b"fin test data\n";
+
+    const MINISIGN_PUBLIC_KEY: &str = "RWQBAgMEBQYHCAOhB7/zzhC+HXDdGOdLwJln5NYwm6UNXx3chmQSVTG4";
+
+    const MINISIGN_SIGNATURE: &str = "\
+untrusted comment: signature from minisign secret key
+RUQBAgMEBQYHCGz1GdnVg3kLw8640b6lm/h2LirqxxkXuEJgCjUpGHETkGr1ACPIMxm+KardsNQYeRo3CnTg9dhXIYDxF1lEugc=
+trusted comment: timestamp:1760000000\tfile:data.txt\thashed
+ym/2iwLDIufcF77umz3oQtMu5XiG4Rz0LKYJZl1VOAB44unQbP1gNHx9GOp7wvLuMtzDiFBFLsFLV7KebgwJBA==
+";
+
+    const ED25519_PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+mDMEatVBpxYJKwYBBAHaRw8BAQdA05alb+mKE6esM/sXecQ8ktS8SbtRx9y/Wgsf
+N8XBWyW0JkZpbiBUZXN0IEVkMjU1MTkgPGVkMjU1MTlAZXhhbXBsZS5jb20+iJAE
+ExYIADgWIQRLfJcUvi1i4tpULnXk2Vf6ZWZ70AUCatVBpwIbAwULCQgHAgYVCgkI
+CwIEFgIDAQIeAQIXgAAKCRDk2Vf6ZWZ70CQ5AP4ko9O/onteHDCI9P/nxNXiw8Uo
+Wo0WCpblKWr1+0B1iAEA5i3POoHFetlThR2SVXq1OMTmql3Mc6VjvcZEtMP2vwU=
+=S14H
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    const ED25519_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+iIoEABYIADIWIQRLfJcUvi1i4tpULnXk2Vf6ZWZ70AUCatVBqBQcZWQyNTUxOUBl
+eGFtcGxlLmNvbQAKCRDk2Vf6ZWZ70M/4AQC901+KLvUeqGg78HenzuzVFrGDW3ps
+XFTz6OrzMlogvwEAjON9CKbs67Hw6LmjiVAtYAYnNe3Pjh26mue/L6/nMgA=
+=hcc+
+-----END PGP SIGNATURE-----
+";
+
+    const ED25519_TEXT_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+iIoEARYIADIWIQRLfJcUvi1i4tpULnXk2Vf6ZWZ70AUCatVBrxQcZWQyNTUxOUBl
+eGFtcGxlLmNvbQAKCRDk2Vf6ZWZ70IoaAQC2WRDgXgzFwiDk/wRh2COKQ7sFi4h+
+MMTkJaRVOBDwNQEAvNdNYHSWTNfdb5GLTAJT+jwmCTttfGETUbm4ltKpXQg=
+=8Bw6
+-----END PGP SIGNATURE-----
+";
+
+    const RSA_PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+mQENBGrVQacBCACiX+4dhIin3Eric+WLRFIobGpmFy9RNLkVDU5aMHpIn5kG8oM8
+3ow7rgnvWsEDK1bWS5OjoHNBj/w8DbO7T7SGWReduktoE9y+LVq/Y04WqoC4CILK
+DJT6e1Wx4Mi5R+LZ/xcfTsWs2+rXezkcx7aS0uhsSkFitQwFkKpeKViasnhuQcKJ
+oKFLiImiFV9AWo7+p+D3V7+1lEHs7lxVHvXHRVthTwyEoDZHcEOMDAxIIcsgm72J
+8HlBg4rwH2N5vIvHohR4FRh/1bVR6elcTkRLeqbIqW3QsqcfH7TDtiWvCpUAj0y8
+PwKAZPmBTLMMoUpDfe8tgAKkTXc48S4YF+9VABEBAAG0HkZpbiBUZXN0IFJTQSA8
+cnNhQGV4YW1wbGUuY29tPokBTgQTAQoAOBYhBAVLc7QZ0shtpBh67JDnYI1MaU1b
+BQJq1UGnAhsDBQsJCAcCBhUKCQgLAgQWAgMBAh4BAheAAAoJEJDnYI1MaU1bW4wI
+AJXDzSyc4kxChLqIv2CzBmzNvNVwLPhregbOaz7lm1DFLarM94dbC03RXomvlKYP
+ogPFpL5WFvhnKq800WM8Ea9+6erGVog60k4YM3hLSg8EokW0DvewnaHDb/UFX+Ir
+G2k57I+RvuLQ4ZdOdxwozAVRkOcMDafdXH0YrJG81vuI/iwU3dRAoZjvaKuUj731
+H/FdIsyVqsSYsENWIy5hAzXHmUmqsYIcFAKuV7J5v68lWSbjtAbyluJNzGXBz4hS
+sWQUDhzPNtIf9qm4Sfj2ugqtvETnXBrcJVIc5aykrw7mNDJqIDSU5OruR2A//Wlq
+HHM3JBWqm2bcw7EutO9jXCw=
+=usNf
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    const RSA_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+iQFEBAABCgAuFiEEBUtztBnSyG2kGHrskOdgjUxpTVsFAmrVQa8QHHJzYUBleGFt
+cGxlLmNvbQAKCRCQ52CNTGlNW6bmB/4nb+oNft323yNDA8Gj1aknvZdnYMU3fM5B
+qhUU70GJz5ODnTM95Jg3xrwiIJLy0+9/QYADMwndCO83SdFrA/WSWNRDJID61lbw
+FyqBubEIrQibap/yzoz8SGJnEFFy20PHT7SAYsXgNCdsrfvgArhsbs78UsrOfRmq
+a29P5Cv3rkttJX0nke3V82dfWMLxxLpOPDbW2vWSsGC3x6uNKuj70pf1gtNAMhDN
+bbKLZn8AaJ8nxinBjCGdzo9rhlnfcFG1sEt+3GQWWFAJA6MHU9g2uF7ji0NMg+zC
+Gan17wy86Z21765iqYioAsBQyc9xN5/6ctX3tof2WYZI64sudE3J
+=8WGY
+-----END PGP SIGNATURE-----
+";
+
+    /// A 1024-bit RSA key, which is too small to be trusted
+    const RSA_1024_PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+xo0EahilAAEEAMJ8aN9+Gh7Uyd6Gc56KcYqnKOklum0KlIwtD3sFm7Yxf2yEahuD
+h4BEwB/rtPSF60srsOqxe4Kv7bIB54CCdC2zBvPvZcR+y9o2L55UmSMkZwdJ+y1G
+ZBctRuO0IzboG+h0TWvvXmhsFSbXFlwact2FC4HSLPO3lWmCZD9ea02bABEBAAHN
+J0ZpbiBUZXN0IFJTQS0xMDI0IDxyc2ExMDI0QGV4YW1wbGUuY29tPsK2BBMBCAAg
+BQJqGKUAFiEEHDCKefIsPoZ+Cco6cOFPZs04SgICGwMACgkQcOFPZs04SgLS+AP/
+RTzfNctSHH8czKmKXHNUoQgNKurVJRMo3sYx001mgrB+rSnTWoJMddUx3Wo6qu9n
+ZDJlR7vh0d/21w7BOvChqxCX/MawnzS+T5bY3mV3VlVkmka1NjCutBM2xqieC+zm
+8afXZ8P0Ybwgt7nmxtyxmNChSocYo0gs/W/ggYD3pj0=
+=ZkqT
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    const RSA_1024_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+wrMEAAEIAB0FAmoYpQAWIQQcMIp58iw+hn4Jyjpw4U9mzThKAgAKCRBw4U9mzThK
+AtXOBACKu+78W63R4cGH3ZDtvL2G/kAmUqSHWTVt5ajFC7WbZBudToApVrtkxW1S
+z9MPOsxcwX+sqV73lcAYcDMmMUT3roOByCl4hPNdgSHbMnnSFzWtaoxqHn+n35Yu
+95y3+evTVGZnXpYQJXPybsdyt97DO4pOCY7q4xCdtMC3KoTMoA==
+=xY7D
+-----END PGP SIGNATURE-----
+";
+
+    /// An encrypt-only RSA key (algorithm 2), which must never verify signatures
+    const RSA_ENCRYPT_ONLY_PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+xsBNBGoYpQACCADHxnFKys4/io0IQt8vCLTjaP4Zpz8occ+urMDJcDTJsZtwE6XS
++Ab8JLWKurWhR6hxrfzhDZucPxUdVRuMDzgB1wgw7SWngYxRLRi7mSjGnIkBzTs+
+ukyj5v/LRjQWVnmlUYPskE5KmIxA4kIgKbRBWjodmAEHLqoH7uZtWzw5CswBNQ30
+SyUW4oAoRSsA3g1JkzQoYQHgTIaYF/zxZ40CftktW02i7q879IG3II8cP9w9U7lj
+K4svC8JGI0DN6q1eejgSBgtsvLKmTqe7gBAHjJ2FkD00dbkBSmGrYEzV2LvYHTqM
+Jcv8/xGyo9CtcW5o/TQ7vRRkvwKDpsI9JW39ABEBAAHNM0ZpbiBUZXN0IFJTQSBF
+bmNyeXB0LU9ubHkgPHJzYS1lbmNyeXB0QGV4YW1wbGUuY29tPsLAdgQTAggAIAUC
+ahilABYhBJ8bEGHjkIszLffpC4bW+gvoU/6kAhsDAAoJEIbW+gvoU/6kj3AH/jG1
+F14w4NnE50QQ6LxVV+zTO+pr+Z48Sgq5uk0LghtTbMHspMQIj3a4dozdLYsFeqkB
+i9L17RGuCb9KZZn14fMBRYxV2Ak0xZ2Sskp6EEj2rXsUl1jqVmDnrXLgZ9/vJUZr
+JAod08++kHbKyiS8j/xlgNvKFw/yemCmKDZGvGKl1DTJo4oO06jW9SD6AntBdjaG
+iPAhnjf2m04X4n8EMWw4KF1n7zqJgcBI8GuQ36k0fnLihTIH+xYgXE2IeAZKY1Ug
+FPbuBSUfzNGM68fktkQboLT6vjtU9V7um+cySpYpISaxNjuDwdQD+Vb488kP+UGh
+kjJ2HvTd3hgeZd412mk=
+=76PG
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    const RSA_ENCRYPT_ONLY_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+wsBzBAACCAAdBQJqGKUAFiEEnxsQYeOQizMt9+kLhtb6C+hT/qQACgkQhtb6C+hT
+/qRlkQgAsNFEF5R8brr1X1XxsaPIl4TRdIJVRdDtlKEtHGL7MJ99RvYTAEdI1a21
+hZXpftmqynBdxNJnobbbfj9Nmkzqsn2or5z7/Fr4CtS5HualI7ZueiVgRicwR5Rb
+kRDfE4QRzjJQXNG/He8gMEsAs3RYAGSmY8OCEeyPnWlqrEx14CLVBAcgk47FgLgz
+k9N1fnEMMrhPtDbMPhqi9CzhdOWqDImre1WrIggcBMN8Wm7OiQj4xQGkoJEtr096
+78XTIC2eG+Gmzrsicbon+LUbESySCrCUFOW93u4+YClb37AH2HbBrTxrAMmjDrx+
+5nbfuAOc+ir1Qavix4r4MEZLGD+8/Q==
+=buhZ
+-----END PGP SIGNATURE-----
+";
+
+    /// A certify-only primary key with a signing subkey
+    const SUBKEY_PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+xjMEahilABYJKwYBBAHaRw8BAQdAiojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SI
+AbQPb1zNJEZpbiBUZXN0IFN1YmtleSA8c3Via2V5QGV4YW1wbGUuY29tPsJ4BBMW
+CAAgBQJqGKUAFiEEggL20i3IyXxiAeLeRvQUftzbf8sCGwEACgkQRvQUftzbf8tu
+BAEA37G31iJtXmZ7vCShWuZuujYK8wQaK8odQb1AHzMbzgsA/RcEI5z+kO/XS/Ye
+LE2kWiCwDLg8Jeeecw3ZnkbO4rcCzjMEahilABYJKwYBBAHaRw8BAQdAgTl3Dqh9
+F19Wo1Rmw0x+zMuNipG07jeiXfYPW4/Js5TCwC8EGBYIAJcFAmoYpQAWIQSCAvbS
+LcjJfGIB4t5G9BR+3Nt/ywIbAnYgBBkWCAAdBQJqGKUAFiEE4pZGDoMrSHLmRyOW
+AAisYHb9c6wACgkQAAisYHb9c6xXewEAklU34mS0qNFObLxrU3Bxd4SnF0330fln
+ZLpaYrY2fiAA/RFrRoROWLzNmDXyY8FRJne9JfrnqG1GI12e43AIGToOAAoJEEb0
+FH7c23/LpysBAN5pZ6Hio3OCUo+6EV/+Na1idWQV8sWz1JMv4QzZEtfMAQC9noN0
+lhqKRGHCs/wkzcnFd6eKM2LGVN3kEmc/UimdDg==
+=QAL6
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    /// The same key after its subkey was revoked
+    const SUBKEY_REVOKED_PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+xjMEahilABYJKwYBBAHaRw8BAQdAiojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SI
+AbQPb1zNJEZpbiBUZXN0IFN1YmtleSA8c3Via2V5QGV4YW1wbGUuY29tPsJ4BBMW
+CAAgBQJqGKUAFiEEggL20i3IyXxiAeLeRvQUftzbf8sCGwEACgkQRvQUftzbf8tu
+BAEA37G31iJtXmZ7vCShWuZuujYK8wQaK8odQb1AHzMbzgsA/RcEI5z+kO/XS/Ye
+LE2kWiCwDLg8Jeeecw3ZnkbO4rcCzjMEahilABYJKwYBBAHaRw8BAQdAgTl3Dqh9
+F19Wo1Rmw0x+zMuNipG07jeiXfYPW4/Js5TCwC8EGBYIAJcFAmoYpQAWIQSCAvbS
+LcjJfGIB4t5G9BR+3Nt/ywIbAnYgBBkWCAAdBQJqGKUAFiEE4pZGDoMrSHLmRyOW
+AAisYHb9c6wACgkQAAisYHb9c6xXewEAklU34mS0qNFObLxrU3Bxd4SnF0330fln
+ZLpaYrY2fiAA/RFrRoROWLzNmDXyY8FRJne9JfrnqG1GI12e43AIGToOAAoJEEb0
+FH7c23/LpysBAN5pZ6Hio3OCUo+6EV/+Na1idWQV8sWz1JMv4QzZEtfMAQC9noN0
+lhqKRGHCs/wkzcnFd6eKM2LGVN3kEmc/UimdDsJ4BCgWCAAgBQJqGKV4FiEEggL2
+0i3IyXxiAeLeRvQUftzbf8sCHQAACgkQRvQUftzbf8s0BgD8Ch+cMvTdbNcFXoVT
+2semi5ZYdMcHh3R/mzzwqTl3fWoBAONOQMFdxZo+evsOLuArSnDrfU2bfImn4GjP
+dN5yxRcK
+=ix6j
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    /// The same key without the binding signature of its subkey
+    const SUBKEY_UNBOUND_PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+xjMEahilABYJKwYBBAHaRw8BAQdAiojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SI
+AbQPb1zNJEZpbiBUZXN0IFN1YmtleSA8c3Via2V5QGV4YW1wbGUuY29tPsJ4BBMW
+CAAgBQJqGKUAFiEEggL20i3IyXxiAeLeRvQUftzbf8sCGwEACgkQRvQUftzbf8tu
+BAEA37G31iJtXmZ7vCShWuZuujYK8wQaK8odQb1AHzMbzgsA/RcEI5z+kO/XS/Ye
+LE2kWiCwDLg8Jeeecw3ZnkbO4rcCzjMEahilABYJKwYBBAHaRw8BAQdAgTl3Dqh9
+F19Wo1Rmw0x+zMuNipG07jeiXfYPW4/Js5Q=
+=tU5R
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    const SUBKEY_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+wnUEABYIAB0FAmoYpTwWIQTilkYOgytIcuZHI5YACKxgdv1zrAAKCRAACKxgdv1z
+rI3VAP4/2044KG+T79fyBQHIUgGxv2wTheO3k2dKxyggqVHPjQD7B/BqHPCOZ64D
+O4SkdBs/q4dTKheZmz/amhogtDltGAQ=
+=3XlB
+-----END PGP SIGNATURE-----
+";
+
+    /// Contains a critical notation
+    const SUBKEY_CRITICAL_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+wpYEABYIAD4FAmoYpTwWIQTilkYOgytIcuZHI5YACKxgdv1zrCCUgAAAAAAUAANj
+cml0aWNhbEBleGFtcGxlLmNvbXllcwAKCRAACKxgdv1zrBOSAP9dIvtOm/ju1RR3
+Bd3kr7jjX5QFzzarmIjUKsRmLv5BwAD/Veb06iebhXRTY+GNiMsdkWNC8Pi2PkSF
+K0LHzhAO9gk=
+=3O66
+-----END PGP SIGNATURE-----
+";
+
+    const SUBKEY_NO_ISSUER_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+wlMEABYIAAYFAmoYpTwAAPDmAP46MK9KAYGdsVCxp6DjUoFUlcXp2Cmomnf1J3Eu
+lii9TwD4xMA1QFFM9EgkEZyek9KHVKegOZHSlV4qTHwUWq23AQ==
+=KnBI
+-----END PGP SIGNATURE-----
+";
+
+    /// Made by the primary key, which may only certify
+    const PRIMARY_KEY_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+wnUEABYIAB0FAmoYpTwWIQSCAvbSLcjJfGIB4t5G9BR+3Nt/ywAKCRBG9BR+3Nt/
+y4gSAP4wInvmWbWNIohrzKnu51O+zN5Gl4wsL+kIkOrxj5FvFgD+NUs/4tVpGwv1
+n/prCU1d/0o+1OBqEoE8rzR+h0lpzQo=
+=95JG
+-----END PGP SIGNATURE-----
+";
+
+    /// Expired one day after it was created
+    const EXPIRED_PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+mDMEahilABYJKwYBBAHaRw8BAQdAA91xhXmOvOfY/O66hZGuzCdsmX7DnfrTxb4m
+l/1NHGO0JkZpbiBUZXN0IEV4cGlyZWQgPGV4cGlyZWRAZXhhbXBsZS5jb20+iJYE
+ExYIAD4CGwMFCwkIBwIGFQoJCAsCBBYCAwECHgECF4AWIQTyaNaTNFV0ZwLTpT2B
+LoViE8MdpQUCahizEAUJAAFfkAAKCRCBLoViE8MdpSXzAQDfiPvob3QFuqndtFwn
+rrzU79ocuTM+v3S27eBbtxQD5AD+JwidlaWX1KTavWXnorm50l0mMbzGjt/VfUQj
+mAYKEg0=
+=ML8c
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    /// Made two days after the key was created
+    const EXPIRED_SIGNATURE: &str = "\
+-----BEGIN PGP SIGNATURE-----
+
+iIoEABYIADIWIQTyaNaTNFV0ZwLTpT2BLoViE8MdpQUCahtIABQcZXhwaXJlZEBl
+eGFtcGxlLmNvbQAKCRCBLoViE8MdpXoXAP4jHsIvNRpslB47nlKoaoKi2giX/C4y
+O6FjrbKEKQ6X9AEAge8I0d1fS/2/DdXot35ZTR1ZEWiO5WwXnjsPVfF8/AU=
+=6gnK
+-----END PGP SIGNATURE-----
+";
+
+    #[test]
+    fn minisign() {
+        let public_key = MINISIGN_PUBLIC_KEY.as_bytes();
+        let signature = MINISIGN_SIGNATURE.as_bytes();
+        assert!(verify_minisign(public_key, signature, DATA).is_ok());
+        assert!(verify_minisign(public_key, signature, b"tampered data\n").is_err());
+
+        let public_key_file =
+            format!("untrusted comment: minisign public key\n{MINISIGN_PUBLIC_KEY}\n");
+        assert!(verify_minisign(public_key_file.as_bytes(), signature, DATA).is_ok());
+    }
+
+    #[test]
+    fn pgp_ed25519() {
+        let keys = openpgp::parse_keys(ED25519_PUBLIC_KEY.as_bytes()).unwrap();
+        assert!(openpgp::verify(&keys, ED25519_SIGNATURE.as_bytes(), DATA).is_ok());
+        assert!(openpgp::verify(&keys, ED25519_TEXT_SIGNATURE.as_bytes(), DATA).is_ok());
+        assert!(openpgp::verify(&keys, ED25519_SIGNATURE.as_bytes(), b"tampered data\n").is_err());
+    }
+
+    #[test]
+    fn pgp_rsa() {
+        let keys = openpgp::parse_keys(RSA_PUBLIC_KEY.as_bytes()).unwrap();
+        assert!(openpgp::verify(&keys, RSA_SIGNATURE.as_bytes(), DATA).is_ok());
+        assert!(openpgp::verify(&keys, RSA_SIGNATURE.as_bytes(), b"tampered data\n").is_err());
+    }
+
+    #[test]
+    fn pgp_keyring() {
+        let keyring = [RSA_PUBLIC_KEY, ED25519_PUBLIC_KEY].join("\n");
+        let keys = openpgp::parse_keys(keyring.as_bytes()).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert!(openpgp::verify(&keys, RSA_SIGNATURE.as_bytes(), DATA).is_ok());
+        assert!(openpgp::verify(&keys, ED25519_SIGNATURE.as_bytes(), DATA).is_ok());
+    }
+
+    #[test]
+    fn pgp_weak_rsa_keys() {
+        let keys = openpgp::parse_keys(RSA_1024_PUBLIC_KEY.as_bytes()).unwrap();
+        let error = openpgp::verify(&keys, RSA_1024_SIGNATURE.as_bytes(), DATA).unwrap_err();
+        assert!(
+            error.ends_with("is an RSA key of less than 2048 bits"),
+            "{error}"
+        );
+
+        assert!(openpgp::parse_keys(RSA_ENCRYPT_ONLY_PUBLIC_KEY.as_bytes()).is_err());
+        let keyring = [RSA_ENCRYPT_ONLY_PUBLIC_KEY, RSA_PUBLIC_KEY].join("\n");
+        let keys = openpgp::parse_keys(keyring.as_bytes()).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert!(openpgp::verify(&keys, RSA_ENCRYPT_ONLY_SIGNATURE.as_bytes(), DATA).is_err());
+    }
+
+    #[test]
+    fn pgp_wrong_key() {
+        let keys = openpgp::parse_keys(RSA_PUBLIC_KEY.as_bytes()).unwrap();
+        assert!(openpgp::verify(&keys, ED25519_SIGNATURE.as_bytes(), DATA).is_err());
+    }
+
+    #[test]
+    fn pgp_invalid_armor() {
+        // The corrupted key no longer matches its self-signature
+        let corrupted = ED25519_PUBLIC_KEY.replace("mDMEat", "mDMEau");
+        let keys = openpgp::parse_keys(corrupted.as_bytes()).unwrap();
+        assert!(openpgp::verify(&keys, ED25519_SIGNATURE.as_bytes(), DATA).is_err());
+        assert!(openpgp::parse_keys(b"not a key").is_err());
+
+        // Certificates which cannot be parsed do not prevent using the others
+        let truncated = "-----BEGIN PGP PUBLIC KEY BLOCK-----\n\nmDMEatVBpxYJ\n-----END PGP PUBLIC KEY BLOCK-----\n";
+        let keyring = [truncated, ED25519_PUBLIC_KEY].join("\n");
+        let keys = openpgp::parse_keys(keyring.as_bytes()).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert!(openpgp::verify(&keys, ED25519_SIGNATURE.as_bytes(), DATA).is_ok());
+    }
+
+    #[test]
+    fn pgp_subkey() {
+        let keys = openpgp::parse_keys(SUBKEY_PUBLIC_KEY.as_bytes()).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert!(openpgp::verify(&keys, SUBKEY_SIGNATURE.as_bytes(), DATA).is_ok());
+        assert!(openpgp::verify(&keys, SUBKEY_SIGNATURE.as_bytes(), b"tampered data\n").is_err());
+
+        let error = openpgp::verify(&keys, PRIMARY_KEY_SIGNATURE.as_bytes(), DATA).unwrap_err();
+        assert!(error.ends_with("is not allowed to sign"), "{error}");
+    }
+
+    #[test]
+    fn pgp_invalid_subkeys() {
+        let keys = openpgp::parse_keys(SUBKEY_REVOKED_PUBLIC_KEY.as_bytes()).unwrap();
+        let error = openpgp::verify(&keys, SUBKEY_SIGNATURE.as_bytes(), DATA).unwrap_err();
+        assert!(error.ends_with("has been revoked"), "{error}");
+
+        // Subkeys without any binding signature are not read at all
+        let keys = openpgp::parse_keys(SUBKEY_UNBOUND_PUBLIC_KEY.as_bytes()).unwrap();
+        assert_eq!(keys.len(), 1);
+        let error = openpgp::verify(&keys, SUBKEY_SIGNATURE.as_bytes(), DATA).unwrap_err();
+        assert_eq!(error, "No matching public key found");
+    }
+
+    #[test]
+    fn pgp_expired_key() {
+        let keys = openpgp::parse_keys(EXPIRED_PUBLIC_KEY.as_bytes()).unwrap();
+        let error = openpgp::verify(&keys, EXPIRED_SIGNATURE.as_bytes(), DATA).unwrap_err();
+        assert!(
+            error.ends_with("had expired when the signature was made"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn pgp_invalid_signatures() {
+        let keys = openpgp::parse_keys(SUBKEY_PUBLIC_KEY.as_bytes()).unwrap();
+        assert_eq!(
+            openpgp::verify(&keys, SUBKEY_CRITICAL_SIGNATURE.as_bytes(), DATA),
+            Err("Unsupported critical signature subpacket: 20".into())
+        );
+        assert_eq!(
+            openpgp::verify(&keys, SUBKEY_NO_ISSUER_SIGNATURE.as_bytes(), DATA),
+            Err("Signature does not specify its issuer".into())
+        );
+    }
+}
@@ -0,0 +1,15 @@
+mod test_source {
+    #![cfg(test)]
+
+    use fin::source::Source;
+
+    #[test]
+    fn github_releases() {
+        assert_eq!(Source::github_release("latest"), "latest");
+        assert_eq!(Source::github_release("v1.0"), "tags/v1.0");
+        assert_eq!(Source::github_release("tags/v1.0"), "tags/v1.0");
+        // Numeric release IDs keep working as they did before tags were recorded
+        assert_eq!(Source::github_release("123456"), "123456");
+        assert_eq!(Source::github_release("tags/2024"), "tags/2024");
+    }
+}
@@ -0,0 +1,132 @@
+mod common;
+
+mod test_template {
+    #![cfg(test)]
+
+    use crate::common::TempDir;
+    use fin::installer::Installer;
+    use fin::template;
+
+    use std::fs;
+
+    use toml::Table;
+
+    const TEMPLATE: &str = r#"
+name = "$font Nerd Font"
+tags = [ "monospace", "nerd-font" ]
+
+[source.GitHub]
+author = "ryanoasis"
+project = "nerd-fonts"
+
+[action.Extract]
+file = "$archive.zip"
+include = [ "*" ]
+"#;
+
+    /// Writes the template into a new installers directory, and returns it
+    /// along with its path (ending with '/')
+    fn installer_dir(test: &str) -> (TempDir, String) {
+        let temp_dir = TempDir::new(&format!("template-{test}"));
+        let dir = format!("{}/", temp_dir.path());
+        fs::create_dir_all([dir.as_str(), template::TEMPLATES_DIR].concat()).unwrap();
+        fs::write(
+            [dir.as_str(), template::TEMPLATES_DIR, "nerd-font"].concat(),
+            TEMPLATE,
+        )
+        .unwrap();
+        (temp_dir, dir)
+    }
+
+    #[test]
+    fn installers_without_templates_are_unchanged() {
+        let contents = "name = \"Font\"\n";
+        assert_eq!(
+            template::resolve(contents, "/nonexistent/", "font").unwrap(),
+            contents
+        );
+    }
+
+    #[test]
+    fn extends_template() {
+        let (_temp_dir, dir) = installer_dir("extends");
+        let contents = r#"
+extends = "nerd-font"
+variables = { font = "JetBrains Mono", archive = "JetBrainsMono" }
+tags = [ "monospace", "ligatures" ]
+"#;
+        let resolved = template::resolve(contents, &dir, "jetbrains-mono-nf").unwrap();
+        let installer = toml::from_str::<Installer>(&resolved).unwrap();
+        assert_eq!(installer.name, "JetBrains Mono Nerd Font");
+        assert_eq!(installer.tags, ["monospace", "ligatures"]);
+        assert!(resolved.contains("JetBrainsMono.zip"));
+        assert!(!resolved.contains("extends") && !resolved.contains("variables"));
+    }
+
+    #[test]
+    fn invalid_templates() {
+        let (_temp_dir, dir) = installer_dir("invalid");
+        for (contents, error) in [
+            ("extends = \"missing\"", "Template not found: `missing`"),
+            ("extends = \"../nerd-font\"", "Template not found"),
+            (
+                "extends = \"nerd-font\"\nvariables = { tag = \"v1\" }",
+                "Variable `$tag` is reserved",
+            ),
+            (
+                "extends = \"nerd-font\"\nvariables = { font = 1 }",
+                "must be a string",
+            ),
+        ] {
+            let e = template::resolve(contents, &dir, "font").unwrap_err();
+            assert!(e.contains(error), "{e}");
+        }
+    }
+
+    #[test]
+    fn tables_are_merged() {
+        let mut base = TEMPLATE.parse::<Table>().unwrap();
+        let overrides = r#"
+[action.Extract]
+exclude = [ "*.otf" ]
+"#;
+        template::merge(&mut base, overrides.parse().unwrap());
+        let extract = &base["action"]["Extract"];
+        assert_eq!(extract["include"].as_array().unwrap().len(), 1);
+        assert_eq!(extract["exclude"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn other_variants_replace_the_template() {
+        let mut base = TEMPLATE.parse::<Table>().unwrap();
+        let overrides = r#"
+[source.Webpage]
+url = "https://www.nerdfonts.com/font-downloads"
+"#;
+        template::merge(&mut base, overrides.parse().unwrap());
+        let source = base["source"].as_table().unwrap();
+        assert!(source.contains_key("Webpage"));
+        assert!(!source.contains_key("GitHub"));
+    }
+
+    #[test]
+    fn whole_variable_names_are_substituted() {
+        let (_temp_dir, dir) = installer_dir("names");
+        let resolve = |variables: &str, description: &str| {
+            let contents = format!(
+                "extends = \"nerd-font\"\nvariables = {{ archive = \"Font\", {variables} }}\ndescription = \"{description}\"\n"
+            );
+            let resolved = template::resolve(&contents, &dir, "font").unwrap();
+            toml::from_str::<Installer>(&resolved).unwrap().description.unwrap()
+        };
+
+        assert_eq!(
+            resolve("font = \"Font\", font_file = \"File\"", "$font_file, $font"),
+            "File, Font"
+        );
+        // Neither `$t` nor `$font` are the start of a longer name
+        assert_eq!(resolve("t = \"T\"", "$tag-$t.$file"), "$tag-T.$file");
+        assert_eq!(resolve("font = \"Font\"", "$font_file"), "$font_file");
+        assert_eq!(resolve("font = \"Font\"", "$ $font$"), "$ Font$");
+    }
+}
@@ -0,0 +1,155 @@
+mod common;
+
+mod test_verify {
+    #![cfg(test)]
+
+    use std::fs;
+
+    use crate::common::TempDir;
+
+    use fin::action::Action;
+    use fin::actions::install::install_status;
+    use fin::actions::verify::VerifyAction;
+    use fin::args::Args;
+    use fin::checksum::{sha256_data, sha256_file};
+    use fin::config::Config;
+    use fin::installed::{InstalledFont, is_generated_file, list_files};
+    use fin::ownership::Ownership;
+
+    fn installed_font(dir: &str, files: &[(&str, &[u8])]) -> InstalledFont {
+        InstalledFont {
+            url: "https://example.com/font.zip".to_string(),
+            extra_urls: Vec::new(),
+            tag: None,
+            requested_tag: None,
+            dir: dir.to_owned(),
+            files: files.iter().map(|(file, _)| file.to_string()).collect(),
+            sums: files
+                .iter()
+                .map(|(file, data)| (file.to_string(), sha256_data(data)))
+                .collect(),
+            origin: None,
+            variant: None,
+            dependency: false,
+        }
+    }
+
+    #[test]
+    fn sums() {
+        let temp_dir = TempDir::new("verify-sums");
+        let dir = temp_dir.path().to_owned();
+        let path = format!("{dir}/A.ttf");
+        fs::write(&path, b"font").unwrap();
+        assert_eq!(sha256_file(&path).unwrap(), sha256_data(b"font"));
+        assert_eq!(
+            sha256_data(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert!(sha256_file(&format!("{dir}/missing.ttf")).is_err());
+    }
+
+    #[test]
+    fn files_are_listed_recursively() {
+        let temp_dir = TempDir::new("verify-list");
+        let dir = temp_dir.path().to_owned();
+        fs::create_dir_all(format!("{dir}/static/italic")).unwrap();
+        for file in ["OFL.txt", "static/A.ttf", "static/italic/A-Italic.ttf"] {
+            fs::write(format!("{dir}/{file}"), b"").unwrap();
+        }
+        assert_eq!(
+            list_files(&dir),
+            ["OFL.txt", "static/A.ttf", "static/italic/A-Italic.ttf"]
+        );
+        assert!(list_files(&format!("{dir}/missing")).is_empty());
+    }
+
+    #[test]
+    fn integrity() {
+        let ownership = Ownership::default();
+        let temp_dir = TempDir::new("verify-integrity");
+        let dir = temp_dir.path().to_owned();
+        let installed = installed_font(&dir, &[("A.ttf", b"a"), ("B.ttf", b"b"), ("C.ttf", b"c")]);
+        for (file, data) in [("A.ttf", "a"), ("B.ttf", "b"), ("C.ttf", "c")] {
+            fs::write(format!("{dir}/{file}"), data).unwrap();
+        }
+        assert!(!installed.verify(&ownership).failed());
+
+        fs::write(format!("{dir}/A.ttf"), b"changed").unwrap();
+        fs::remove_file(format!("{dir}/B.ttf")).unwrap();
+        fs::write(format!("{dir}/notes.txt"), b"").unwrap();
+        let integrity = installed.verify(&ownership);
+        assert!(integrity.failed());
+        assert_eq!(integrity.modified, ["A.ttf"]);
+        assert_eq!(integrity.missing, ["B.ttf"]);
+        assert_eq!(integrity.extra, ["notes.txt"]);
+
+        // Extra files, or files installed before sums were recorded, do not fail
+        let mut unverified = installed_font(&dir, &[("C.ttf", b"c")]);
+        unverified.sums.clear();
+        let integrity = unverified.verify(&ownership);
+        assert!(!integrity.failed());
+        assert_eq!(integrity.unverified, ["C.ttf"]);
+
+        // Files of other fonts sharing the directory are not extra
+        let mut ownership = Ownership::default();
+        ownership.insert("other", &dir, &["notes.txt".to_string()]);
+        assert!(installed.verify(&ownership).extra.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(installed.verify(&ownership).dir_missing);
+    }
+
+    #[test]
+    fn untracked_files() {
+        let temp_dir = TempDir::new("verify-untracked");
+        let dir = temp_dir.path().to_owned();
+        let font_a = installed_font(&dir, &[("A.ttf", b"a")]);
+        let font_b = installed_font(&dir, &[("B.ttf", b"b")]);
+        for file in ["A.ttf", "B.ttf", "notes.txt"] {
+            fs::write(format!("{dir}/{file}"), b"").unwrap();
+        }
+
+        // `fin doctor` only reports the files which neither font installed
+        let mut ownership = Ownership::default();
+        ownership.insert("font-a", &dir, &font_a.files);
+        ownership.insert("font-b", &dir, &font_b.files);
+        assert_eq!(font_a.untracked_files(&ownership), ["notes.txt"]);
+        assert_eq!(font_b.untracked_files(&ownership), ["notes.txt"]);
+
+        let ownership = Ownership::default();
+        assert_eq!(font_a.untracked_files(&ownership), ["B.ttf", "notes.txt"]);
+    }
+
+    #[test]
+    fn fontconfig_files_are_not_untracked() {
+        let temp_dir = TempDir::new("verify-fontconfig");
+        let dir = temp_dir.path().to_owned();
+        let installed = installed_font(&dir, &[("A.ttf", b"a"), ("static/B.ttf", b"b")]);
+        fs::create_dir_all(format!("{dir}/static")).unwrap();
+        for file in ["A.ttf", "static/B.ttf", ".uuid", "static/.uuid", "fonts.dir"] {
+            fs::write(format!("{dir}/{file}"), b"").unwrap();
+        }
+
+        let ownership = Ownership::default();
+        assert!(installed.untracked_files(&ownership).is_empty());
+        assert!(installed.verify(&ownership).extra.is_empty());
+        assert!(is_generated_file("fonts.cache-9"));
+        assert!(!is_generated_file("uuid.ttf"));
+    }
+
+    #[test]
+    fn repair_reinstalls() {
+        let (args, _) = Args::parse(
+            ["verify", "--repair", "--yes"].map(String::from),
+            Config::default(),
+        )
+        .unwrap();
+        assert_eq!(install_status(&args, &Ok(())), "installed");
+
+        let repair_args = VerifyAction::repair_args(&args);
+        assert_eq!(repair_args.action, Action::Reinstall);
+        assert!(repair_args.options.repair);
+        assert_eq!(install_status(&repair_args, &Ok(())), "reinstalled");
+        assert_eq!(install_status(&repair_args, &Err(String::new())), "failed");
+    }
+}
@@ -0,0 +1,328 @@
+mod test_woff {
+    #![cfg(test)]
+
+    use fin::woff::*;
+    use std::collections::BTreeMap;
+    use std::io::Write;
+
+    fn head() -> Vec<u8> {
+        let mut head = vec![0; 54];
+        head[0..4].copy_from_slice(&0x0001_0000_u32.to_be_bytes());
+        head[12..16].copy_from_slice(&0x5F0F_3CF5_u32.to_be_bytes());
+        head
+    }
+
+    fn hhea(num_h_metrics: u16) -> Vec<u8> {
+        let mut hhea = vec![0; 36];
+        hhea[34..36].copy_from_slice(&num_h_metrics.to_be_bytes());
+        hhea
+    }
+
+    fn maxp(num_glyphs: u16) -> Vec<u8> {
+        let mut maxp = vec![0, 0, 0x50, 0];
+        maxp.extend_from_slice(&num_glyphs.to_be_bytes());
+        maxp
+    }
+
+    fn u32_at(data: &[u8], offset: usize) -> u32 {
+        u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn checksum(data: &[u8]) -> u32 {
+        data.chunks(4).fold(0u32, |sum, chunk| {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            sum.wrapping_add(u32::from_be_bytes(word))
+        })
+    }
+
+    /// Parses the sfnt font, verifying its checksums, and returns its tables
+    fn parse_sfnt(font: &[u8]) -> BTreeMap<String, Vec<u8>> {
+        assert_eq!(checksum(font), 0xB1B0_AFBA, "Invalid `checkSumAdjustment`");
+
+        let num_tables = u16::from_be_bytes([font[4], font[5]]) as usize;
+        let mut tables = BTreeMap::new();
+        for i in 0..num_tables {
+            let record = 12 + i * 16;
+            let tag = String::from_utf8(font[record..record + 4].to_vec()).unwrap();
+            let offset = u32_at(font, record + 8) as usize;
+            let length = u32_at(font, record + 12) as usize;
+            let mut table = font[offset..offset + length].to_vec();
+            if tag == "head" {
+                table[8..12].fill(0);
+            }
+            assert_eq!(
+                checksum(&table),
+                u32_at(font, record + 4),
+                "{tag}: checksum"
+            );
+            tables.insert(tag, table);
+        }
+        tables
+    }
+
+    #[test]
+    fn decode_woff() {
+        let tables: [(&[u8; 4], Vec<u8>, bool); 3] = [
+            (b"head", head(), false),
+            (
+                b"name",
+                b"some name table contents, repeated repeated repeated".to_vec(),
+                true,
+            ),
+            (b"cmap", vec![1, 2, 3], false),
+        ];
+
+        let mut directory = Vec::new();
+        let mut data = Vec::new();
+        let data_offset = 44 + tables.len() * 20;
+        for (tag, table, compress) in &tables {
+            let stored = match compress {
+                true => {
+                    let mut encoder =
+                        flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+                    encoder.write_all(table).unwrap();
+                    encoder.finish().unwrap()
+                }
+                false => table.clone(),
+            };
+            assert!(!compress || stored.len() < table.len());
+
+            directory.extend_from_slice(*tag);
+            directory.extend_from_slice(&((data_offset + data.len()) as u32).to_be_bytes());
+            directory.extend_from_slice(&(stored.len() as u32).to_be_bytes());
+            directory.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            directory.extend_from_slice(&0u32.to_be_bytes());
+            data.extend_from_slice(&stored);
+            data.resize(data.len().next_multiple_of(4), 0);
+        }
+
+        let mut woff = b"wOFF".to_vec();
+        woff.extend_from_slice(b"OTTO");
+        woff.extend_from_slice(&((data_offset + data.len()) as u32).to_be_bytes());
+        woff.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+        woff.resize(44, 0);
+        woff.extend_from_slice(&directory);
+        woff.extend_from_slice(&data);
+
+        let (font, extension) = decode(&woff).unwrap();
+        assert_eq!(extension, "otf");
+        assert_eq!(&font[0..4], b"OTTO");
+
+        let decoded = parse_sfnt(&font);
+        assert_eq!(decoded.len(), tables.len());
+        for (tag, table, _) in &tables {
+            let tag = String::from_utf8(tag.to_vec()).unwrap();
+            if tag != "head" {
+                assert_eq!(&decoded[&tag], table, "{tag}");
+            }
+        }
+    }
+
+    /// Builds a WOFF2 font from `(flags, original length, transform length, data)`
+    /// entries, using the known table index in `flags` for every tag
+    fn build_woff2(flavor: &[u8; 4], entries: &[(u8, usize, Option<usize>, Vec<u8>)]) -> Vec<u8> {
+        let base128 = |mut value: usize| {
+            let mut bytes = vec![(value & 0x7f) as u8];
+            value >>= 7;
+            while value > 0 {
+                bytes.insert(0, (value & 0x7f) as u8 | 0x80);
+                value >>= 7;
+            }
+            bytes
+        };
+
+        let mut directory = Vec::new();
+        let mut stream = Vec::new();
+        for (flags, orig_length, transform_length, data) in entries {
+            directory.push(*flags);
+            directory.extend(base128(*orig_length));
+            if let Some(transform_length) = transform_length {
+                directory.extend(base128(*transform_length));
+            }
+            stream.extend_from_slice(data);
+        }
+
+        let mut compressed = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+            writer.write_all(&stream).unwrap();
+        }
+
+        let mut woff2 = b"wOF2".to_vec();
+        woff2.extend_from_slice(flavor);
+        woff2.extend_from_slice(&0u32.to_be_bytes());
+        woff2.extend_from_slice(&(entries.len() as u16).to_be_bytes());
+        woff2.extend_from_slice(&[0; 6]);
+        woff2.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+        woff2.resize(48, 0);
+        woff2.extend_from_slice(&directory);
+        woff2.extend_from_slice(&compressed);
+        woff2
+    }
+
+    #[test]
+    fn decode_woff2_null_transform() {
+        let cmap = vec![9, 8, 7, 6, 5];
+        let woff2 = build_woff2(
+            &[0, 1, 0, 0],
+            &[
+                (0, cmap.len(), None, cmap.clone()),
+                (1, 54, None, head()),
+                (2, 36, None, hhea(1)),
+                (4, 6, None, maxp(1)),
+            ],
+        );
+
+        let (font, extension) = decode(&woff2).unwrap();
+        assert_eq!(extension, "ttf");
+
+        let decoded = parse_sfnt(&font);
+        assert_eq!(decoded.len(), 4);
+        assert_eq!(decoded["cmap"], cmap);
+        assert_eq!(decoded["hhea"], hhea(1));
+        assert_eq!(decoded["maxp"], maxp(1));
+    }
+
+    /// Builds a transformed `glyf` table of simple glyphs from its streams
+    fn transformed_glyf(
+        n_contours: &[u8],
+        n_points: &[u8],
+        flags: &[u8],
+        glyphs: &[u8],
+    ) -> Vec<u8> {
+        let bbox_bitmap = [0; 4];
+
+        let mut glyf = Vec::new();
+        glyf.extend_from_slice(&0u16.to_be_bytes()); // reserved
+        glyf.extend_from_slice(&0u16.to_be_bytes()); // optionFlags
+        glyf.extend_from_slice(&((n_contours.len() / 2) as u16).to_be_bytes()); // numGlyphs
+        glyf.extend_from_slice(&0u16.to_be_bytes()); // indexFormat
+        for size in [
+            n_contours.len(),
+            n_points.len(),
+            flags.len(),
+            glyphs.len(),
+            0,
+            bbox_bitmap.len(),
+            0,
+        ] {
+            glyf.extend_from_slice(&(size as u32).to_be_bytes());
+        }
+        glyf.extend_from_slice(n_contours);
+        glyf.extend_from_slice(n_points);
+        glyf.extend_from_slice(flags);
+        glyf.extend_from_slice(glyphs);
+        glyf.extend_from_slice(&bbox_bitmap);
+        glyf
+    }
+
+    #[test]
+    fn decode_woff2_glyf_transform() {
+        // Glyph 0: a triangle (10,0) (110,0) (60,100), the last point off-curve
+        // Glyph 1: empty
+        let glyf = transformed_glyf(
+            &[0, 1, 0, 0],
+            &[3],
+            &[11, 11, 86 | 0x80],
+            &[10, 100, 49, 99, 0],
+        );
+
+        // Proportional left side bearings are reconstructed from `glyf`
+        let hmtx = [1, 0x01, 0xF4, 0, 0];
+
+        let woff2 = build_woff2(
+            &[0, 1, 0, 0],
+            &[
+                (1, 54, None, head()),
+                (2, 36, None, hhea(2)),
+                (4, 6, None, maxp(2)),
+                (10, 24, Some(glyf.len()), glyf),
+                (11, 6, Some(0), vec![]),
+                (3 | 0x40, 8, Some(hmtx.len()), hmtx.to_vec()),
+            ],
+        );
+
+        let (font, extension) = decode(&woff2).unwrap();
+        assert_eq!(extension, "ttf");
+
+        let decoded = parse_sfnt(&font);
+        assert_eq!(
+            decoded["glyf"],
+            [
+                0, 1, // numberOfContours
+                0, 10, 0, 0, 0, 110, 0, 100, // bounding box
+                0, 2, // endPtsOfContours
+                0, 0, // instructionLength
+                0x33, 0x33, 0x26, // flags
+                10, 100, 50,  // x coordinates
+                100, // y coordinates
+                0, 0, 0, // padding
+            ]
+        );
+        assert_eq!(decoded["loca"], [0, 0, 0, 12, 0, 12]);
+        assert_eq!(decoded["hmtx"], [0x01, 0xF4, 0, 10, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn invalid_data() {
+        assert!(decode(b"not a font").is_err());
+        assert!(decode(b"wOF2").is_err());
+        assert!(decode(&[]).is_err());
+    }
+
+    /// Builds a WOFF font of `num_tables` empty tables
+    fn empty_woff(num_tables: u16) -> Vec<u8> {
+        let mut woff = b"wOFF".to_vec();
+        woff.extend_from_slice(&[0, 1, 0, 0]);
+        woff.extend_from_slice(&0u32.to_be_bytes());
+        woff.extend_from_slice(&num_tables.to_be_bytes());
+        woff.resize(44, 0);
+        for _ in 0..num_tables {
+            woff.extend_from_slice(b"name");
+            woff.extend_from_slice(&[0; 16]);
+        }
+        woff
+    }
+
+    #[test]
+    fn malformed_fonts() {
+        assert!(decode(&empty_woff(1)).is_ok());
+        assert_eq!(
+            decode(&empty_woff(0)).unwrap_err(),
+            "WOFF font has no tables"
+        );
+        assert_eq!(decode(&empty_woff(4095)).unwrap().0.len(), 12 + 4095 * 16);
+        assert_eq!(
+            decode(&empty_woff(4096)).unwrap_err(),
+            "Font has too many tables"
+        );
+        assert_eq!(
+            decode(&build_woff2(&[0, 1, 0, 0], &[])).unwrap_err(),
+            "WOFF2 font has no tables"
+        );
+
+        // The first or a later contour of the glyph has no points
+        for n_points in [[0, 3], [3, 0]] {
+            let glyf = transformed_glyf(&[0, 2], &n_points, &[11, 11, 11], &[10, 100, 50]);
+            let woff2 = build_woff2(
+                &[0, 1, 0, 0],
+                &[
+                    (1, 54, None, head()),
+                    (4, 6, None, maxp(1)),
+                    (10, 24, Some(glyf.len()), glyf),
+                    (11, 4, Some(0), vec![]),
+                ],
+            );
+            assert_eq!(decode(&woff2).unwrap_err(), "WOFF2 contour has no points");
+        }
+    }
+
+    #[test]
+    fn web_font_extensions() {
+        assert!(is_web_font("font.woff"));
+        assert!(is_web_font("dir/Font.WOFF2"));
+        assert!(!is_web_font("font.ttf"));
+        assert!(!is_web_font("woff2"));
+    }
+}